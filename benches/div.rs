@@ -0,0 +1,97 @@
+// Benchmarks of the soft float division on the host, with `cargo bench`. The host's multiplier
+// hides most of what the Cortex-M cores spend, so numbers for thumbv7m come from counting the
+// instructions and cycles of `__divsf3` and `__divdf3` over these same operands, with the DWT cycle
+// counter on hardware or an instruction counting emulator. The operands are normal numbers spread
+// over the whole significand range, so that every entry of the reciprocal table gets used
+
+#![feature(compiler_builtins_lib)]
+#![feature(i128_type)]
+#![feature(test)]
+
+extern crate compiler_builtins;
+extern crate test;
+
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::div::{__divdf3, __divsf3, __divtf3};
+use test::{black_box, Bencher};
+
+const N: usize = 256;
+
+// A xorshift generator, so that every run divides the same numbers
+fn random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+// Returns `n` normal numbers with exponents in [-8, 8)
+fn operands<F, T>(n: usize, f: F) -> Vec<T>
+where
+    F: Fn(u64, u32) -> T,
+{
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    (0..n)
+        .map(|_| {
+            let x = random(&mut state);
+            f(x, (x >> 60) as u32)
+        })
+        .collect()
+}
+
+fn f32s() -> Vec<f32> {
+    operands(N, |x, e| f32::from_repr((x as u32 & 0x007f_ffff) | (119 + e) << 23))
+}
+
+fn f64s() -> Vec<f64> {
+    operands(N, |x, e| {
+        f64::from_repr((x & 0x000f_ffff_ffff_ffff) | ((1015 + e) as u64) << 52)
+    })
+}
+
+fn f128s() -> Vec<f128> {
+    operands(N, |x, e| {
+        let significand = (x as u128) << 48 | (x.rotate_left(32) >> 16) as u128;
+        f128::from_repr(significand | ((16375 + e) as u128) << 112)
+    })
+}
+
+#[bench]
+fn divsf3(bencher: &mut Bencher) {
+    let (a, b) = (f32s(), f32s().into_iter().rev().collect::<Vec<_>>());
+    bencher.iter(|| for (&a, &b) in a.iter().zip(&b) {
+        black_box(__divsf3(a, b));
+    });
+}
+
+#[bench]
+fn divdf3(bencher: &mut Bencher) {
+    let (a, b) = (f64s(), f64s().into_iter().rev().collect::<Vec<_>>());
+    bencher.iter(|| for (&a, &b) in a.iter().zip(&b) {
+        black_box(__divdf3(a, b));
+    });
+}
+
+#[bench]
+fn divtf3(bencher: &mut Bencher) {
+    let (a, b) = (f128s(), f128s().into_iter().rev().collect::<Vec<_>>());
+    bencher.iter(|| for (&a, &b) in a.iter().zip(&b) {
+        black_box(__divtf3(a, b));
+    });
+}
+
+#[bench]
+fn reciprocal_f32(bencher: &mut Bencher) {
+    let b = f32s();
+    bencher.iter(|| for &b in &b {
+        black_box(f32::compute_reciprocal(b.repr() & 0x007f_ffff | 0x0080_0000));
+    });
+}
+
+#[bench]
+fn reciprocal_f64(bencher: &mut Bencher) {
+    let b = f64s();
+    bencher.iter(|| for &b in &b {
+        black_box(f64::compute_reciprocal(b.repr() & 0x000f_ffff_ffff_ffff | 1 << 52));
+    });
+}
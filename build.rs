@@ -42,10 +42,12 @@ mod tests {
     extern crate cast;
     extern crate rand;
 
+    use std::cmp::{self, Ordering};
     use std::collections::HashSet;
     use std::fmt::Write;
     use std::fs::File;
     use std::hash::Hash;
+    use std::ops::{Add, Div, Mul, Neg, Sub};
     use std::path::PathBuf;
     use std::{env, mem};
 
@@ -68,38 +70,178 @@ mod tests {
             // float/add.rs
             Adddf3,
             Addsf3,
+            Addtf3,
+
+            // float/cmp.rs
+            Eqdf2,
+            Eqsf2,
+            Eqtf2,
+            Gedf2,
+            Gesf2,
+            Getf2,
+            Gtdf2,
+            Gtsf2,
+            Gttf2,
+            Ledf2,
+            Lesf2,
+            Letf2,
+            Ltdf2,
+            Ltsf2,
+            Lttf2,
+            Nedf2,
+            Nesf2,
+            Netf2,
+            Unorddf2,
+            Unordsf2,
+            Unordtf2,
+
+            // float/complex.rs
+            Divdc3,
+            Divsc3,
+            Muldc3,
+            Mulsc3,
 
             // float/conv.rs
+            ConvMatrix,
+            FixDf,
+            FixSf,
+            FixTf,
             Fixdfdi,
             Fixdfsi,
             Fixsfdi,
             Fixsfsi,
             Fixsfti,
             Fixdfti,
+            Fixtfdi,
+            Fixtfsi,
+            Fixtfti,
             Fixunsdfdi,
             Fixunsdfsi,
             Fixunssfdi,
             Fixunssfsi,
             Fixunssfti,
             Fixunsdfti,
+            Fixunstfdi,
+            Fixunstfsi,
+            Fixunstfti,
             Floatdidf,
+            Floatdisf,
+            Floatditf,
             Floatsidf,
             Floatsisf,
+            Floatsitf,
             Floattisf,
             Floattidf,
+            Floattitf,
             Floatundidf,
+            Floatunditf,
             Floatunsidf,
             Floatunsisf,
+            Floatunsitf,
             Floatuntisf,
             Floatuntidf,
+            Floatuntitf,
+
+            // float/div.rs
+            Divdf3,
+            Divsf3,
+            Divtf3,
+
+            // float/exception.rs
+            ExceptionsConv,
+            ExceptionsDf3,
+            ExceptionsSf3,
+            ExceptionsTf3,
+
+            // float/exp.rs, float/log.rs, float/pow.rs and float/trig.rs
+            MathDf,
+            MathSf,
+
+            // float/extend.rs
+            Extenddftf2,
+            Extendhfsf2,
+            Extendsfdf2,
+            Extendsftf2,
+
+            // float/flush.rs
+            FlushToZero,
+
+            // float/fma.rs
+            Fma,
+            Fmaf,
+            Fmaf128,
+
+            // float/fmod.rs
+            Fmod,
+            Fmodf,
+            Fmodf128,
+
+            // float/integral.rs
+            IntegralDf,
+            IntegralSf,
+
+            // float/minmax.rs
+            MinmaxDf,
+            MinmaxSf,
+
+            // float/mul.rs
+            Muldf3,
+            Mulsf3,
+            Multf3,
+
+            // float/nan.rs
+            NanMode,
+
+            // float/neg.rs
+            Copysign,
+            Copysignf,
+            Fabs,
+            Fabsf,
+            Negdf2,
+            Negsf2,
 
             // float/pow.rs
             Powidf2,
             Powisf2,
 
+            // float/round.rs
+            RoundingConv,
+            RoundingDf3,
+            RoundingSf3,
+            RoundingTf3,
+
+            // float/scale.rs
+            ScaleDf,
+            ScaleSf,
+
+            // float/sqrt.rs
+            Sqrt,
+            Sqrtf,
+            Sqrtf128,
+
             // float/sub.rs
             Subdf3,
             Subsf3,
+            Subtf3,
+
+            // float/trunc.rs
+            Truncdfhf2,
+            Truncdfsf2,
+            Truncsfhf2,
+            Trunctfdf2,
+            Trunctfsf2,
+
+            // float/xf.rs
+            Fixunsxfdi,
+            Fixunsxfsi,
+            Fixunsxfti,
+            Fixxfdi,
+            Fixxfti,
+            Floatdixf,
+            Floattixf,
+            Floatundixf,
+            Floatuntixf,
+            Powixf2,
 
             // int/mul.rs
             Muldi3,
@@ -187,26 +329,8 @@ mod tests {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
 use compiler_builtins::float::add::__adddf3;
 
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
-
 static TEST_CASES: &[((u64, u64), u64)] = &[
 "#
         }
@@ -274,26 +398,8 @@ fn adddf3() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
 use compiler_builtins::float::add::__addsf3;
 
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
-
 static TEST_CASES: &[((u32, u32), u32)] = &[
 "#
         }
@@ -313,6 +419,84 @@ fn addsf3() {
         }
     }
 
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Addtf3 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+    }
+
+    impl TestCase for Addtf3 {
+        fn name() -> &'static str {
+            "addtf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128(rng);
+            // Operands of similar magnitude exercise cancellation and rounding more often
+            let b = if rng.gen() {
+                gen_f128(rng)
+            } else {
+                a ^ (gen_u128(rng) >> rng.gen_range(0, 128))
+            };
+            // TODO accept NaNs. We don't do that right now because the NaN produced by the
+            // reference implementation isn't necessarily the one the intrinsic returns
+            if f128_is_nan(a) || f128_is_nan(b) {
+                return None;
+            }
+            let c = match f128_add(Round::ToNearest, a, b) {
+                Some(c) => c,
+                None => return None,
+            };
+
+            Some(
+                Addtf3 {
+                    a: a,
+                    b: b,
+                    c: c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::add::__addtf3;
+
+static TEST_CASES: &[((u128, u128), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn addtf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __addtf3(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_.repr()));
+    }
+}
+"
+        }
+    }
+
     #[derive(Eq, Hash, PartialEq)]
     pub struct Ashldi3 {
         a: u64,
@@ -546,16 +730,15 @@ fn ashrti3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Divmoddi4 {
-        a: i64,
-        b: i64,
-        c: i64,
-        rem: i64,
+    pub struct ConvMatrix {
+        op: u8,
+        a: u128,
+        b: u128,
     }
 
-    impl TestCase for Divmoddi4 {
+    impl TestCase for ConvMatrix {
         fn name() -> &'static str {
-            "divmoddi4"
+            "conv_matrix"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -563,47 +746,147 @@ fn ashrti3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i64(rng);
-            let b = gen_i64(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-            let rem = a % b;
+            // The first 18 cells convert i32, i64, i128, u32, u64 and u128 (in that order) to f32,
+            // f64 and f128, and the other 18 convert back
+            let op = rng.gen_range(0, 36u8);
+            let cell = (op % 18) as usize;
+            let (signed, bits) = (cell < 9, 32 << (cell / 3 % 3));
+            let (significand_bits, exponent_bits) = [(23, 8), (52, 11), (112, 15)][cell % 3];
+            let (a, b) = if op < 18 {
+                let a = match (signed, bits) {
+                    (true, 32) => gen_i32(rng) as i128 as u128,
+                    (true, 64) => gen_i64(rng) as i128 as u128,
+                    (true, _) => gen_i128(rng) as u128,
+                    (false, 32) => gen_i32(rng) as u32 as u128,
+                    (false, 64) => gen_u64(rng) as u128,
+                    (false, _) => gen_u128(rng),
+                };
+                let negative = signed && (a as i128) < 0;
+                let magnitude = if negative { a.wrapping_neg() } else { a };
+                let (b, _) = round_binary_exc(
+                    Round::ToNearest,
+                    negative,
+                    0,
+                    magnitude,
+                    false,
+                    0,
+                    significand_bits,
+                    exponent_bits,
+                );
+                (a, b)
+            } else {
+                // A number that is often close to the bounds of the integer types
+                let near_bound = |rng: &mut R| {
+                    let bound = *rng.choose(&[0, 31, 32, 63, 64, 127, 128]).unwrap();
+                    (rng.gen::<i64>() as f64) * 2f64.powi(bound + rng.gen_range(-8, 8) - 63)
+                };
+                // NaN is left to `fix_*`, which check its payloads
+                let (a, x) = match cell % 3 {
+                    0 => {
+                        let a = if rng.gen() { gen_f32(rng) } else { near_bound(rng) as f32 };
+                        if a.is_nan() {
+                            return None;
+                        }
+                        (to_u32(a) as u128, f128_from_f64(a as f64))
+                    }
+                    1 => {
+                        let a = if rng.gen() { gen_f64(rng) } else { near_bound(rng) };
+                        if a.is_nan() {
+                            return None;
+                        }
+                        (to_u64(a) as u128, f128_from_f64(a))
+                    }
+                    _ => {
+                        let a = if rng.gen() {
+                            gen_f128(rng)
+                        } else {
+                            f128_from_f64(near_bound(rng))
+                        };
+                        if f128_is_nan(a) {
+                            return None;
+                        }
+                        (a, a)
+                    }
+                };
+                (a, fix_result(x, signed, bits, false))
+            };
 
-            Some(Divmoddi4 { a, b, c, rem })
+            Some(ConvMatrix { op: op, a: a, b: b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {rem})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                rem = self.rem
-            )
-                    .unwrap();
+            writeln!(buffer, "(({op}, {a}), {b}),", op = self.op, a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__divmoddi4;
-
-static TEST_CASES: &[((i64, i64), (i64, i64))] = &[
-"
+            r#"
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::{__fixdfdi, __fixdfsi, __fixdfti, __fixsfdi, __fixsfsi,
+                                     __fixsfti, __fixtfdi, __fixtfsi, __fixtfti, __fixunsdfdi,
+                                     __fixunsdfsi, __fixunsdfti, __fixunssfdi, __fixunssfsi,
+                                     __fixunssfti, __fixunstfdi, __fixunstfsi, __fixunstfti,
+                                     __floatdidf, __floatdisf, __floatditf, __floatsidf,
+                                     __floatsisf, __floatsitf, __floattidf, __floattisf,
+                                     __floattitf, __floatundidf, __floatundisf, __floatunditf,
+                                     __floatunsidf, __floatunsisf, __floatunsitf, __floatuntidf,
+                                     __floatuntisf, __floatuntitf};
+
+static TEST_CASES: &[((u8, u128), u128)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
             "
 ];
 
+// Every signed and unsigned 32, 64 and 128 bit integer to and from f32, f64 and f128
 #[test]
-fn divmoddi4() {
-    for &((a, b), (c, rem)) in TEST_CASES {
-        let mut rem_ = 0;
-        let c_ = __divmoddi4(a, b, &mut rem_);
-        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+fn conv_matrix() {
+    let mut tested = [false; 36];
+    for &((op, a), b) in TEST_CASES {
+        tested[op as usize] = true;
+        let b_ = match op {
+            0 => to_u32(__floatsisf(a as i32)) as u128,
+            1 => to_u64(__floatsidf(a as i32)) as u128,
+            2 => __floatsitf(a as i32).repr(),
+            3 => to_u32(__floatdisf(a as i64)) as u128,
+            4 => to_u64(__floatdidf(a as i64)) as u128,
+            5 => __floatditf(a as i64).repr(),
+            6 => to_u32(__floattisf(a as i128)) as u128,
+            7 => to_u64(__floattidf(a as i128)) as u128,
+            8 => __floattitf(a as i128).repr(),
+            9 => to_u32(__floatunsisf(a as u32)) as u128,
+            10 => to_u64(__floatunsidf(a as u32)) as u128,
+            11 => __floatunsitf(a as u32).repr(),
+            12 => to_u32(__floatundisf(a as u64)) as u128,
+            13 => to_u64(__floatundidf(a as u64)) as u128,
+            14 => __floatunditf(a as u64).repr(),
+            15 => to_u32(__floatuntisf(a)) as u128,
+            16 => to_u64(__floatuntidf(a)) as u128,
+            17 => __floatuntitf(a).repr(),
+            18 => __fixsfsi(mk_f32(a as u32)) as i128 as u128,
+            19 => __fixdfsi(mk_f64(a as u64)) as i128 as u128,
+            20 => __fixtfsi(f128::from_repr(a)) as i128 as u128,
+            21 => __fixsfdi(mk_f32(a as u32)) as i128 as u128,
+            22 => __fixdfdi(mk_f64(a as u64)) as i128 as u128,
+            23 => __fixtfdi(f128::from_repr(a)) as i128 as u128,
+            24 => __fixsfti(mk_f32(a as u32)) as i128 as u128,
+            25 => __fixdfti(mk_f64(a as u64)) as i128 as u128,
+            26 => __fixtfti(f128::from_repr(a)) as i128 as u128,
+            27 => __fixunssfsi(mk_f32(a as u32)) as u128,
+            28 => __fixunsdfsi(mk_f64(a as u64)) as u128,
+            29 => __fixunstfsi(f128::from_repr(a)) as u128,
+            30 => __fixunssfdi(mk_f32(a as u32)) as u128,
+            31 => __fixunsdfdi(mk_f64(a as u64)) as u128,
+            32 => __fixunstfdi(f128::from_repr(a)) as u128,
+            33 => __fixunssfti(mk_f32(a as u32)),
+            34 => __fixunsdfti(mk_f64(a as u64)),
+            _ => __fixunstfti(f128::from_repr(a)),
+        };
+        assert_eq!(((op, a), b), ((op, a), b_));
+    }
+    for (op, &tested) in tested.iter().enumerate() {
+        assert!(tested, \"conversion {} of the matrix isn't tested\", op);
     }
 }
 "
@@ -611,15 +894,15 @@ fn divmoddi4() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Divdi3 {
-        a: i64,
-        b: i64,
-        c: i64,
+    pub struct Copysign {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
     }
 
-    impl TestCase for Divdi3 {
+    impl TestCase for Copysign {
         fn name() -> &'static str {
-            "divdi3"
+            "copysign"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -627,33 +910,27 @@ fn divmoddi4() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i64(rng);
-            let b = gen_i64(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-
-            Some(Divdi3 { a, b, c })
+            let a = to_u64(gen_f64(rng));
+            let b = to_u64(gen_f64(rng));
+            Some(
+                Copysign {
+                    a: a,
+                    b: b,
+                    c: (a & !(1 << 63)) | (b & (1 << 63)),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a}, {b}), {c}),", a = self.a, b = self.b, c = self.c).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__divdi3;
+            r#"
+use compiler_builtins::float::neg;
 
-static TEST_CASES: &[((i64, i64), i64)] = &[
-"
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -661,10 +938,10 @@ static TEST_CASES: &[((i64, i64), i64)] = &[
 ];
 
 #[test]
-fn divdi3() {
+fn copysign() {
     for &((a, b), c) in TEST_CASES {
-        let c_ = __divdi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+        let c_ = neg::copysign(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
     }
 }
 "
@@ -672,16 +949,15 @@ fn divdi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Divmodsi4 {
-        a: i32,
-        b: i32,
-        c: i32,
-        rem: i32,
+    pub struct Copysignf {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
     }
 
-    impl TestCase for Divmodsi4 {
+    impl TestCase for Copysignf {
         fn name() -> &'static str {
-            "divmodsi4"
+            "copysignf"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -689,35 +965,27 @@ fn divdi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i32(rng);
-            let b = gen_i32(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-            let rem = a % b;
-
-            Some(Divmodsi4 { a, b, c, rem })
+            let a = to_u32(gen_f32(rng));
+            let b = to_u32(gen_f32(rng));
+            Some(
+                Copysignf {
+                    a: a,
+                    b: b,
+                    c: (a & !(1 << 31)) | (b & (1 << 31)),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {rem})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                rem = self.rem
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a}, {b}), {c}),", a = self.a, b = self.b, c = self.c).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__divmodsi4;
+            r#"
+use compiler_builtins::float::neg;
 
-static TEST_CASES: &[((i32, i32), (i32, i32))] = &[
-"
+static TEST_CASES: &[((u32, u32), u32)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -725,11 +993,10 @@ static TEST_CASES: &[((i32, i32), (i32, i32))] = &[
 ];
 
 #[test]
-fn divmodsi4() {
-    for &((a, b), (c, rem)) in TEST_CASES {
-        let mut rem_ = 0;
-        let c_ = __divmodsi4(a, b, &mut rem_);
-        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+fn copysignf() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = neg::copysignf(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
     }
 }
 "
@@ -737,15 +1004,18 @@ fn divmodsi4() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Divsi3 {
-        a: i32,
-        b: i32,
-        c: i32,
+    pub struct Divdc3 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+        d: u64,  // f64
+        e: u64,  // f64, real part
+        f: u64,  // f64, imaginary part
     }
 
-    impl TestCase for Divsi3 {
+    impl TestCase for Divdc3 {
         fn name() -> &'static str {
-            "divsi3"
+            "divdc3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -753,33 +1023,51 @@ fn divmodsi4() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i32(rng);
-            let b = gen_i32(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
+            let a = gen_complex_f64(rng);
+            let b = gen_complex_f64(rng);
+            let c = gen_complex_f64(rng);
+            let d = gen_complex_f64(rng);
+            let (e, f) = divc3(a, b, c, d);
 
-            Some(Divsi3 { a, b, c })
+            Some(
+                Divdc3 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c: to_u64(c),
+                    d: to_u64(d),
+                    e: to_u64(e),
+                    f: to_u64(f),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
             writeln!(
                 buffer,
-                "(({a}, {b}), {c}),",
+                "(({a}, {b}, {c}, {d}), ({e}, {f})),",
                 a = self.a,
                 b = self.b,
-                c = self.c
+                c = self.c,
+                d = self.d,
+                e = self.e,
+                f = self.f
             )
                     .unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__divsi3;
+            r#"
+use compiler_builtins::float::complex::__divdc3;
 
-static TEST_CASES: &[((i32, i32), i32)] = &[
-"
+// Equal representations, or both NaN
+fn same(x: u64, y: u64) -> bool {
+    let abs_mask = !0 >> 1;
+    let inf = 0x7ff0_0000_0000_0000;
+    x == y || (x & abs_mask > inf && y & abs_mask > inf)
+}
+
+static TEST_CASES: &[((u64, u64, u64, u64), (u64, u64))] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -787,10 +1075,13 @@ static TEST_CASES: &[((i32, i32), i32)] = &[
 ];
 
 #[test]
-fn divsi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __divsi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn divdc3() {
+    for &((a, b, c, d), (e, f)) in TEST_CASES {
+        let z = __divdc3(mk_f64(a), mk_f64(b), mk_f64(c), mk_f64(d));
+        let (e_, f_) = (to_u64(z.re), to_u64(z.im));
+        if !same(e, e_) || !same(f, f_) {
+            assert_eq!(((a, b, c, d), (e, f)), ((a, b, c, d), (e_, f_)));
+        }
     }
 }
 "
@@ -798,15 +1089,15 @@ fn divsi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Divti3 {
-        a: i128,
-        b: i128,
-        c: i128,
+    pub struct Divdf3 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
     }
 
-    impl TestCase for Divti3 {
+    impl TestCase for Divdf3 {
         fn name() -> &'static str {
-            "divti3"
+            "divdf3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -814,14 +1105,39 @@ fn divsi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i128(rng);
-            let b = gen_i128(rng);
-            if b == 0 {
+            // Besides random operands, some that the original port got wrong: x / 0, x / inf,
+            // quotients below the normal range, which it flushed to zero, and divisors close to 2,
+            // for which its reciprocal estimate wrapped around
+            const REGRESSIONS: &[(u64, u64)] = &[
+                (0x3ff0_0000_0000_0000, 0x0000_0000_0000_0000),
+                (0xbff0_0000_0000_0000, 0x7ff0_0000_0000_0000),
+                (0x0010_0000_0000_0000, 0x4008_0000_0000_0000),
+                (0x0010_0000_0000_0001, 0x4000_0000_0000_0000),
+                (0x0000_0000_0000_0001, 0x4000_0000_0000_0000),
+                (0x0000_0000_0000_0003, 0x4000_0000_0000_0000),
+                (0x3ff0_0000_0000_0000, 0x3fff_ffff_ffff_ffff),
+            ];
+
+            let (a, b) = if rng.gen_weighted_bool(10) {
+                let &(a, b) = rng.choose(REGRESSIONS).unwrap();
+                (mk_f64(a), mk_f64(b))
+            } else {
+                (gen_f64(rng), gen_f64(rng))
+            };
+            let c = a / b;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() || b.is_nan() || c.is_nan() {
                 return None;
             }
-            let c = a / b;
 
-            Some(Divti3 { a, b, c })
+            Some(
+                Divdf3 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c: to_u64(c),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
@@ -836,11 +1152,11 @@ fn divsi3() {
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__divti3;
+            r#"
+use compiler_builtins::float::div::__divdf3;
 
-static TEST_CASES: &[((i128, i128), i128)] = &[
-"
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -848,10 +1164,10 @@ static TEST_CASES: &[((i128, i128), i128)] = &[
 ];
 
 #[test]
-fn divti3() {
+fn divdf3() {
     for &((a, b), c) in TEST_CASES {
-        let c_ = __divti3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+        let c_ = __divdf3(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
     }
 }
 "
@@ -859,14 +1175,16 @@ fn divti3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixdfdi {
-        a: u64,  // f64
+    pub struct Divmoddi4 {
+        a: i64,
         b: i64,
+        c: i64,
+        rem: i64,
     }
 
-    impl TestCase for Fixdfdi {
+    impl TestCase for Divmoddi4 {
         fn name() -> &'static str {
-            "fixdfdi"
+            "divmoddi4"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -874,34 +1192,35 @@ fn divti3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            i64(a).ok().map(|b| Fixdfdi { a: to_u64(a), b })
+            let a = gen_i64(rng);
+            let b = gen_i64(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+            let rem = a % b;
+
+            Some(Divmoddi4 { a, b, c, rem })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {rem})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                rem = self.rem
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixdfdi;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::int::sdiv::__divmoddi4;
 
-static TEST_CASES: &[((u64,), i64)] = &[
-"#
+static TEST_CASES: &[((i64, i64), (i64, i64))] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -909,10 +1228,11 @@ static TEST_CASES: &[((u64,), i64)] = &[
 ];
 
 #[test]
-fn fixdfdi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixdfdi(mk_f64(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divmoddi4() {
+    for &((a, b), (c, rem)) in TEST_CASES {
+        let mut rem_ = 0;
+        let c_ = __divmoddi4(a, b, &mut rem_);
+        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
     }
 }
 "
@@ -920,14 +1240,15 @@ fn fixdfdi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixdfsi {
-        a: u64,  // f64
-        b: i32,
+    pub struct Divdi3 {
+        a: i64,
+        b: i64,
+        c: i64,
     }
 
-    impl TestCase for Fixdfsi {
+    impl TestCase for Divdi3 {
         fn name() -> &'static str {
-            "fixdfsi"
+            "divdi3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -935,34 +1256,33 @@ fn fixdfdi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            i32(a).ok().map(|b| Fixdfsi { a: to_u64(a), b })
+            let a = gen_i64(rng);
+            let b = gen_i64(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+
+            Some(Divdi3 { a, b, c })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixdfsi;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::int::sdiv::__divdi3;
 
-static TEST_CASES: &[((u64,), i32)] = &[
-"#
+static TEST_CASES: &[((i64, i64), i64)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -970,10 +1290,10 @@ static TEST_CASES: &[((u64,), i32)] = &[
 ];
 
 #[test]
-fn fixdfdi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixdfsi(mk_f64(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divdi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __divdi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
     }
 }
 "
@@ -981,14 +1301,16 @@ fn fixdfdi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixsfdi {
-        a: u32,  // f32
-        b: i64,
+    pub struct Divmodsi4 {
+        a: i32,
+        b: i32,
+        c: i32,
+        rem: i32,
     }
 
-    impl TestCase for Fixsfdi {
+    impl TestCase for Divmodsi4 {
         fn name() -> &'static str {
-            "fixsfdi"
+            "divmodsi4"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -996,34 +1318,35 @@ fn fixdfdi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f32(rng);
-            i64(a).ok().map(|b| Fixsfdi { a: to_u32(a), b })
+            let a = gen_i32(rng);
+            let b = gen_i32(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+            let rem = a % b;
+
+            Some(Divmodsi4 { a, b, c, rem })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {rem})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                rem = self.rem
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixsfdi;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::int::sdiv::__divmodsi4;
 
-static TEST_CASES: &[((u32,), i64)] = &[
-"#
+static TEST_CASES: &[((i32, i32), (i32, i32))] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -1031,10 +1354,11 @@ static TEST_CASES: &[((u32,), i64)] = &[
 ];
 
 #[test]
-fn fixsfdi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixsfdi(mk_f32(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divmodsi4() {
+    for &((a, b), (c, rem)) in TEST_CASES {
+        let mut rem_ = 0;
+        let c_ = __divmodsi4(a, b, &mut rem_);
+        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
     }
 }
 "
@@ -1042,14 +1366,18 @@ fn fixsfdi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixsfsi {
+    pub struct Divsc3 {
         a: u32,  // f32
-        b: i32,
+        b: u32,  // f32
+        c: u32,  // f32
+        d: u32,  // f32
+        e: u32,  // f32, real part
+        f: u32,  // f32, imaginary part
     }
 
-    impl TestCase for Fixsfsi {
+    impl TestCase for Divsc3 {
         fn name() -> &'static str {
-            "fixsfsi"
+            "divsc3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1057,33 +1385,50 @@ fn fixsfdi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f32(rng);
-            i32(a).ok().map(|b| Fixsfsi { a: to_u32(a), b })
+            let a = gen_complex_f32(rng);
+            let b = gen_complex_f32(rng);
+            let c = gen_complex_f32(rng);
+            let d = gen_complex_f32(rng);
+            let (e, f) = divc3(a, b, c, d);
+
+            Some(
+                Divsc3 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c: to_u32(c),
+                    d: to_u32(d),
+                    e: to_u32(e),
+                    f: to_u32(f),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}, {c}, {d}), ({e}, {f})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                d = self.d,
+                e = self.e,
+                f = self.f
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixsfsi;
+use compiler_builtins::float::complex::__divsc3;
 
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
+// Equal representations, or both NaN
+fn same(x: u32, y: u32) -> bool {
+    let abs_mask = !0 >> 1;
+    let inf = 0x7f80_0000;
+    x == y || (x & abs_mask > inf && y & abs_mask > inf)
 }
 
-static TEST_CASES: &[((u32,), i32)] = &[
+static TEST_CASES: &[((u32, u32, u32, u32), (u32, u32))] = &[
 "#
         }
 
@@ -1092,10 +1437,13 @@ static TEST_CASES: &[((u32,), i32)] = &[
 ];
 
 #[test]
-fn fixsfsi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixsfsi(mk_f32(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divsc3() {
+    for &((a, b, c, d), (e, f)) in TEST_CASES {
+        let z = __divsc3(mk_f32(a), mk_f32(b), mk_f32(c), mk_f32(d));
+        let (e_, f_) = (to_u32(z.re), to_u32(z.im));
+        if !same(e, e_) || !same(f, f_) {
+            assert_eq!(((a, b, c, d), (e, f)), ((a, b, c, d), (e_, f_)));
+        }
     }
 }
 "
@@ -1103,14 +1451,15 @@ fn fixsfsi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixsfti {
+    pub struct Divsf3 {
         a: u32,  // f32
-        b: i128,
+        b: u32,  // f32
+        c: u32,  // f32
     }
 
-    impl TestCase for Fixsfti {
+    impl TestCase for Divsf3 {
         fn name() -> &'static str {
-            "fixsfti"
+            "divsf3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1118,33 +1467,57 @@ fn fixsfsi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f32(rng);
-            i128(a).ok().map(|b| Fixsfti { a: to_u32(a), b })
+            // Besides random operands, some that the original port got wrong: x / 0, x / inf,
+            // quotients below the normal range, which it flushed to zero, and divisors close to 2,
+            // for which its reciprocal estimate wrapped around
+            const REGRESSIONS: &[(u32, u32)] = &[
+                (0x3f80_0000, 0x0000_0000),
+                (0xbf80_0000, 0x7f80_0000),
+                (0x0080_0000, 0x4040_0000),
+                (0x0080_0001, 0x4000_0000),
+                (0x0000_0001, 0x4000_0000),
+                (0x0000_0003, 0x4000_0000),
+                (0x3f80_0000, 0x3fff_ffff),
+            ];
+
+            let (a, b) = if rng.gen_weighted_bool(10) {
+                let &(a, b) = rng.choose(REGRESSIONS).unwrap();
+                (mk_f32(a), mk_f32(b))
+            } else {
+                (gen_f32(rng), gen_f32(rng))
+            };
+            let c = a / b;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() || b.is_nan() || c.is_nan() {
+                return None;
+            }
+
+            Some(
+                Divsf3 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c: to_u32(c),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixsfti;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::div::__divsf3;
 
-static TEST_CASES: &[((u32,), i128)] = &[
+static TEST_CASES: &[((u32, u32), u32)] = &[
 "#
         }
 
@@ -1153,10 +1526,10 @@ static TEST_CASES: &[((u32,), i128)] = &[
 ];
 
 #[test]
-fn fixsfti() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixsfti(mk_f32(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divsf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __divsf3(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
     }
 }
 "
@@ -1164,14 +1537,15 @@ fn fixsfti() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixdfti {
-        a: u64,  // f64
-        b: i128,
+    pub struct Divsi3 {
+        a: i32,
+        b: i32,
+        c: i32,
     }
 
-    impl TestCase for Fixdfti {
+    impl TestCase for Divsi3 {
         fn name() -> &'static str {
-            "fixdfti"
+            "divsi3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1179,34 +1553,33 @@ fn fixsfti() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            i128(a).ok().map(|b| Fixdfti { a: to_u64(a), b })
+            let a = gen_i32(rng);
+            let b = gen_i32(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+
+            Some(Divsi3 { a, b, c })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixdfti;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::int::sdiv::__divsi3;
 
-static TEST_CASES: &[((u64,), i128)] = &[
-"#
+static TEST_CASES: &[((i32, i32), i32)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -1214,10 +1587,10 @@ static TEST_CASES: &[((u64,), i128)] = &[
 ];
 
 #[test]
-fn fixdfti() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixdfti(mk_f64(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divsi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __divsi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
     }
 }
 "
@@ -1225,14 +1598,15 @@ fn fixdfti() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixunsdfdi {
-        a: u64,  // f64
-        b: u64,
+    pub struct Divtf3 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
     }
 
-    impl TestCase for Fixunsdfdi {
+    impl TestCase for Divtf3 {
         fn name() -> &'static str {
-            "fixunsdfdi"
+            "divtf3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1240,34 +1614,50 @@ fn fixdfti() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            u64(a).ok().map(|b| Fixunsdfdi { a: to_u64(a), b })
+            let a = gen_f128(rng);
+            // Operands of similar magnitude exercise cancellation and rounding more often
+            let b = if rng.gen() {
+                gen_f128(rng)
+            } else {
+                a ^ (gen_u128(rng) >> rng.gen_range(0, 128))
+            };
+            // TODO accept NaNs. We don't do that right now because the NaN produced by the
+            // reference implementation isn't necessarily the one the intrinsic returns
+            if f128_is_nan(a) || f128_is_nan(b) {
+                return None;
+            }
+            let c = match f128_div(Round::ToNearest, a, b) {
+                Some(c) => c,
+                None => return None,
+            };
+
+            Some(
+                Divtf3 {
+                    a: a,
+                    b: b,
+                    c: c,
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixunsdfdi;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::div::__divtf3;
 
-static TEST_CASES: &[((u64,), u64)] = &[
-"#
+static TEST_CASES: &[((u128, u128), u128)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -1275,10 +1665,10 @@ static TEST_CASES: &[((u64,), u64)] = &[
 ];
 
 #[test]
-fn fixunsdfdi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixunsdfdi(mk_f64(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divtf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __divtf3(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_.repr()));
     }
 }
 "
@@ -1286,14 +1676,15 @@ fn fixunsdfdi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixunsdfsi {
-        a: u64,  // f64
-        b: u32,
+    pub struct Divti3 {
+        a: i128,
+        b: i128,
+        c: i128,
     }
 
-    impl TestCase for Fixunsdfsi {
+    impl TestCase for Divti3 {
         fn name() -> &'static str {
-            "fixunsdfsi"
+            "divti3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1301,34 +1692,33 @@ fn fixunsdfdi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            u32(a).ok().map(|b| Fixunsdfsi { a: to_u64(a), b })
+            let a = gen_i128(rng);
+            let b = gen_i128(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+
+            Some(Divti3 { a, b, c })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixunsdfsi;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::int::sdiv::__divti3;
 
-static TEST_CASES: &[((u64,), u32)] = &[
-"#
+static TEST_CASES: &[((i128, i128), i128)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -1336,10 +1726,10 @@ static TEST_CASES: &[((u64,), u32)] = &[
 ];
 
 #[test]
-fn fixunsdfdi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixunsdfsi(mk_f64(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn divti3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __divti3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
     }
 }
 "
@@ -1347,14 +1737,15 @@ fn fixunsdfdi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixunssfdi {
-        a: u32,  // f32
-        b: u64,
+    pub struct Eqdf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
     }
 
-    impl TestCase for Fixunssfdi {
+    impl TestCase for Eqdf2 {
         fn name() -> &'static str {
-            "fixunssfdi"
+            "eqdf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1362,33 +1753,39 @@ fn fixunsdfdi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f32(rng);
-            u64(a).ok().map(|b| Fixunssfdi { a: to_u32(a), b })
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Eqdf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixunssfdi;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::cmp::__eqdf2;
 
-static TEST_CASES: &[((u32,), u64)] = &[
+static TEST_CASES: &[((u64, u64), i32)] = &[
 "#
         }
 
@@ -1397,10 +1794,10 @@ static TEST_CASES: &[((u32,), u64)] = &[
 ];
 
 #[test]
-fn fixunssfdi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixunssfdi(mk_f32(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn eqdf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __eqdf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
     }
 }
 "
@@ -1408,14 +1805,15 @@ fn fixunssfdi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixunssfsi {
+    pub struct Eqsf2 {
         a: u32,  // f32
-        b: u32,
+        b: u32,  // f32
+        c: i32,
     }
 
-    impl TestCase for Fixunssfsi {
+    impl TestCase for Eqsf2 {
         fn name() -> &'static str {
-            "fixunssfsi"
+            "eqsf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1424,32 +1822,38 @@ fn fixunssfdi() {
             Self: Sized,
         {
             let a = gen_f32(rng);
-            u32(a).ok().map(|b| Fixunssfsi { a: to_u32(a), b })
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Eqsf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixunssfsi;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::cmp::__eqsf2;
 
-static TEST_CASES: &[((u32,), u32)] = &[
+static TEST_CASES: &[((u32, u32), i32)] = &[
 "#
         }
 
@@ -1458,10 +1862,10 @@ static TEST_CASES: &[((u32,), u32)] = &[
 ];
 
 #[test]
-fn fixunssfsi() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixunssfsi(mk_f32(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn eqsf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __eqsf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
     }
 }
 "
@@ -1469,14 +1873,15 @@ fn fixunssfsi() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixunssfti {
-        a: u32,  // f32
-        b: u128,
+    pub struct Eqtf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
     }
 
-    impl TestCase for Fixunssfti {
+    impl TestCase for Eqtf2 {
         fn name() -> &'static str {
-            "fixunssfti"
+            "eqtf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1484,34 +1889,41 @@ fn fixunssfsi() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f32(rng);
-            u128(a).ok().map(|b| Fixunssfti { a: to_u32(a), b })
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = cmp_le_abi(F128(a), F128(b));
+
+            Some(
+                Eqtf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixunssfti;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__eqtf2;
 
-static TEST_CASES: &[((u32,), u128)] = &[
-"#
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -1519,10 +1931,10 @@ static TEST_CASES: &[((u32,), u128)] = &[
 ];
 
 #[test]
-fn fixunssfti() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixunssfti(mk_f32(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn eqtf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __eqtf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
     }
 }
 "
@@ -1530,14 +1942,17 @@ fn fixunssfti() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Fixunsdfti  {
-        a: u64,  // f64
+    pub struct ExceptionsConv {
+        op: u8,
+        mode: i32,
+        a: u128,
         b: u128,
+        flags: i32,
     }
 
-    impl TestCase for Fixunsdfti {
+    impl TestCase for ExceptionsConv {
         fn name() -> &'static str {
-            "fixunsdfti"
+            "exceptions_conv"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1545,33 +1960,127 @@ fn fixunssfti() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            u128(a).ok().map(|b| Fixunsdfti { a: to_u64(a), b })
+            // A number that is often close to the bounds of the integer types
+            let mut gen_fix = |rng: &mut R| if rng.gen() {
+                gen_f64(rng)
+            } else {
+                let bound = *rng.choose(&[0, 31, 32, 63, 64, 127, 128]).unwrap();
+                (rng.gen::<i64>() as f64) * 2f64.powi(bound + rng.gen_range(-8, 8) - 63)
+            };
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 8);
+            let (a, (b, flags)) = match op {
+                0 => {
+                    let a = gen_u128(rng);
+                    (a, round_binary_exc(mode, false, 0, a, false, 0, 23, 8))
+                }
+                1 | 2 => {
+                    let a = if op == 1 {
+                        gen_i128(rng)
+                    } else {
+                        gen_i32(rng) as i128
+                    };
+                    let magnitude = if a < 0 { (a as u128).wrapping_neg() } else { a as u128 };
+                    let (significand_bits, exponent_bits) = if op == 1 {
+                        (52, 11)
+                    } else {
+                        (23, 8)
+                    };
+                    let b = round_binary_exc(
+                        mode,
+                        a < 0,
+                        0,
+                        magnitude,
+                        false,
+                        0,
+                        significand_bits,
+                        exponent_bits,
+                    );
+                    (a as u128, b)
+                }
+                3 => {
+                    let a = gen_fix(rng) as f32;
+                    (to_u32(a) as u128, (0, fix_exc(f128_from_f64(a as f64), true, 32)))
+                }
+                4 | 5 => {
+                    let a = gen_fix(rng);
+                    let flags = if a.is_nan() {
+                        FE_INVALID
+                    } else if op == 4 {
+                        fix_exc(f128_from_f64(a), false, 64)
+                    } else {
+                        fix_exc(f128_from_f64(a), true, 128)
+                    };
+                    (to_u64(a) as u128, (0, flags))
+                }
+                6 => {
+                    let a = if rng.gen_weighted_bool(16) {
+                        0x7ff0_0000_0000_0000 | rng.gen_range(1, 1 << 51)
+                    } else {
+                        to_u64(gen_f64(rng))
+                    };
+                    let x = mk_f64(a);
+                    let b = if x.is_nan() {
+                        let invalid = a & (1 << 51) == 0;
+                        (0x7fc0_0000, if invalid { FE_INVALID } else { 0 })
+                    } else if x.is_infinite() {
+                        (to_u32(x as f32) as u128, 0)
+                    } else {
+                        let (sign, m, e) = f128_parts(f128_from_f64(x));
+                        round_binary_exc(mode, sign, 0, m, false, e, 23, 8)
+                    };
+                    (a as u128, b)
+                }
+                _ => {
+                    let a = if rng.gen_weighted_bool(16) {
+                        0x7f80_0000 | rng.gen_range(1, 1 << 22)
+                    } else {
+                        to_u32(gen_f32(rng))
+                    };
+                    let b = if mk_f32(a).is_nan() {
+                        let invalid = a & (1 << 22) == 0;
+                        (0x7ff8 << 48, if invalid { FE_INVALID } else { 0 })
+                    } else {
+                        (to_u64(mk_f32(a) as f64) as u128, 0)
+                    };
+                    (a as u128, b)
+                }
+            };
+
+            Some(
+                ExceptionsConv {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    flags: flags,
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}), ({b}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__fixunsdfti;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
-
-static TEST_CASES: &[((u64,), u128)] = &[
+use compiler_builtins::float::conv::{__fixdfti, __fixsfsi, __fixunsdfdi, __floatsisf,
+                                     __floattidf, __floatuntisf};
+use compiler_builtins::float::exception;
+use compiler_builtins::float::extend::__extendsfdf2;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::trunc::__truncdfsf2;
+
+static TEST_CASES: &[((u8, i32, u128), (u128, i32))] = &[
 "#
         }
 
@@ -1580,25 +2089,58 @@ static TEST_CASES: &[((u64,), u128)] = &[
 ];
 
 #[test]
-fn fixunsdfti() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __fixunsdfti(mk_f64(a));
-        assert_eq!(((a,), b), ((a,), b_));
+fn exceptions_conv() {
+    for &((op, mode, a), (b, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        // The integer results of the `__fix*` conversions aren't checked here
+        let b_ = match op {
+            0 => to_u32(__floatuntisf(a)) as u128,
+            1 => to_u64(__floattidf(a as i128)) as u128,
+            2 => to_u32(__floatsisf(a as i32)) as u128,
+            3 => {
+                __fixsfsi(mk_f32(a as u32));
+                0
+            }
+            4 => {
+                __fixunsdfdi(mk_f64(a as u64));
+                0
+            }
+            5 => {
+                __fixdfti(mk_f64(a as u64));
+                0
+            }
+            6 => {
+                let b_ = __truncdfsf2(mk_f64(a as u64));
+                if b_.is_nan() && mk_f32(b as u32).is_nan() { b } else { to_u32(b_) as u128 }
+            }
+            _ => {
+                let b_ = __extendsfdf2(mk_f32(a as u32));
+                if b_.is_nan() && mk_f64(b as u64).is_nan() { b } else { to_u64(b_) as u128 }
+            }
+        };
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, mode, a), (b, flags)), ((op, mode, a), (b_, flags_)));
     }
+    round::set_mode(Round::ToNearest);
 }
 "
         }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatdidf {
-        a: i64,
-        b: u64, // f64
+    pub struct ExceptionsDf3 {
+        op: u8,
+        mode: i32,
+        a: u64,  // f64
+        b: u64,  // f64, or the `i32` exponent of `__powidf2`
+        c: u64,  // f64
+        flags: i32,
     }
 
-    impl TestCase for Floatdidf {
+    impl TestCase for ExceptionsDf3 {
         fn name() -> &'static str {
-            "floatdidf"
+            "exceptions_df3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1606,38 +2148,61 @@ fn fixunsdfti() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i64(rng);
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 5);
+            let a = gen_f64_exc(rng, false);
+            let b = if op == 4 {
+                if mk_f64(a).is_nan() {
+                    return None;
+                }
+                rng.gen_range(-40i32, 40) as u32 as u64
+            } else if rng.gen_weighted_bool(8) {
+                *rng.choose(&[a, a ^ (1 << 63)]).unwrap()
+            } else {
+                gen_f64_exc(rng, false)
+            };
+            let (c, mut flags) = arith_exc(mode, op, a as u128, b as u128, 52, 11);
+            if op == 4 && !soft_powi() {
+                flags = -1;
+            }
+
             Some(
-                Floatdidf {
-                    a,
-                    b: to_u64(f64(a)),
+                ExceptionsDf3 {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c as u64,
+                    flags: flags,
                 },
             )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), ({c}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatdidf;
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::add::__adddf3;
+use compiler_builtins::float::div::__divdf3;
+use compiler_builtins::float::exception;
+use compiler_builtins::float::mul::__muldf3;
+use compiler_builtins::float::pow::__powidf2;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::__subdf3;
 
-static TEST_CASES: &[((i64,), u64)] = &[
+static TEST_CASES: &[((u8, i32, u64, u64), (u64, i32))] = &[
 "#
         }
 
@@ -1646,25 +2211,43 @@ static TEST_CASES: &[((i64,), u64)] = &[
 ];
 
 #[test]
-fn floatdidf() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __floatdidf(a);
-        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+fn exceptions_df3() {
+    for &((op, mode, a, b), (c, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let (x, y) = (mk_f64(a), mk_f64(b));
+        let c_ = match op {
+            0 => __adddf3(x, y),
+            1 => __subdf3(x, y),
+            2 => __muldf3(x, y),
+            3 => __divdf3(x, y),
+            _ => __powidf2(x, b as i32),
+        };
+        // The flags are -1, and not checked, where `__powidf2` uses the FPU
+        let flags_ = if flags == -1 { flags } else { exception::test(exception::ALL) };
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if c_.is_nan() && mk_f64(c).is_nan() { c } else { to_u64(c_) };
+        assert_eq!(((op, mode, a, b), (c, flags)), ((op, mode, a, b), (c_, flags_)));
     }
+    round::set_mode(Round::ToNearest);
 }
 "
         }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatsidf {
-        a: i32,
-        b: u64, // f64
+    pub struct ExceptionsSf3 {
+        op: u8,
+        mode: i32,
+        a: u32,  // f32
+        b: u32,  // f32, or the `i32` exponent of `__powisf2`
+        c: u32,  // f32
+        flags: i32,
     }
 
-    impl TestCase for Floatsidf {
+    impl TestCase for ExceptionsSf3 {
         fn name() -> &'static str {
-            "floatsidf"
+            "exceptions_sf3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1672,38 +2255,61 @@ fn floatdidf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i32(rng);
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 5);
+            let a = gen_f32_exc(rng, false);
+            let b = if op == 4 {
+                if mk_f32(a).is_nan() {
+                    return None;
+                }
+                rng.gen_range(-40i32, 40) as u32
+            } else if rng.gen_weighted_bool(8) {
+                *rng.choose(&[a, a ^ (1 << 31)]).unwrap()
+            } else {
+                gen_f32_exc(rng, false)
+            };
+            let (c, mut flags) = arith_exc(mode, op, a as u128, b as u128, 23, 8);
+            if op == 4 && !soft_powi() {
+                flags = -1;
+            }
+
             Some(
-                Floatsidf {
-                    a,
-                    b: to_u64(f64(a)),
+                ExceptionsSf3 {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c as u32,
+                    flags: flags,
                 },
             )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), ({c}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatsidf;
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::add::__addsf3;
+use compiler_builtins::float::div::__divsf3;
+use compiler_builtins::float::exception;
+use compiler_builtins::float::mul::__mulsf3;
+use compiler_builtins::float::pow::__powisf2;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::__subsf3;
 
-static TEST_CASES: &[((i32,), u64)] = &[
+static TEST_CASES: &[((u8, i32, u32, u32), (u32, i32))] = &[
 "#
         }
 
@@ -1712,25 +2318,43 @@ static TEST_CASES: &[((i32,), u64)] = &[
 ];
 
 #[test]
-fn floatsidf() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __floatsidf(a);
-        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+fn exceptions_sf3() {
+    for &((op, mode, a, b), (c, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let (x, y) = (mk_f32(a), mk_f32(b));
+        let c_ = match op {
+            0 => __addsf3(x, y),
+            1 => __subsf3(x, y),
+            2 => __mulsf3(x, y),
+            3 => __divsf3(x, y),
+            _ => __powisf2(x, b as i32),
+        };
+        // The flags are -1, and not checked, where `__powisf2` uses the FPU
+        let flags_ = if flags == -1 { flags } else { exception::test(exception::ALL) };
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if c_.is_nan() && mk_f32(c).is_nan() { c } else { to_u32(c_) };
+        assert_eq!(((op, mode, a, b), (c, flags)), ((op, mode, a, b), (c_, flags_)));
     }
+    round::set_mode(Round::ToNearest);
 }
 "
         }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatsisf {
-        a: i32,
-        b: u32, // f32
+    pub struct ExceptionsTf3 {
+        op: u8,
+        mode: i32,
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+        flags: i32,
     }
 
-    impl TestCase for Floatsisf {
+    impl TestCase for ExceptionsTf3 {
         fn name() -> &'static str {
-            "floatsisf"
+            "exceptions_tf3"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1738,39 +2362,54 @@ fn floatsidf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i32(rng);
+            let a = gen_f128(rng);
+            let b = if rng.gen() {
+                gen_f128(rng)
+            } else {
+                a ^ (gen_u128(rng) >> rng.gen_range(0, 128))
+            };
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 4);
+            let (c, flags) = arith_exc(mode, op, a, b, 112, 15);
+
             Some(
-                Floatsisf {
-                    a,
-                    b: to_u32(f32(a)),
+                ExceptionsTf3 {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c,
+                    flags: flags,
                 },
             )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), ({c}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatsisf;
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
-
-static TEST_CASES: &[((i32,), u32)] = &[
-"#
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::add::__addtf3;
+use compiler_builtins::float::div::__divtf3;
+use compiler_builtins::float::exception;
+use compiler_builtins::float::mul::__multf3;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::__subtf3;
+
+static TEST_CASES: &[((u8, i32, u128, u128), (u128, i32))] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -1778,25 +2417,38 @@ static TEST_CASES: &[((i32,), u32)] = &[
 ];
 
 #[test]
-fn floatsisf() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __floatsisf(a);
-        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+fn exceptions_tf3() {
+    let is_nan = |x: u128| x & !(1 << 127) > 0x7fff << 112;
+    for &((op, mode, a, b), (c, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let (x, y) = (f128::from_repr(a), f128::from_repr(b));
+        let c_ = match op {
+            0 => __addtf3(x, y),
+            1 => __subtf3(x, y),
+            2 => __multf3(x, y),
+            _ => __divtf3(x, y),
+        };
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if is_nan(c_.repr()) && is_nan(c) { c } else { c_.repr() };
+        assert_eq!(((op, mode, a, b), (c, flags)), ((op, mode, a, b), (c_, flags_)));
     }
+    round::set_mode(Round::ToNearest);
 }
 "
         }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floattisf {
-        a: i128,
-        b: u32, // f32
+    pub struct Extenddftf2 {
+        a: u64,  // f64
+        b: u128,  // f128
     }
 
-    impl TestCase for Floattisf {
+    impl TestCase for Extenddftf2 {
         fn name() -> &'static str {
-            "floattisf"
+            "extenddftf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1804,11 +2456,17 @@ fn floatsisf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i128(rng);
+            let a = gen_f64(rng);
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() {
+                return None;
+            }
+
             Some(
-                Floattisf {
-                    a,
-                    b: to_u32(f32(a)),
+                Extenddftf2 {
+                    a: to_u64(a),
+                    b: f128_from_f64(a),
                 },
             )
         }
@@ -1819,23 +2477,10 @@ fn floatsisf() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floattisf;
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::extend::__extenddftf2;
 
-static TEST_CASES: &[((i128,), u32)] = &[
+static TEST_CASES: &[((u64,), u128)] = &[
 "#
         }
 
@@ -1844,10 +2489,10 @@ static TEST_CASES: &[((i128,), u32)] = &[
 ];
 
 #[test]
-fn floattisf() {
+fn extenddftf2() {
     for &((a,), b) in TEST_CASES {
-        let b_ = __floattisf(a);
-        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+        let b_ = __extenddftf2(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_.repr()));
     }
 }
 "
@@ -1855,14 +2500,14 @@ fn floattisf() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floattidf {
-        a: i128,
-        b: u64, // f64
+    pub struct Extendhfsf2 {
+        a: u16,
+        b: u32, // f32
     }
 
-    impl TestCase for Floattidf {
+    impl TestCase for Extendhfsf2 {
         fn name() -> &'static str {
-            "floattidf"
+            "extendhfsf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1870,11 +2515,11 @@ fn floattisf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i128(rng);
+            let a = rng.gen::<u16>();
             Some(
-                Floattidf {
+                Extendhfsf2 {
                     a,
-                    b: to_u64(f64(a)),
+                    b: extend_f16(a),
                 },
             )
         }
@@ -1885,23 +2530,9 @@ fn floattisf() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floattidf;
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::extend::__extendhfsf2;
 
-static TEST_CASES: &[((i128,), u64)] = &[
+static TEST_CASES: &[((u16,), u32)] = &[
 "#
         }
 
@@ -1910,27 +2541,30 @@ static TEST_CASES: &[((i128,), u64)] = &[
 ];
 
 #[test]
-fn floattidf() {
+fn extendhfsf2() {
     for &((a,), b) in TEST_CASES {
-        let b_ = __floattidf(a);
-        let g_b = to_u64(b_);
-        let diff = if g_b > b { g_b - b } else { b - g_b };
-        assert_eq!(((a,), b, g_b, true), ((a,), b, g_b, diff <= 1));
+        let b_ = __extendhfsf2(a);
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
     }
 }
 "
         }
+
+        fn ntests() -> usize {
+            // Every half precision number
+            1 << 16
+        }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatundidf {
-        a: u64,
-        b: u64, // f64
+    pub struct Extendsfdf2 {
+        a: u32,  // f32
+        b: u64,  // f64
     }
 
-    impl TestCase for Floatundidf {
+    impl TestCase for Extendsfdf2 {
         fn name() -> &'static str {
-            "floatundidf"
+            "extendsfdf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -1938,11 +2572,18 @@ fn floattidf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u64(rng);
+            let a = gen_f32(rng);
+            let b = f64(a);
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() {
+                return None;
+            }
+
             Some(
-                Floatundidf {
-                    a,
-                    b: to_u64(f64(a)),
+                Extendsfdf2 {
+                    a: to_u32(a),
+                    b: to_u64(b),
                 },
             )
         }
@@ -1953,23 +2594,9 @@ fn floattidf() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatundidf;
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::extend::__extendsfdf2;
 
-static TEST_CASES: &[((u64,), u64)] = &[
+static TEST_CASES: &[((u32,), u64)] = &[
 "#
         }
 
@@ -1978,9 +2605,9 @@ static TEST_CASES: &[((u64,), u64)] = &[
 ];
 
 #[test]
-fn floatundidf() {
+fn extendsfdf2() {
     for &((a,), b) in TEST_CASES {
-        let b_ = __floatundidf(a);
+        let b_ = __extendsfdf2(mk_f32(a));
         assert_eq!(((a,), b), ((a,), to_u64(b_)));
     }
 }
@@ -1989,14 +2616,14 @@ fn floatundidf() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatunsidf {
-        a: u32,
-        b: u64, // f64
+    pub struct Extendsftf2 {
+        a: u32,  // f32
+        b: u128,  // f128
     }
 
-    impl TestCase for Floatunsidf {
+    impl TestCase for Extendsftf2 {
         fn name() -> &'static str {
-            "floatunsidf"
+            "extendsftf2"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2004,11 +2631,17 @@ fn floatundidf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u32(rng);
+            let a = gen_f32(rng);
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() {
+                return None;
+            }
+
             Some(
-                Floatunsidf {
-                    a,
-                    b: to_u64(f64(a)),
+                Extendsftf2 {
+                    a: to_u32(a),
+                    b: f128_from_f64(f64(a)),
                 },
             )
         }
@@ -2019,23 +2652,10 @@ fn floatundidf() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatunsidf;
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::extend::__extendsftf2;
 
-static TEST_CASES: &[((u32,), u64)] = &[
+static TEST_CASES: &[((u32,), u128)] = &[
 "#
         }
 
@@ -2044,10 +2664,10 @@ static TEST_CASES: &[((u32,), u64)] = &[
 ];
 
 #[test]
-fn floatunsidf() {
+fn extendsftf2() {
     for &((a,), b) in TEST_CASES {
-        let b_ = __floatunsidf(a);
-        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+        let b_ = __extendsftf2(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_.repr()));
     }
 }
 "
@@ -2055,14 +2675,14 @@ fn floatunsidf() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatunsisf {
-        a: u32,
-        b: u32, // f32
+    pub struct Fabs {
+        a: u64,  // f64
+        b: u64,  // f64
     }
 
-    impl TestCase for Floatunsisf {
+    impl TestCase for Fabs {
         fn name() -> &'static str {
-            "floatunsisf"
+            "fabs"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2070,11 +2690,11 @@ fn floatunsidf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u32(rng);
+            let a = to_u64(gen_f64(rng));
             Some(
-                Floatunsisf {
-                    a,
-                    b: to_u32(f32(a)),
+                Fabs {
+                    a: a,
+                    b: a & !(1 << 63),
                 },
             )
         }
@@ -2085,23 +2705,9 @@ fn floatunsidf() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatunsisf;
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::neg;
 
-static TEST_CASES: &[((u32,), u32)] = &[
+static TEST_CASES: &[((u64,), u64)] = &[
 "#
         }
 
@@ -2110,10 +2716,10 @@ static TEST_CASES: &[((u32,), u32)] = &[
 ];
 
 #[test]
-fn floatunsisf() {
+fn fabs() {
     for &((a,), b) in TEST_CASES {
-        let b_ = __floatunsisf(a);
-        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+        let b_ = neg::fabs(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
     }
 }
 "
@@ -2121,14 +2727,14 @@ fn floatunsisf() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatuntisf {
-        a: u128,
-        b: u32, // f32
+    pub struct Fabsf {
+        a: u32,  // f32
+        b: u32,  // f32
     }
 
-    impl TestCase for Floatuntisf {
+    impl TestCase for Fabsf {
         fn name() -> &'static str {
-            "floatuntisf"
+            "fabsf"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2136,14 +2742,13 @@ fn floatunsisf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u128(rng);
-            let f_a = f32(a);
-            f_a.ok().map(|f| {
-                Floatuntisf {
-                    a,
-                    b: to_u32(f),
-                }
-            })
+            let a = to_u32(gen_f32(rng));
+            Some(
+                Fabsf {
+                    a: a,
+                    b: a & !(1 << 31),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
@@ -2152,23 +2757,9 @@ fn floatunsisf() {
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatuntisf;
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::neg;
 
-static TEST_CASES: &[((u128,), u32)] = &[
+static TEST_CASES: &[((u32,), u32)] = &[
 "#
         }
 
@@ -2177,9 +2768,9 @@ static TEST_CASES: &[((u128,), u32)] = &[
 ];
 
 #[test]
-fn floatuntisf() {
+fn fabsf() {
     for &((a,), b) in TEST_CASES {
-        let b_ = __floatuntisf(a);
+        let b_ = neg::fabsf(mk_f32(a));
         assert_eq!(((a,), b), ((a,), to_u32(b_)));
     }
 }
@@ -2188,14 +2779,16 @@ fn floatuntisf() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Floatuntidf {
-        a: u128,
-        b: u64, // f64
+    pub struct FixDf {
+        op: u8,
+        a: u64,  // f64
+        b: u128,
+        flags: i32,
     }
 
-    impl TestCase for Floatuntidf {
+    impl TestCase for FixDf {
         fn name() -> &'static str {
-            "floatuntidf"
+            "fix_df"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2203,38 +2796,58 @@ fn floatuntisf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u128(rng);
+            let sign = rng.gen::<u64>() & (1 << 63);
+            let a = if rng.gen_weighted_bool(4) {
+                // NaN with a random payload, quiet or signaling
+                sign | 0x7ff0_0000_0000_0000 | rng.gen_range(1, 1 << 52)
+            } else if rng.gen_weighted_bool(16) {
+                sign | 0x7ff0_0000_0000_0000
+            } else if rng.gen() {
+                to_u64(gen_f64(rng))
+            } else {
+                // A number that is often close to the bounds of the integer types
+                let bound = *rng.choose(&[0, 31, 32, 63, 64, 127, 128]).unwrap();
+                let m = if rng.gen_weighted_bool(4) { 1 << 62 } else { rng.gen::<i64>() };
+                sign ^ to_u64((m as f64) * 2f64.powi(bound + rng.gen_range(-8, 8) - 62))
+            };
+            let x = if mk_f64(a).is_nan() {
+                ((a >> 63) as u128) << 127 | F128_INF | 1
+            } else {
+                f128_from_f64(mk_f64(a))
+            };
+            let op = rng.gen_range(0, 12);
+            let (signed, bits, saturating) = (op % 6 < 3, 32 << (op % 3), op >= 6);
             Some(
-                Floatuntidf {
-                    a,
-                    b: to_u64(f64(a)),
+                FixDf {
+                    op: op,
+                    a: a,
+                    b: fix_result(x, signed, bits, saturating),
+                    flags: fix_exc(x, signed, bits),
                 },
             )
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+            writeln!(
+                buffer,
+                "(({op}, {a}), ({b}, {flags})),",
+                op = self.op,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::conv::__floatuntidf;
+use compiler_builtins::float::conv::{__fixdfdi, __fixdfsi, __fixdfti, __fixunsdfdi, __fixunsdfsi,
+                                     __fixunsdfti, __rust_fixdfdi_sat, __rust_fixdfsi_sat,
+                                     __rust_fixdfti_sat, __rust_fixunsdfdi_sat,
+                                     __rust_fixunsdfsi_sat, __rust_fixunsdfti_sat};
+use compiler_builtins::float::exception;
 
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
-
-static TEST_CASES: &[((u128,), u64)] = &[
+static TEST_CASES: &[((u8, u64), (u128, i32))] = &[
 "#
         }
 
@@ -2242,13 +2855,29 @@ static TEST_CASES: &[((u128,), u64)] = &[
             "
 ];
 
+// Both the compiler-rt conformant conversions and the saturating ones, including NaN and out of
+// range inputs
 #[test]
-fn floatuntidf() {
-    for &((a,), b) in TEST_CASES {
-        let b_ = __floatuntidf(a);
-        let g_b = to_u64(b_);
-        let diff = if g_b > b { g_b - b } else { b - g_b };
-        assert_eq!(((a,), b, g_b, true), ((a,), b, g_b, diff <= 1));
+fn fix_df() {
+    for &((op, a), (b, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let x = mk_f64(a);
+        let b_ = match op {
+            0 => __fixdfsi(x) as i128 as u128,
+            1 => __fixdfdi(x) as i128 as u128,
+            2 => __fixdfti(x) as u128,
+            3 => __fixunsdfsi(x) as u128,
+            4 => __fixunsdfdi(x) as u128,
+            5 => __fixunsdfti(x),
+            6 => __rust_fixdfsi_sat(x) as i128 as u128,
+            7 => __rust_fixdfdi_sat(x) as i128 as u128,
+            8 => __rust_fixdfti_sat(x) as u128,
+            9 => __rust_fixunsdfsi_sat(x) as u128,
+            10 => __rust_fixunsdfdi_sat(x) as u128,
+            _ => __rust_fixunsdfti_sat(x),
+        };
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, a), (b, flags)), ((op, a), (b_, flags_)));
     }
 }
 "
@@ -2256,15 +2885,16 @@ fn floatuntidf() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Moddi3 {
-        a: i64,
-        b: i64,
-        c: i64,
+    pub struct FixSf {
+        op: u8,
+        a: u32,  // f32
+        b: u128,
+        flags: i32,
     }
 
-    impl TestCase for Moddi3 {
+    impl TestCase for FixSf {
         fn name() -> &'static str {
-            "moddi3"
+            "fix_sf"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2272,44 +2902,88 @@ fn floatuntidf() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i64(rng);
-            let b = gen_i64(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a % b;
-
-            Some(Moddi3 { a, b, c })
+            let sign = rng.gen::<u32>() & (1 << 31);
+            let a = if rng.gen_weighted_bool(4) {
+                // NaN with a random payload, quiet or signaling
+                sign | 0x7f80_0000 | rng.gen_range(1, 1 << 23)
+            } else if rng.gen_weighted_bool(16) {
+                sign | 0x7f80_0000
+            } else if rng.gen() {
+                to_u32(gen_f32(rng))
+            } else {
+                // A number that is often close to the bounds of the integer types
+                let bound = *rng.choose(&[0, 31, 32, 63, 64, 127, 128]).unwrap();
+                let m = if rng.gen_weighted_bool(4) { 1 << 30 } else { rng.gen::<i32>() };
+                sign ^ to_u32((m as f32) * 2f32.powi(bound + rng.gen_range(-8, 8) - 30))
+            };
+            let x = if mk_f32(a).is_nan() {
+                ((a >> 31) as u128) << 127 | F128_INF | 1
+            } else {
+                f128_from_f64(mk_f32(a) as f64)
+            };
+            let op = rng.gen_range(0, 12);
+            let (signed, bits, saturating) = (op % 6 < 3, 32 << (op % 3), op >= 6);
+            Some(
+                FixSf {
+                    op: op,
+                    a: a,
+                    b: fix_result(x, signed, bits, saturating),
+                    flags: fix_exc(x, signed, bits),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
             writeln!(
                 buffer,
-                "(({a}, {b}), {c}),",
+                "(({op}, {a}), ({b}, {flags})),",
+                op = self.op,
                 a = self.a,
                 b = self.b,
-                c = self.c
+                flags = self.flags
             )
                     .unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__moddi3;
+            r#"
+use compiler_builtins::float::conv::{__fixsfdi, __fixsfsi, __fixsfti, __fixunssfdi, __fixunssfsi,
+                                     __fixunssfti, __rust_fixsfdi_sat, __rust_fixsfsi_sat,
+                                     __rust_fixsfti_sat, __rust_fixunssfdi_sat,
+                                     __rust_fixunssfsi_sat, __rust_fixunssfti_sat};
+use compiler_builtins::float::exception;
 
-static TEST_CASES: &[((i64, i64), i64)] = &[
-"
+static TEST_CASES: &[((u8, u32), (u128, i32))] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
             "
 ];
 
+// Both the compiler-rt conformant conversions and the saturating ones, including NaN and out of
+// range inputs
 #[test]
-fn moddi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __moddi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fix_sf() {
+    for &((op, a), (b, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let x = mk_f32(a);
+        let b_ = match op {
+            0 => __fixsfsi(x) as i128 as u128,
+            1 => __fixsfdi(x) as i128 as u128,
+            2 => __fixsfti(x) as u128,
+            3 => __fixunssfsi(x) as u128,
+            4 => __fixunssfdi(x) as u128,
+            5 => __fixunssfti(x),
+            6 => __rust_fixsfsi_sat(x) as i128 as u128,
+            7 => __rust_fixsfdi_sat(x) as i128 as u128,
+            8 => __rust_fixsfti_sat(x) as u128,
+            9 => __rust_fixunssfsi_sat(x) as u128,
+            10 => __rust_fixunssfdi_sat(x) as u128,
+            _ => __rust_fixunssfti_sat(x),
+        };
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, a), (b, flags)), ((op, a), (b_, flags_)));
     }
 }
 "
@@ -2317,15 +2991,16 @@ fn moddi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Modsi3 {
-        a: i32,
-        b: i32,
-        c: i32,
+    pub struct FixTf {
+        op: u8,
+        a: u128,  // f128
+        b: u128,
+        flags: i32,
     }
 
-    impl TestCase for Modsi3 {
+    impl TestCase for FixTf {
         fn name() -> &'static str {
-            "modsi3"
+            "fix_tf"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2333,32 +3008,54 @@ fn moddi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i32(rng);
-            let b = gen_i32(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a % b;
-
-            Some(Modsi3 { a, b, c })
+            let sign = (rng.gen::<bool>() as u128) << 127;
+            let a = if rng.gen_weighted_bool(4) {
+                // NaN with a random payload, quiet or signaling
+                sign | F128_INF | (gen_u128(rng) & ((1 << 112) - 1)).max(1)
+            } else if rng.gen_weighted_bool(16) {
+                sign | F128_INF
+            } else if rng.gen_weighted_bool(4) {
+                // Exactly the bounds of the integer types
+                let bound = *rng.choose(&[31, 32, 63, 64, 127, 128]).unwrap();
+                sign | ((16383 + bound) << 112)
+            } else {
+                gen_f128_exponent(rng, -2, 130)
+            };
+            let x = a;
+            let op = rng.gen_range(0, 12);
+            let (signed, bits, saturating) = (op % 6 < 3, 32 << (op % 3), op >= 6);
+            Some(
+                FixTf {
+                    op: op,
+                    a: a,
+                    b: fix_result(x, signed, bits, saturating),
+                    flags: fix_exc(x, signed, bits),
+                },
+            )
         }
 
         fn to_string(&self, buffer: &mut String) {
             writeln!(
                 buffer,
-                "(({a}, {b}), {c}),",
+                "(({op}, {a}), ({b}, {flags})),",
+                op = self.op,
                 a = self.a,
                 b = self.b,
-                c = self.c
+                flags = self.flags
             )
                     .unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::sdiv::__modsi3;
-
-static TEST_CASES: &[((i32, i32), i32)] = &[
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::{__fixtfdi, __fixtfsi, __fixtfti, __fixunstfdi, __fixunstfsi,
+                                     __fixunstfti, __rust_fixtfdi_sat, __rust_fixtfsi_sat,
+                                     __rust_fixtfti_sat, __rust_fixunstfdi_sat,
+                                     __rust_fixunstfsi_sat, __rust_fixunstfti_sat};
+use compiler_builtins::float::exception;
+
+static TEST_CASES: &[((u8, u128), (u128, i32))] = &[
 "
         }
 
@@ -2366,11 +3063,29 @@ static TEST_CASES: &[((i32, i32), i32)] = &[
             "
 ];
 
+// Both the compiler-rt conformant conversions and the saturating ones, including NaN and out of
+// range inputs
 #[test]
-fn modsi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __modsi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fix_tf() {
+    for &((op, a), (b, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let x = f128::from_repr(a);
+        let b_ = match op {
+            0 => __fixtfsi(x) as i128 as u128,
+            1 => __fixtfdi(x) as i128 as u128,
+            2 => __fixtfti(x) as u128,
+            3 => __fixunstfsi(x) as u128,
+            4 => __fixunstfdi(x) as u128,
+            5 => __fixunstfti(x),
+            6 => __rust_fixtfsi_sat(x) as i128 as u128,
+            7 => __rust_fixtfdi_sat(x) as i128 as u128,
+            8 => __rust_fixtfti_sat(x) as u128,
+            9 => __rust_fixunstfsi_sat(x) as u128,
+            10 => __rust_fixunstfdi_sat(x) as u128,
+            _ => __rust_fixunstfti_sat(x),
+        };
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, a), (b, flags)), ((op, a), (b_, flags_)));
     }
 }
 "
@@ -2378,15 +3093,14 @@ fn modsi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Modti3 {
-        a: i128,
-        b: i128,
-        c: i128,
+    pub struct Fixdfdi {
+        a: u64,  // f64
+        b: i64,
     }
 
-    impl TestCase for Modti3 {
+    impl TestCase for Fixdfdi {
         fn name() -> &'static str {
-            "modti3"
+            "fixdfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2394,33 +3108,20 @@ fn modsi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i128(rng);
-            let b = gen_i128(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a % b;
-
-            Some(Modti3 { a, b, c })
+            let a = gen_f64(rng);
+            i64(a).ok().map(|b| Fixdfdi { a: to_u64(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::sdiv::__modti3;
+            r#"
+use compiler_builtins::float::conv::__fixdfdi;
 
-static TEST_CASES: &[((i128, i128), i128)] = &[
-"
+static TEST_CASES: &[((u64,), i64)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -2428,10 +3129,10 @@ static TEST_CASES: &[((i128, i128), i128)] = &[
 ];
 
 #[test]
-fn modti3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __modti3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixdfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixdfdi(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2439,15 +3140,14 @@ fn modti3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    struct Muldi3 {
-        a: u64,
-        b: u64,
-        c: u64,
+    pub struct Fixdfsi {
+        a: u64,  // f64
+        b: i32,
     }
 
-    impl TestCase for Muldi3 {
+    impl TestCase for Fixdfsi {
         fn name() -> &'static str {
-            "muldi3"
+            "fixdfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2455,30 +3155,20 @@ fn modti3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u64(rng);
-            let b = gen_u64(rng);
-            let c = a.wrapping_mul(b);
-
-            Some(Muldi3 { a, b, c })
+            let a = gen_f64(rng);
+            i32(a).ok().map(|b| Fixdfsi { a: to_u64(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::mul::__muldi3;
+            r#"
+use compiler_builtins::float::conv::__fixdfsi;
 
-static TEST_CASES: &[((u64, u64), u64)] = &[
-"
+static TEST_CASES: &[((u64,), i32)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -2486,10 +3176,10 @@ static TEST_CASES: &[((u64, u64), u64)] = &[
 ];
 
 #[test]
-fn muldi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __muldi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixdfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixdfsi(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2497,48 +3187,35 @@ fn muldi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Mulodi4 {
-        a: i64,
+    pub struct Fixsfdi {
+        a: u32,  // f32
         b: i64,
-        c: i64,
-        overflow: u32,
     }
 
-    impl TestCase for Mulodi4 {
+    impl TestCase for Fixsfdi {
         fn name() -> &'static str {
-            "mulodi4"
+            "fixsfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
         where
             R: Rng,
+            Self: Sized,
         {
-            let a = gen_i64(rng);
-            let b = gen_i64(rng);
-            let c = a.wrapping_mul(b);
-            let overflow = if a.checked_mul(b).is_some() { 0 } else { 1 };
-
-            Some(Mulodi4 { a, b, c, overflow })
+            let a = gen_f32(rng);
+            i64(a).ok().map(|b| Fixsfdi { a: to_u32(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {overflow})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                overflow = self.overflow
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::mul::__mulodi4;
+            r#"
+use compiler_builtins::float::conv::__fixsfdi;
 
-static TEST_CASES: &[((i64, i64), (i64, i32))] = &[
-"
+static TEST_CASES: &[((u32,), i64)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -2546,11 +3223,10 @@ static TEST_CASES: &[((i64, i64), (i64, i32))] = &[
 ];
 
 #[test]
-fn mulodi4() {
-    let mut overflow_ = 2;
-    for &((a, b), (c, overflow)) in TEST_CASES {
-        let c_ = __mulodi4(a, b, &mut overflow_);
-        assert_eq!(((a, b), (c, overflow)), ((a, b), (c_, overflow_)));
+fn fixsfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixsfdi(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2558,36 +3234,35 @@ fn mulodi4() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Mulosi4 {
-        a: i32,
+    pub struct Fixsfsi {
+        a: u32,  // f32
         b: i32,
-        c: i32,
-        overflow: u32,
     }
 
-    impl TestCase for Mulosi4 {
+    impl TestCase for Fixsfsi {
         fn name() -> &'static str {
-            "mulosi4"
+            "fixsfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
         where
             R: Rng,
+            Self: Sized,
         {
-            let a = gen_i32(rng);
-            let b = gen_i32(rng);
-            let c = a.wrapping_mul(b);
-            let overflow = if a.checked_mul(b).is_some() { 0 } else { 1 };
-
-            Some(Mulosi4 { a, b, c, overflow })
+            let a = gen_f32(rng);
+            i32(a).ok().map(|b| Fixsfsi { a: to_u32(a), b })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::mul::__mulosi4;
+            r#"
+use compiler_builtins::float::conv::__fixsfsi;
 
-static TEST_CASES: &[((i32, i32), (i32, i32))] = &[
-"
+static TEST_CASES: &[((u32,), i32)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -2595,60 +3270,46 @@ static TEST_CASES: &[((i32, i32), (i32, i32))] = &[
 ];
 
 #[test]
-fn mulosi4() {
-    let mut overflow_ = 2;
-    for &((a, b), (c, overflow)) in TEST_CASES {
-        let c_ = __mulosi4(a, b, &mut overflow_);
-        assert_eq!(((a, b), (c, overflow)), ((a, b), (c_, overflow_)));
+fn fixsfsi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixsfsi(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
         }
-
-        fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {overflow})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                overflow = self.overflow
-            )
-                    .unwrap();
-        }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Muloti4 {
-        a: i128,
+    pub struct Fixsfti {
+        a: u32,  // f32
         b: i128,
-        c: i128,
-        overflow: u32,
     }
 
-    impl TestCase for Muloti4 {
+    impl TestCase for Fixsfti {
         fn name() -> &'static str {
-            "muloti4"
+            "fixsfti"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
         where
             R: Rng,
+            Self: Sized,
         {
-            let a = gen_i128(rng);
-            let b = gen_i128(rng);
-            let c = a.wrapping_mul(b);
-            let overflow = if a.checked_mul(b).is_some() { 0 } else { 1 };
+            let a = gen_f32(rng);
+            i128(a).ok().map(|b| Fixsfti { a: to_u32(a), b })
+        }
 
-            Some(Muloti4 { a, b, c, overflow })
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::mul::__muloti4;
+            r#"
+use compiler_builtins::float::conv::__fixsfti;
 
-static TEST_CASES: &[((i128, i128), (i128, i32))] = &[
-"
+static TEST_CASES: &[((u32,), i128)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -2656,39 +3317,25 @@ static TEST_CASES: &[((i128, i128), (i128, i32))] = &[
 ];
 
 #[test]
-fn muloti4() {
-    let mut overflow_ = 2;
-    for &((a, b), (c, overflow)) in TEST_CASES {
-        let c_ = __muloti4(a, b, &mut overflow_);
-        assert_eq!(((a, b), (c, overflow)), ((a, b), (c_, overflow_)));
+fn fixsfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixsfti(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
         }
-
-        fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {overflow})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                overflow = self.overflow
-            )
-                    .unwrap();
-        }
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Multi3 {
-        a: i128,
+    pub struct Fixdfti {
+        a: u64,  // f64
         b: i128,
-        c: i128,
     }
 
-    impl TestCase for Multi3 {
+    impl TestCase for Fixdfti {
         fn name() -> &'static str {
-            "multi3"
+            "fixdfti"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2696,30 +3343,20 @@ fn muloti4() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_i128(rng);
-            let b = gen_i128(rng);
-            let c = a.wrapping_mul(b);
-
-            Some(Multi3 { a, b, c })
+            let a = gen_f64(rng);
+            i128(a).ok().map(|b| Fixdfti { a: to_u64(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::mul::__multi3;
+            r#"
+use compiler_builtins::float::conv::__fixdfti;
 
-static TEST_CASES: &[((i128, i128), i128)] = &[
-"
+static TEST_CASES: &[((u64,), i128)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -2727,10 +3364,10 @@ static TEST_CASES: &[((i128, i128), i128)] = &[
 ];
 
 #[test]
-fn multi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __multi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixdfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixdfti(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2738,15 +3375,14 @@ fn multi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Powidf2 {
-        a: u64,  // f64
-        b: i32,
-        c: u64,  // f64
+    pub struct Fixtfdi {
+        a: u128,  // f128
+        b: i64,
     }
 
-    impl TestCase for Powidf2 {
+    impl TestCase for Fixtfdi {
         fn name() -> &'static str {
-            "powidf2"
+            "fixtfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2754,59 +3390,21 @@ fn multi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f64(rng);
-            let b = gen_i32(rng);
-            let c = a.powi(b);
-            // TODO accept NaNs. We don't do that right now because we can't check
-            // for NaN-ness on the thumb targets
-            if a.is_nan() || c.is_nan() {
-                return None;
-            }
-
-            Some(
-                Powidf2 {
-                    a: to_u64(a),
-                    b,
-                    c: to_u64(c),
-                },
-            )
+            let a = gen_f128_exponent(rng, -2, 66);
+            f128_to_i128(a).and_then(|b| i64(b).ok()).map(|b| Fixtfdi { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::pow::__powidf2;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__fixtfdi;
 
-static TEST_CASES: &[((u64, i32), u64)] = &[
-"#
+static TEST_CASES: &[((u128,), i64)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -2814,10 +3412,10 @@ static TEST_CASES: &[((u64, i32), u64)] = &[
 ];
 
 #[test]
-fn powidf2() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __powidf2(mk_f64(a), b);
-        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
+fn fixtfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixtfdi(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2825,15 +3423,14 @@ fn powidf2() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Powisf2 {
-        a: u32,  // f32
+    pub struct Fixtfsi {
+        a: u128,  // f128
         b: i32,
-        c: u32,  // f32
     }
 
-    impl TestCase for Powisf2 {
+    impl TestCase for Fixtfsi {
         fn name() -> &'static str {
-            "powisf2"
+            "fixtfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2841,59 +3438,21 @@ fn powidf2() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_f32(rng);
-            let b = gen_i32(rng);
-            let c = a.powi(b);
-            // TODO accept NaNs. We don't do that right now because we can't check
-            // for NaN-ness on the thumb targets
-            if a.is_nan() || c.is_nan() {
-                return None;
-            }
-
-            Some(
-                Powisf2 {
-                    a: to_u32(a),
-                    b,
-                    c: to_u32(c),
-                },
-            )
+            let a = gen_f128_exponent(rng, -2, 34);
+            f128_to_i128(a).and_then(|b| i32(b).ok()).map(|b| Fixtfsi { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::pow::__powisf2;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__fixtfsi;
 
-static TEST_CASES: &[((u32, i32), u32)] = &[
-"#
+static TEST_CASES: &[((u128,), i32)] = &[
+"
         }
 
         fn epilogue() -> &'static str {
@@ -2901,10 +3460,10 @@ static TEST_CASES: &[((u32, i32), u32)] = &[
 ];
 
 #[test]
-fn powisf2() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __powisf2(mk_f32(a), b);
-        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
+fn fixtfsi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixtfsi(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2912,15 +3471,14 @@ fn powisf2() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Lshrdi3 {
-        a: u64,
-        b: u32,
-        c: u64,
+    pub struct Fixtfti {
+        a: u128,  // f128
+        b: i128,
     }
 
-    impl TestCase for Lshrdi3 {
+    impl TestCase for Fixtfti {
         fn name() -> &'static str {
-            "lshrdi3"
+            "fixtfti"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2928,29 +3486,20 @@ fn powisf2() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u64(rng);
-            let b = (rng.gen::<u8>() % 64) as u32;
-            let c = a >> b;
-
-            Some(Lshrdi3 { a, b, c })
+            let a = gen_f128_exponent(rng, -2, 130);
+            f128_to_i128(a).map(|b| Fixtfti { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::shift::__lshrdi3;
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__fixtfti;
 
-static TEST_CASES: &[((u64, u32), u64)] = &[
+static TEST_CASES: &[((u128,), i128)] = &[
 "
         }
 
@@ -2959,10 +3508,10 @@ static TEST_CASES: &[((u64, u32), u64)] = &[
 ];
 
 #[test]
-fn lshrdi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __lshrdi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixtfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixtfti(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -2970,15 +3519,14 @@ fn lshrdi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Lshrti3 {
-        a: u128,
-        b: u32,
-        c: u128,
+    pub struct Fixunsdfdi {
+        a: u64,  // f64
+        b: u64,
     }
 
-    impl TestCase for Lshrti3 {
+    impl TestCase for Fixunsdfdi {
         fn name() -> &'static str {
-            "lshrti3"
+            "fixunsdfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -2986,30 +3534,20 @@ fn lshrdi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u128(rng);
-            let b = (rng.gen::<u8>() % 128) as u32;
-            let c = a >> b;
-
-            Some(Lshrti3 { a, b, c })
+            let a = gen_f64(rng);
+            u64(a).ok().map(|b| Fixunsdfdi { a: to_u64(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::shift::__lshrti3;
+            r#"
+use compiler_builtins::float::conv::__fixunsdfdi;
 
-static TEST_CASES: &[((u128, u32), u128)] = &[
-"
+static TEST_CASES: &[((u64,), u64)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -3017,10 +3555,10 @@ static TEST_CASES: &[((u128, u32), u128)] = &[
 ];
 
 #[test]
-fn lshrti3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __lshrti3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixunsdfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunsdfdi(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3028,15 +3566,14 @@ fn lshrti3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Subdf3 {
+    pub struct Fixunsdfsi {
         a: u64,  // f64
-        b: u64,  // f64
-        c: u64,  // f64
+        b: u32,
     }
 
-    impl TestCase for Subdf3 {
+    impl TestCase for Fixunsdfsi {
         fn name() -> &'static str {
-            "subdf3"
+            "fixunsdfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3045,57 +3582,18 @@ fn lshrti3() {
             Self: Sized,
         {
             let a = gen_f64(rng);
-            let b = gen_f64(rng);
-            let c = a - b;
-            // TODO accept NaNs. We don't do that right now because we can't check
-            // for NaN-ness on the thumb targets (due to missing intrinsics)
-            if a.is_nan() || b.is_nan() || c.is_nan() {
-                return None;
-            }
-
-            Some(
-                Subdf3 {
-                    a: to_u64(a),
-                    b: to_u64(b),
-                    c: to_u64(c),
-                },
-            )
+            u32(a).ok().map(|b| Fixunsdfsi { a: to_u64(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::sub::__subdf3;
-
-fn mk_f64(x: u64) -> f64 {
-    unsafe { mem::transmute(x) }
-}
-
-fn to_u64(x: f64) -> u64 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::conv::__fixunsdfsi;
 
-static TEST_CASES: &[((u64, u64), u64)] = &[
+static TEST_CASES: &[((u64,), u32)] = &[
 "#
         }
 
@@ -3104,10 +3602,10 @@ static TEST_CASES: &[((u64, u64), u64)] = &[
 ];
 
 #[test]
-fn subdf3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __subdf3(mk_f64(a), mk_f64(b));
-        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
+fn fixunsdfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunsdfsi(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3115,15 +3613,14 @@ fn subdf3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Subsf3 {
+    pub struct Fixunssfdi {
         a: u32,  // f32
-        b: u32,  // f32
-        c: u32,  // f32
+        b: u64,
     }
 
-    impl TestCase for Subsf3 {
+    impl TestCase for Fixunssfdi {
         fn name() -> &'static str {
-            "subsf3"
+            "fixunssfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3132,57 +3629,18 @@ fn subdf3() {
             Self: Sized,
         {
             let a = gen_f32(rng);
-            let b = gen_f32(rng);
-            let c = a - b;
-            // TODO accept NaNs. We don't do that right now because we can't check
-            // for NaN-ness on the thumb targets (due to missing intrinsics)
-            if a.is_nan() || b.is_nan() || c.is_nan() {
-                return None;
-            }
-
-            Some(
-                Subsf3 {
-                    a: to_u32(a),
-                    b: to_u32(b),
-                    c: to_u32(c),
-                },
-            )
+            u64(a).ok().map(|b| Fixunssfdi { a: to_u32(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             r#"
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-use core::mem;
-#[cfg(not(all(target_arch = "arm",
-              not(any(target_env = "gnu", target_env = "musl")),
-              target_os = "linux",
-              test)))]
-use std::mem;
-use compiler_builtins::float::sub::__subsf3;
-
-fn mk_f32(x: u32) -> f32 {
-    unsafe { mem::transmute(x) }
-}
-
-fn to_u32(x: f32) -> u32 {
-    unsafe { mem::transmute(x) }
-}
+use compiler_builtins::float::conv::__fixunssfdi;
 
-static TEST_CASES: &[((u32, u32), u32)] = &[
+static TEST_CASES: &[((u32,), u64)] = &[
 "#
         }
 
@@ -3191,10 +3649,10 @@ static TEST_CASES: &[((u32, u32), u32)] = &[
 ];
 
 #[test]
-fn subsf3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __subsf3(mk_f32(a), mk_f32(b));
-        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
+fn fixunssfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunssfdi(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3202,15 +3660,14 @@ fn subsf3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Udivdi3 {
-        a: u64,
-        b: u64,
-        c: u64,
+    pub struct Fixunssfsi {
+        a: u32,  // f32
+        b: u32,
     }
 
-    impl TestCase for Udivdi3 {
+    impl TestCase for Fixunssfsi {
         fn name() -> &'static str {
-            "udivdi3"
+            "fixunssfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3218,33 +3675,20 @@ fn subsf3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u64(rng);
-            let b = gen_u64(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-
-            Some(Udivdi3 { a, b, c })
+            let a = gen_f32(rng);
+            u32(a).ok().map(|b| Fixunssfsi { a: to_u32(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::udiv::__udivdi3;
+            r#"
+use compiler_builtins::float::conv::__fixunssfsi;
 
-static TEST_CASES: &[((u64, u64), u64)] = &[
-"
+static TEST_CASES: &[((u32,), u32)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -3252,10 +3696,10 @@ static TEST_CASES: &[((u64, u64), u64)] = &[
 ];
 
 #[test]
-fn udivdi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __udivdi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixunssfsi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunssfsi(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3263,16 +3707,14 @@ fn udivdi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Udivmoddi4 {
-        a: u64,
-        b: u64,
-        c: u64,
-        rem: u64,
+    pub struct Fixunssfti {
+        a: u32,  // f32
+        b: u128,
     }
 
-    impl TestCase for Udivmoddi4 {
+    impl TestCase for Fixunssfti {
         fn name() -> &'static str {
-            "udivmoddi4"
+            "fixunssfti"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3280,35 +3722,20 @@ fn udivdi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u64(rng);
-            let b = gen_u64(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-            let rem = a % b;
-
-            Some(Udivmoddi4 { a, b, c, rem })
+            let a = gen_f32(rng);
+            u128(a).ok().map(|b| Fixunssfti { a: to_u32(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {rem})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                rem = self.rem
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::udiv::__udivmoddi4;
+            r#"
+use compiler_builtins::float::conv::__fixunssfti;
 
-static TEST_CASES: &[((u64, u64), (u64, u64))] = &[
-"
+static TEST_CASES: &[((u32,), u128)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -3316,11 +3743,10 @@ static TEST_CASES: &[((u64, u64), (u64, u64))] = &[
 ];
 
 #[test]
-fn udivmoddi4() {
-    for &((a, b), (c, rem)) in TEST_CASES {
-        let mut rem_ = 0;
-        let c_ = __udivmoddi4(a, b, Some(&mut rem_));
-        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+fn fixunssfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunssfti(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3328,16 +3754,14 @@ fn udivmoddi4() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Udivmodsi4 {
-        a: u32,
-        b: u32,
-        c: u32,
-        rem: u32,
+    pub struct Fixunsdfti  {
+        a: u64,  // f64
+        b: u128,
     }
 
-    impl TestCase for Udivmodsi4 {
+    impl TestCase for Fixunsdfti {
         fn name() -> &'static str {
-            "udivmodsi4"
+            "fixunsdfti"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3345,35 +3769,20 @@ fn udivmoddi4() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u32(rng);
-            let b = gen_u32(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-            let rem = a % b;
-
-            Some(Udivmodsi4 { a, b, c, rem })
+            let a = gen_f64(rng);
+            u128(a).ok().map(|b| Fixunsdfti { a: to_u64(a), b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {rem})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                rem = self.rem
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::udiv::__udivmodsi4;
+            r#"
+use compiler_builtins::float::conv::__fixunsdfti;
 
-static TEST_CASES: &[((u32, u32), (u32, u32))] = &[
-"
+static TEST_CASES: &[((u64,), u128)] = &[
+"#
         }
 
         fn epilogue() -> &'static str {
@@ -3381,11 +3790,10 @@ static TEST_CASES: &[((u32, u32), (u32, u32))] = &[
 ];
 
 #[test]
-fn udivmodsi4() {
-    for &((a, b), (c, rem)) in TEST_CASES {
-        let mut rem_ = 0;
-        let c_ = __udivmodsi4(a, b, Some(&mut rem_));
-        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+fn fixunsdfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunsdfti(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3393,16 +3801,14 @@ fn udivmodsi4() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Udivmodti4 {
-        a: u128,
-        b: u128,
-        c: u128,
-        rem: u128,
+    pub struct Fixunstfdi {
+        a: u128,  // f128
+        b: u64,
     }
 
-    impl TestCase for Udivmodti4 {
+    impl TestCase for Fixunstfdi {
         fn name() -> &'static str {
-            "udivmodti4"
+            "fixunstfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3410,34 +3816,20 @@ fn udivmodsi4() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u128(rng);
-            let b = gen_u128(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-            let rem = a % b;
-
-            Some(Udivmodti4 { a, b, c, rem })
+            let a = gen_f128_exponent(rng, -2, 66);
+            f128_to_u128(a).and_then(|b| u64(b).ok()).map(|b| Fixunstfdi { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), ({c}, {rem})),",
-                a = self.a,
-                b = self.b,
-                c = self.c,
-                rem = self.rem
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::udiv::__udivmodti4;
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__fixunstfdi;
 
-static TEST_CASES: &[((u128, u128), (u128, u128))] = &[
+static TEST_CASES: &[((u128,), u64)] = &[
 "
         }
 
@@ -3446,11 +3838,10 @@ static TEST_CASES: &[((u128, u128), (u128, u128))] = &[
 ];
 
 #[test]
-fn udivmodti4() {
-    for &((a, b), (c, rem)) in TEST_CASES {
-        let mut rem_ = 0;
-        let c_ = __udivmodti4(a, b, Some(&mut rem_));
-        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+fn fixunstfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunstfdi(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3458,15 +3849,14 @@ fn udivmodti4() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Udivsi3 {
-        a: u32,
+    pub struct Fixunstfsi {
+        a: u128,  // f128
         b: u32,
-        c: u32,
     }
 
-    impl TestCase for Udivsi3 {
+    impl TestCase for Fixunstfsi {
         fn name() -> &'static str {
-            "udivsi3"
+            "fixunstfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3474,32 +3864,20 @@ fn udivmodti4() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u32(rng);
-            let b = gen_u32(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-
-            Some(Udivsi3 { a, b, c })
+            let a = gen_f128_exponent(rng, -2, 34);
+            f128_to_u128(a).and_then(|b| u32(b).ok()).map(|b| Fixunstfsi { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::udiv::__udivsi3;
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__fixunstfsi;
 
-static TEST_CASES: &[((u32, u32), u32)] = &[
+static TEST_CASES: &[((u128,), u32)] = &[
 "
         }
 
@@ -3508,10 +3886,10 @@ static TEST_CASES: &[((u32, u32), u32)] = &[
 ];
 
 #[test]
-fn udivsi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __udivsi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixunstfsi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunstfsi(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3519,15 +3897,14 @@ fn udivsi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Udivti3 {
-        a: u128,
+    pub struct Fixunstfti {
+        a: u128,  // f128
         b: u128,
-        c: u128,
     }
 
-    impl TestCase for Udivti3 {
+    impl TestCase for Fixunstfti {
         fn name() -> &'static str {
-            "udivti3"
+            "fixunstfti"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3535,32 +3912,20 @@ fn udivsi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u128(rng);
-            let b = gen_u128(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a / b;
-
-            Some(Udivti3 { a, b, c })
+            let a = gen_f128_exponent(rng, -2, 130);
+            f128_to_u128(a).map(|b| Fixunstfti { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::udiv::__udivti3;
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__fixunstfti;
 
-static TEST_CASES: &[((u128, u128), u128)] = &[
+static TEST_CASES: &[((u128,), u128)] = &[
 "
         }
 
@@ -3569,10 +3934,10 @@ static TEST_CASES: &[((u128, u128), u128)] = &[
 ];
 
 #[test]
-fn udivti3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __udivti3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixunstfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __fixunstfti(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3580,15 +3945,14 @@ fn udivti3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Umoddi3 {
-        a: u64,
+    pub struct Fixunsxfdi {
+        a: u128,  // f80
         b: u64,
-        c: u64,
     }
 
-    impl TestCase for Umoddi3 {
+    impl TestCase for Fixunsxfdi {
         fn name() -> &'static str {
-            "umoddi3"
+            "fixunsxfdi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3596,32 +3960,20 @@ fn udivti3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u64(rng);
-            let b = gen_u64(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a % b;
-
-            Some(Umoddi3 { a, b, c })
+            let a = gen_x87_exponent(rng, -2, 66);
+            f128_to_u128(x87_to_f128(a)).and_then(|b| u64(b).ok()).map(|b| Fixunsxfdi { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::udiv::__umoddi3;
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_fixunsxfdi;
 
-static TEST_CASES: &[((u64, u64), u64)] = &[
+static TEST_CASES: &[((u128,), u64)] = &[
 "
         }
 
@@ -3630,10 +3982,10 @@ static TEST_CASES: &[((u64, u64), u64)] = &[
 ];
 
 #[test]
-fn umoddi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __umoddi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixunsxfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __rust_fixunsxfdi(&f80::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
@@ -3641,15 +3993,14 @@ fn umoddi3() {
     }
 
     #[derive(Eq, Hash, PartialEq)]
-    pub struct Umodsi3 {
-        a: u32,
+    pub struct Fixunsxfsi {
+        a: u128,  // f80
         b: u32,
-        c: u32,
     }
 
-    impl TestCase for Umodsi3 {
+    impl TestCase for Fixunsxfsi {
         fn name() -> &'static str {
-            "umodsi3"
+            "fixunsxfsi"
         }
 
         fn generate<R>(rng: &mut R) -> Option<Self>
@@ -3657,32 +4008,20 @@ fn umoddi3() {
             R: Rng,
             Self: Sized,
         {
-            let a = gen_u32(rng);
-            let b = gen_u32(rng);
-            if b == 0 {
-                return None;
-            }
-            let c = a % b;
-
-            Some(Umodsi3 { a, b, c })
+            let a = gen_x87_exponent(rng, -2, 34);
+            f128_to_u128(x87_to_f128(a)).and_then(|b| u32(b).ok()).map(|b| Fixunsxfsi { a, b })
         }
 
         fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
         }
 
         fn prologue() -> &'static str {
             "
-use compiler_builtins::int::udiv::__umodsi3;
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_fixunsxfsi;
 
-static TEST_CASES: &[((u32, u32), u32)] = &[
+static TEST_CASES: &[((u128,), u32)] = &[
 "
         }
 
@@ -3691,221 +4030,9643 @@ static TEST_CASES: &[((u32, u32), u32)] = &[
 ];
 
 #[test]
-fn umodsi3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __umodsi3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+fn fixunsxfsi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __rust_fixunsxfsi(&f80::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
     }
 }
 "
         }
     }
 
-    #[derive(Eq, Hash, PartialEq)]
-    pub struct Umodti3 {
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fixunsxfti {
+        a: u128,  // f80
+        b: u128,
+    }
+
+    impl TestCase for Fixunsxfti {
+        fn name() -> &'static str {
+            "fixunsxfti"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_x87_exponent(rng, -2, 130);
+            f128_to_u128(x87_to_f128(a)).map(|b| Fixunsxfti { a, b })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_fixunsxfti;
+
+static TEST_CASES: &[((u128,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fixunsxfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __rust_fixunsxfti(&f80::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fixxfdi {
+        a: u128,  // f80
+        b: i64,
+    }
+
+    impl TestCase for Fixxfdi {
+        fn name() -> &'static str {
+            "fixxfdi"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_x87_exponent(rng, -2, 66);
+            f128_to_i128(x87_to_f128(a)).and_then(|b| i64(b).ok()).map(|b| Fixxfdi { a, b })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_fixxfdi;
+
+static TEST_CASES: &[((u128,), i64)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fixxfdi() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __rust_fixxfdi(&f80::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fixxfti {
+        a: u128,  // f80
+        b: i128,
+    }
+
+    impl TestCase for Fixxfti {
+        fn name() -> &'static str {
+            "fixxfti"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_x87_exponent(rng, -2, 130);
+            f128_to_i128(x87_to_f128(a)).map(|b| Fixxfti { a, b })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_fixxfti;
+
+static TEST_CASES: &[((u128,), i128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fixxfti() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __rust_fixxfti(&f80::from_repr(a));
+        assert_eq!(((a,), b), ((a,), b_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatdidf {
+        a: i64,
+        b: u64, // f64
+    }
+
+    impl TestCase for Floatdidf {
+        fn name() -> &'static str {
+            "floatdidf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i64(rng);
+            Some(
+                Floatdidf {
+                    a,
+                    b: to_u64(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatdidf;
+
+static TEST_CASES: &[((i64,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatdidf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatdidf(a);
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatdisf {
+        a: i64,
+        b: u32, // f32
+    }
+
+    impl TestCase for Floatdisf {
+        fn name() -> &'static str {
+            "floatdisf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i64(rng);
+            Some(
+                Floatdisf {
+                    a,
+                    b: to_u32(f32(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatdisf;
+
+static TEST_CASES: &[((i64,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatdisf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatdisf(a);
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatditf {
+        a: i64,
+        b: u128,  // f128
+    }
+
+    impl TestCase for Floatditf {
+        fn name() -> &'static str {
+            "floatditf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i64(rng);
+            Some(
+                Floatditf {
+                    a,
+                    b: f128_from_i128(a as i128),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__floatditf;
+
+static TEST_CASES: &[((i64,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatditf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatditf(a);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatdixf {
+        a: i64,
+        b: u128,  // f80
+    }
+
+    impl TestCase for Floatdixf {
+        fn name() -> &'static str {
+            "floatdixf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i64(rng);
+            Some(
+                Floatdixf {
+                    a,
+                    b: x87_from_i128(a as i128),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_floatdixf;
+
+static TEST_CASES: &[((i64,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatdixf() {
+    for &((a,), b) in TEST_CASES {
+        let mut b_ = f80::from_repr(0);
+        __rust_floatdixf(a, &mut b_);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatsidf {
+        a: i32,
+        b: u64, // f64
+    }
+
+    impl TestCase for Floatsidf {
+        fn name() -> &'static str {
+            "floatsidf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i32(rng);
+            Some(
+                Floatsidf {
+                    a,
+                    b: to_u64(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatsidf;
+
+static TEST_CASES: &[((i32,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatsidf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatsidf(a);
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatsisf {
+        a: i32,
+        b: u32, // f32
+    }
+
+    impl TestCase for Floatsisf {
+        fn name() -> &'static str {
+            "floatsisf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i32(rng);
+            Some(
+                Floatsisf {
+                    a,
+                    b: to_u32(f32(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatsisf;
+
+static TEST_CASES: &[((i32,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatsisf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatsisf(a);
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floattisf {
+        a: i128,
+        b: u32, // f32
+    }
+
+    impl TestCase for Floattisf {
+        fn name() -> &'static str {
+            "floattisf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i128(rng);
+            Some(
+                Floattisf {
+                    a,
+                    b: to_u32(f32(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floattisf;
+
+static TEST_CASES: &[((i128,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floattisf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floattisf(a);
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatsitf {
+        a: i32,
+        b: u128,  // f128
+    }
+
+    impl TestCase for Floatsitf {
+        fn name() -> &'static str {
+            "floatsitf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i32(rng);
+            Some(
+                Floatsitf {
+                    a,
+                    b: f128_from_i128(a as i128),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__floatsitf;
+
+static TEST_CASES: &[((i32,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatsitf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatsitf(a);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floattidf {
+        a: i128,
+        b: u64, // f64
+    }
+
+    impl TestCase for Floattidf {
+        fn name() -> &'static str {
+            "floattidf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i128(rng);
+            Some(
+                Floattidf {
+                    a,
+                    b: to_u64(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floattidf;
+
+static TEST_CASES: &[((i128,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floattidf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floattidf(a);
+        let g_b = to_u64(b_);
+        let diff = if g_b > b { g_b - b } else { b - g_b };
+        assert_eq!(((a,), b, g_b, true), ((a,), b, g_b, diff <= 1));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floattitf {
+        a: i128,
+        b: u128,  // f128
+    }
+
+    impl TestCase for Floattitf {
+        fn name() -> &'static str {
+            "floattitf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i128(rng);
+            Some(
+                Floattitf {
+                    a,
+                    b: f128_from_i128(a),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__floattitf;
+
+static TEST_CASES: &[((i128,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floattitf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floattitf(a);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floattixf {
+        a: i128,
+        b: u128,  // f80
+    }
+
+    impl TestCase for Floattixf {
+        fn name() -> &'static str {
+            "floattixf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i128(rng);
+            Some(
+                Floattixf {
+                    a,
+                    b: x87_from_i128(a),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_floattixf;
+
+static TEST_CASES: &[((i128,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floattixf() {
+    for &((a,), b) in TEST_CASES {
+        let mut b_ = f80::from_repr(0);
+        __rust_floattixf(a, &mut b_);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatundidf {
+        a: u64,
+        b: u64, // f64
+    }
+
+    impl TestCase for Floatundidf {
+        fn name() -> &'static str {
+            "floatundidf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            Some(
+                Floatundidf {
+                    a,
+                    b: to_u64(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatundidf;
+
+static TEST_CASES: &[((u64,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatundidf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatundidf(a);
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatunditf {
+        a: u64,
+        b: u128,  // f128
+    }
+
+    impl TestCase for Floatunditf {
+        fn name() -> &'static str {
+            "floatunditf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            Some(
+                Floatunditf {
+                    a,
+                    b: f128_round(false, 0, a as u128, false, 0),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__floatunditf;
+
+static TEST_CASES: &[((u64,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatunditf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatunditf(a);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatundixf {
+        a: u64,
+        b: u128,  // f80
+    }
+
+    impl TestCase for Floatundixf {
+        fn name() -> &'static str {
+            "floatundixf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            Some(
+                Floatundixf {
+                    a,
+                    b: x87_round(false, 0, a as u128, false, 0),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_floatundixf;
+
+static TEST_CASES: &[((u64,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatundixf() {
+    for &((a,), b) in TEST_CASES {
+        let mut b_ = f80::from_repr(0);
+        __rust_floatundixf(a, &mut b_);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatunsidf {
+        a: u32,
+        b: u64, // f64
+    }
+
+    impl TestCase for Floatunsidf {
+        fn name() -> &'static str {
+            "floatunsidf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u32(rng);
+            Some(
+                Floatunsidf {
+                    a,
+                    b: to_u64(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatunsidf;
+
+static TEST_CASES: &[((u32,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatunsidf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatunsidf(a);
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatunsisf {
+        a: u32,
+        b: u32, // f32
+    }
+
+    impl TestCase for Floatunsisf {
+        fn name() -> &'static str {
+            "floatunsisf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u32(rng);
+            Some(
+                Floatunsisf {
+                    a,
+                    b: to_u32(f32(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatunsisf;
+
+static TEST_CASES: &[((u32,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatunsisf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatunsisf(a);
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatuntisf {
+        a: u128,
+        b: u32, // f32
+    }
+
+    impl TestCase for Floatuntisf {
+        fn name() -> &'static str {
+            "floatuntisf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            let f_a = f32(a);
+            f_a.ok().map(|f| {
+                Floatuntisf {
+                    a,
+                    b: to_u32(f),
+                }
+            })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatuntisf;
+
+static TEST_CASES: &[((u128,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatuntisf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatuntisf(a);
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatunsitf {
+        a: u32,
+        b: u128,  // f128
+    }
+
+    impl TestCase for Floatunsitf {
+        fn name() -> &'static str {
+            "floatunsitf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u32(rng);
+            Some(
+                Floatunsitf {
+                    a,
+                    b: f128_round(false, 0, a as u128, false, 0),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__floatunsitf;
+
+static TEST_CASES: &[((u32,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatunsitf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatunsitf(a);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatuntidf {
+        a: u128,
+        b: u64, // f64
+    }
+
+    impl TestCase for Floatuntidf {
+        fn name() -> &'static str {
+            "floatuntidf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            Some(
+                Floatuntidf {
+                    a,
+                    b: to_u64(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::conv::__floatuntidf;
+
+static TEST_CASES: &[((u128,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatuntidf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatuntidf(a);
+        let g_b = to_u64(b_);
+        let diff = if g_b > b { g_b - b } else { b - g_b };
+        assert_eq!(((a,), b, g_b, true), ((a,), b, g_b, diff <= 1));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatuntitf {
+        a: u128,
+        b: u128,  // f128
+    }
+
+    impl TestCase for Floatuntitf {
+        fn name() -> &'static str {
+            "floatuntitf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            Some(
+                Floatuntitf {
+                    a,
+                    b: f128_round(false, 0, a, false, 0),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::__floatuntitf;
+
+static TEST_CASES: &[((u128,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatuntitf() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __floatuntitf(a);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Floatuntixf {
+        a: u128,
+        b: u128,  // f80
+    }
+
+    impl TestCase for Floatuntixf {
+        fn name() -> &'static str {
+            "floatuntixf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            Some(
+                Floatuntixf {
+                    a,
+                    b: x87_round(false, 0, a as u128, false, 0),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_floatuntixf;
+
+static TEST_CASES: &[((u128,), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn floatuntixf() {
+    for &((a,), b) in TEST_CASES {
+        let mut b_ = f80::from_repr(0);
+        __rust_floatuntixf(a, &mut b_);
+        assert_eq!(((a,), b), ((a,), b_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct FlushToZero {
+        op: u8,
+        mode: i32,
+        a: u128,
+        b: u128,
+        r: u128,
+        flags: i32,
+    }
+
+    impl TestCase for FlushToZero {
+        fn name() -> &'static str {
+            "flush_to_zero"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            // The significand and exponent bits of f16, f32, f64 and f128
+            const FORMATS: [(u32, u32); 4] = [(10, 5), (23, 8), (52, 11), (112, 15)];
+            // `add`, `sub`, `mul` and `div` on f32, f64 and f128, then the conversions between
+            // formats, then `__fixsfsi` and `__fixdfdi`
+            const CONVERSIONS: [(usize, usize); 6] =
+                [(0, 1), (1, 2), (2, 3), (1, 0), (2, 1), (3, 2)];
+
+            let op = rng.gen_range(0, 20);
+            let (src, dst) = if op < 12 {
+                (op / 4 + 1, op / 4 + 1)
+            } else if op < 18 {
+                CONVERSIONS[op - 12]
+            } else {
+                (op - 17, op - 17)
+            };
+            let (sb, eb) = FORMATS[src];
+            let max_exponent = (1 << eb) - 1;
+            let bias = max_exponent >> 1;
+
+            // Finite numbers, mostly subnormals and numbers whose sums, products and quotients
+            // are tiny, so that both the operands and the results get flushed
+            let mut gen_operand = |rng: &mut R| {
+                let sign = (rng.gen::<bool>() as u128) << (sb + eb);
+                let exponent = match rng.gen_range(0, 5) {
+                    0 => 0,
+                    1 => rng.gen_range(1, sb as i32 + 4),
+                    2 => rng.gen_range(bias / 2 - 2, bias / 2 + 3),
+                    3 => rng.gen_range(bias - 2, bias + 3),
+                    _ => rng.gen_range(1, max_exponent),
+                };
+                let significand = gen_u128(rng) & ((1 << sb) - 1);
+                sign | (exponent as u128) << sb | significand
+            };
+            let a = gen_operand(rng);
+            let b = gen_operand(rng);
+            let mode = *rng.choose(rounds()).unwrap();
+
+            // The reference operations on the flushed operands
+            let (a_, b_) = (flush_operand(a, sb, eb), flush_operand(b, sb, eb));
+            let widen = |x: u128| f128_from_binary(x, sb, eb);
+            let (r, flags) = if op < 12 {
+                match f128_arith_exact(mode, op as u8 % 4, widen(a_), widen(b_)) {
+                    Some(x) => x.round_flush(mode, sb, eb),
+                    None => arith_exc(mode, op as u8 % 4, a_, b_, sb, eb),
+                }
+            } else if op < 18 {
+                let (sign, m, e) = binary_parts(a_, sb, eb);
+                let (dst_sb, dst_eb) = FORMATS[dst];
+                Exact::Finite(sign, 0, m, false, e).round_flush(mode, dst_sb, dst_eb)
+            } else {
+                let x = widen(a_);
+                let bits = if op == 18 { 32 } else { 64 };
+                let r = fix_result(x, true, bits, false) & ((1 << bits) - 1);
+                (r, fix_exc(x, true, bits))
+            };
+
+            Some(
+                FlushToZero {
+                    op: op as u8,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    r: r,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), ({r}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                r = self.r,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::add::{__adddf3, __addsf3, __addtf3};
+use compiler_builtins::float::conv::{__fixdfdi, __fixsfsi};
+use compiler_builtins::float::div::{__divdf3, __divsf3, __divtf3};
+use compiler_builtins::float::exception;
+use compiler_builtins::float::extend::{__extenddftf2, __extendhfsf2, __extendsfdf2};
+use compiler_builtins::float::flush;
+use compiler_builtins::float::mul::{__muldf3, __mulsf3, __multf3};
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::{__subdf3, __subsf3, __subtf3};
+use compiler_builtins::float::trunc::{__truncdfsf2, __truncsfhf2, __trunctfdf2};
+
+static TEST_CASES: &[((u8, i32, u128, u128), (u128, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn flush_to_zero() {
+    flush::set_enabled(true);
+    for &((op, mode, a, b), (r, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let r_ = match op {
+            0 => to_u32(__addsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            1 => to_u32(__subsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            2 => to_u32(__mulsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            3 => to_u32(__divsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            4 => to_u64(__adddf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            5 => to_u64(__subdf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            6 => to_u64(__muldf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            7 => to_u64(__divdf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            8 => __addtf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            9 => __subtf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            10 => __multf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            11 => __divtf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            12 => to_u32(__extendhfsf2(a as u16)) as u128,
+            13 => to_u64(__extendsfdf2(mk_f32(a as u32))) as u128,
+            14 => __extenddftf2(mk_f64(a as u64)).repr(),
+            15 => __truncsfhf2(mk_f32(a as u32)) as u128,
+            16 => to_u32(__truncdfsf2(mk_f64(a as u64))) as u128,
+            17 => to_u64(__trunctfdf2(f128::from_repr(a))) as u128,
+            18 => __fixsfsi(mk_f32(a as u32)) as u32 as u128,
+            _ => __fixdfdi(mk_f64(a as u64)) as u64 as u128,
+        };
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, mode, a, b), (r, flags)), ((op, mode, a, b), (r_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+    flush::set_enabled(false);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fma {
+        mode: i32,
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+        d: u64,  // f64
+        flags: i32,
+    }
+
+    impl TestCase for Fma {
+        fn name() -> &'static str {
+            "fma"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mode = *rng.choose(rounds()).unwrap();
+            let (a, b) = (gen_f64_exc(rng, true), gen_f64_exc(rng, true));
+            // Addends close to the negated product exercise cancellation, and addends within the
+            // width of the product exercise the alignment
+            let p = fma_exc(mode, a as u128, b as u128, 0, 52, 11).0 as u64;
+            let c = match rng.gen_range(0, 4) {
+                0 if p & !(1 << 63) < 0x7ff0_0000_0000_0000 => {
+                    (p ^ (1 << 63)).wrapping_add(rng.gen_range(0, 5)).wrapping_sub(2)
+                }
+                1 if p & !(1 << 63) < 0x7ff0_0000_0000_0000 => {
+                    let shift = rng.gen_range(0, 2 * 52) << 52;
+                    let c = if rng.gen() { p.wrapping_add(shift) } else { p.wrapping_sub(shift) };
+                    c ^ ((rng.gen::<bool>() as u64) << 63)
+                }
+                _ => gen_f64_exc(rng, true),
+            };
+            let (d, flags) = fma_exc(mode, a as u128, b as u128, c as u128, 52, 11);
+
+            Some(
+                Fma {
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c,
+                    d: d as u64,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({mode}, {a}, {b}, {c}), ({d}, {flags})),",
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                d = self.d,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::fma;
+use compiler_builtins::float::round::{self, Round};
+
+static TEST_CASES: &[((i32, u64, u64, u64), (u64, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fma() {
+    for &((mode, a, b, c), (d, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let d_ = fma::fma(mk_f64(a), mk_f64(b), mk_f64(c));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let d_ = if mk_f64(d).is_nan() && d_.is_nan() { d } else { to_u64(d_) };
+        assert_eq!(((mode, a, b, c), (d, flags)), ((mode, a, b, c), (d_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fmaf {
+        mode: i32,
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+        d: u32,  // f32
+        flags: i32,
+    }
+
+    impl TestCase for Fmaf {
+        fn name() -> &'static str {
+            "fmaf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mode = *rng.choose(rounds()).unwrap();
+            let (a, b) = (gen_f32_exc(rng, true), gen_f32_exc(rng, true));
+            // Addends close to the negated product exercise cancellation, and addends within the
+            // width of the product exercise the alignment
+            let p = fma_exc(mode, a as u128, b as u128, 0, 23, 8).0 as u32;
+            let c = match rng.gen_range(0, 4) {
+                0 if p & !(1 << 31) < 0x7f80_0000 => {
+                    (p ^ (1 << 31)).wrapping_add(rng.gen_range(0, 5)).wrapping_sub(2)
+                }
+                1 if p & !(1 << 31) < 0x7f80_0000 => {
+                    let shift = rng.gen_range(0, 2 * 23) << 23;
+                    let c = if rng.gen() { p.wrapping_add(shift) } else { p.wrapping_sub(shift) };
+                    c ^ ((rng.gen::<bool>() as u32) << 31)
+                }
+                _ => gen_f32_exc(rng, true),
+            };
+            let (d, flags) = fma_exc(mode, a as u128, b as u128, c as u128, 23, 8);
+
+            Some(
+                Fmaf {
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c,
+                    d: d as u32,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({mode}, {a}, {b}, {c}), ({d}, {flags})),",
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                d = self.d,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::fma;
+use compiler_builtins::float::round::{self, Round};
+
+static TEST_CASES: &[((i32, u32, u32, u32), (u32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fmaf() {
+    for &((mode, a, b, c), (d, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let d_ = fma::fmaf(mk_f32(a), mk_f32(b), mk_f32(c));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let d_ = if mk_f32(d).is_nan() && d_.is_nan() { d } else { to_u32(d_) };
+        assert_eq!(((mode, a, b, c), (d, flags)), ((mode, a, b, c), (d_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fmaf128 {
+        mode: i32,
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+        d: u128,  // f128
+        flags: i32,
+    }
+
+    impl TestCase for Fmaf128 {
+        fn name() -> &'static str {
+            "fmaf128"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mode = *rng.choose(rounds()).unwrap();
+            let (a, b) = (gen_f128(rng), gen_f128(rng));
+            // Addends close to the negated product exercise cancellation, and addends within the
+            // width of the product exercise the alignment
+            let p = fma_exc(mode, a, b, 0, 112, 15).0;
+            let c = match rng.gen_range(0, 4) {
+                0 if p & !F128_SIGN < F128_INF => {
+                    (p ^ F128_SIGN).wrapping_add(rng.gen_range(0u32, 5) as u128).wrapping_sub(2)
+                }
+                1 if p & !F128_SIGN < F128_INF => {
+                    let shift = (rng.gen_range(0u32, 2 * 112) as u128) << 112;
+                    let c = if rng.gen() { p.wrapping_add(shift) } else { p.wrapping_sub(shift) };
+                    c ^ ((rng.gen::<bool>() as u128) << 127)
+                }
+                _ => gen_f128(rng),
+            };
+            let (d, flags) = fma_exc(mode, a, b, c, 112, 15);
+
+            Some(
+                Fmaf128 {
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c,
+                    d: d,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({mode}, {a}, {b}, {c}), ({d}, {flags})),",
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                d = self.d,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::exception;
+use compiler_builtins::float::fma;
+use compiler_builtins::float::round::{self, Round};
+
+static TEST_CASES: &[((i32, u128, u128, u128), (u128, i32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fmaf128() {
+    let is_nan = |x: u128| x & !(1 << 127) > 0x7fff << 112;
+    for &((mode, a, b, c), (d, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let d_ = fma::fmaf128(f128::from_repr(a), f128::from_repr(b), f128::from_repr(c));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let d_ = if is_nan(d) && is_nan(d_.repr()) { d } else { d_.repr() };
+        assert_eq!(((mode, a, b, c), (d, flags)), ((mode, a, b, c), (d_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fmod {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+        flags: i32,
+    }
+
+    impl TestCase for Fmod {
+        fn name() -> &'static str {
+            "fmod"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64_exc(rng, true);
+            let b = if !rng.gen_weighted_bool(4) {
+                // Divisors with an exponent up to a few significand widths below the dividend's
+                let shift = rng.gen_range(0, 3 * 52) << 52;
+                let exponent = (a & !(1 << 63)).wrapping_sub(shift) & !((1 << 52) - 1);
+                exponent | (gen_f64_exc(rng, true) & ((1 << 63) | ((1 << 52) - 1)))
+            } else {
+                gen_f64_exc(rng, true)
+            };
+            let (c, flags) = fmod_exc(a as u128, b as u128, 52, 11);
+
+            Some(
+                Fmod {
+                    a: a,
+                    b: b,
+                    c: c as u64,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {flags})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::fmod;
+
+static TEST_CASES: &[((u64, u64), (u64, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fmod() {
+    for &((a, b), (c, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let c_ = fmod::fmod(mk_f64(a), mk_f64(b));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if mk_f64(c).is_nan() && c_.is_nan() { c } else { to_u64(c_) };
+        assert_eq!(((a, b), (c, flags)), ((a, b), (c_, flags_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fmodf {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+        flags: i32,
+    }
+
+    impl TestCase for Fmodf {
+        fn name() -> &'static str {
+            "fmodf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32_exc(rng, true);
+            let b = if !rng.gen_weighted_bool(4) {
+                // Divisors with an exponent up to a few significand widths below the dividend's
+                let shift = rng.gen_range(0, 3 * 23) << 23;
+                let exponent = (a & !(1 << 31)).wrapping_sub(shift) & !((1 << 23) - 1);
+                exponent | (gen_f32_exc(rng, true) & ((1 << 31) | ((1 << 23) - 1)))
+            } else {
+                gen_f32_exc(rng, true)
+            };
+            let (c, flags) = fmod_exc(a as u128, b as u128, 23, 8);
+
+            Some(
+                Fmodf {
+                    a: a,
+                    b: b,
+                    c: c as u32,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {flags})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::fmod;
+
+static TEST_CASES: &[((u32, u32), (u32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fmodf() {
+    for &((a, b), (c, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let c_ = fmod::fmodf(mk_f32(a), mk_f32(b));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if mk_f32(c).is_nan() && c_.is_nan() { c } else { to_u32(c_) };
+        assert_eq!(((a, b), (c, flags)), ((a, b), (c_, flags_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Fmodf128 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+        flags: i32,
+    }
+
+    impl TestCase for Fmodf128 {
+        fn name() -> &'static str {
+            "fmodf128"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let gen = gen_f128;
+            let a = gen(rng);
+            let b = if !rng.gen_weighted_bool(4) {
+                // Divisors with an exponent up to a few significand widths below the dividend's
+                let shift = (rng.gen_range(0u32, 3 * 112) as u128) << 112;
+                let exponent = (a & !F128_SIGN).wrapping_sub(shift) & !((1 << 112) - 1);
+                exponent | (gen(rng) & (F128_SIGN | ((1 << 112) - 1)))
+            } else {
+                gen(rng)
+            };
+            let (c, flags) = fmod_exc(a, b, 112, 15);
+
+            Some(
+                Fmodf128 {
+                    a: a,
+                    b: b,
+                    c: c,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {flags})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::exception;
+use compiler_builtins::float::fmod;
+
+static TEST_CASES: &[((u128, u128), (u128, i32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn fmodf128() {
+    let is_nan = |x: u128| x & !(1 << 127) > 0x7fff << 112;
+    for &((a, b), (c, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let c_ = fmod::fmodf128(f128::from_repr(a), f128::from_repr(b));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if is_nan(c) && is_nan(c_.repr()) { c } else { c_.repr() };
+        assert_eq!(((a, b), (c, flags)), ((a, b), (c_, flags_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Gedf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
+    }
+
+    impl TestCase for Gedf2 {
+        fn name() -> &'static str {
+            "gedf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = cmp_ge_abi(a, b);
+
+            Some(
+                Gedf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__gedf2;
+
+static TEST_CASES: &[((u64, u64), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn gedf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __gedf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Gesf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: i32,
+    }
+
+    impl TestCase for Gesf2 {
+        fn name() -> &'static str {
+            "gesf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = cmp_ge_abi(a, b);
+
+            Some(
+                Gesf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__gesf2;
+
+static TEST_CASES: &[((u32, u32), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn gesf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __gesf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Getf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
+    }
+
+    impl TestCase for Getf2 {
+        fn name() -> &'static str {
+            "getf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = cmp_ge_abi(F128(a), F128(b));
+
+            Some(
+                Getf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__getf2;
+
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn getf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __getf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Gtdf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
+    }
+
+    impl TestCase for Gtdf2 {
+        fn name() -> &'static str {
+            "gtdf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = cmp_ge_abi(a, b);
+
+            Some(
+                Gtdf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__gtdf2;
+
+static TEST_CASES: &[((u64, u64), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn gtdf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __gtdf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Gtsf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: i32,
+    }
+
+    impl TestCase for Gtsf2 {
+        fn name() -> &'static str {
+            "gtsf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = cmp_ge_abi(a, b);
+
+            Some(
+                Gtsf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__gtsf2;
+
+static TEST_CASES: &[((u32, u32), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn gtsf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __gtsf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Gttf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
+    }
+
+    impl TestCase for Gttf2 {
+        fn name() -> &'static str {
+            "gttf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = cmp_ge_abi(F128(a), F128(b));
+
+            Some(
+                Gttf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__gttf2;
+
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn gttf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __gttf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct IntegralDf {
+        op: u8,
+        mode: i32,
+        a: u64,  // f64
+        b: u64,  // f64
+        flags: i32,
+    }
+
+    impl TestCase for IntegralDf {
+        fn name() -> &'static str {
+            "integral_df"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let op = rng.gen_range(0, 6);
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = if rng.gen_weighted_bool(4) {
+                gen_f64_exc(rng, true)
+            } else {
+                // Numbers with both integer and fraction bits, some of them halfway cases
+                let exponent: u64 = rng.gen_range(1021, 1077);
+                let a = (gen_f64_exc(rng, true) & !(0x7ff0_0000_0000_0000)) | exponent << 52;
+                if rng.gen() && exponent >= 1023 && exponent < 1075 {
+                    let half = 1 << (1075 - 1 - exponent);
+                    (a & !(2 * half - 1)) | half
+                } else {
+                    a
+                }
+            };
+            let (b, flags) = integral_exc(op, mode, a as u128, 52, 11);
+
+            Some(
+                IntegralDf {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b as u64,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}), ({b}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::integral;
+use compiler_builtins::float::round::{self, Round};
+
+static TEST_CASES: &[((u8, i32, u64), (u64, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn integral_df() {
+    // floor, ceil, trunc, round, rint and nearbyint
+    let functions: [extern \"C\" fn(f64) -> f64; 6] =
+        [integral::floor, integral::ceil, integral::trunc, integral::round,
+                     integral::rint, integral::nearbyint];
+    for &((op, mode, a), (b, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let b_ = functions[op as usize](mk_f64(a));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let b_ = if mk_f64(b).is_nan() && b_.is_nan() { b } else { to_u64(b_) };
+        assert_eq!(((op, mode, a), (b, flags)), ((op, mode, a), (b_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct IntegralSf {
+        op: u8,
+        mode: i32,
+        a: u32,  // f32
+        b: u32,  // f32
+        flags: i32,
+    }
+
+    impl TestCase for IntegralSf {
+        fn name() -> &'static str {
+            "integral_sf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let op = rng.gen_range(0, 6);
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = if rng.gen_weighted_bool(4) {
+                gen_f32_exc(rng, true)
+            } else {
+                // Numbers with both integer and fraction bits, some of them halfway cases
+                let exponent: u32 = rng.gen_range(125, 152);
+                let a = (gen_f32_exc(rng, true) & !(0x7f80_0000)) | exponent << 23;
+                if rng.gen() && exponent >= 127 && exponent < 150 {
+                    let half = 1 << (150 - 1 - exponent);
+                    (a & !(2 * half - 1)) | half
+                } else {
+                    a
+                }
+            };
+            let (b, flags) = integral_exc(op, mode, a as u128, 23, 8);
+
+            Some(
+                IntegralSf {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b as u32,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}), ({b}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::integral;
+use compiler_builtins::float::round::{self, Round};
+
+static TEST_CASES: &[((u8, i32, u32), (u32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn integral_sf() {
+    // floor, ceil, trunc, round, rint and nearbyint
+    let functions: [extern \"C\" fn(f32) -> f32; 6] =
+        [integral::floorf, integral::ceilf, integral::truncf, integral::roundf,
+                     integral::rintf, integral::nearbyintf];
+    for &((op, mode, a), (b, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let b_ = functions[op as usize](mk_f32(a));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let b_ = if mk_f32(b).is_nan() && b_.is_nan() { b } else { to_u32(b_) };
+        assert_eq!(((op, mode, a), (b, flags)), ((op, mode, a), (b_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Ledf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
+    }
+
+    impl TestCase for Ledf2 {
+        fn name() -> &'static str {
+            "ledf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Ledf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__ledf2;
+
+static TEST_CASES: &[((u64, u64), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn ledf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __ledf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Lesf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: i32,
+    }
+
+    impl TestCase for Lesf2 {
+        fn name() -> &'static str {
+            "lesf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Lesf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__lesf2;
+
+static TEST_CASES: &[((u32, u32), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn lesf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __lesf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Ltdf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
+    }
+
+    impl TestCase for Ltdf2 {
+        fn name() -> &'static str {
+            "ltdf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Ltdf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__ltdf2;
+
+static TEST_CASES: &[((u64, u64), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn ltdf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __ltdf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Ltsf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: i32,
+    }
+
+    impl TestCase for Ltsf2 {
+        fn name() -> &'static str {
+            "ltsf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Ltsf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__ltsf2;
+
+static TEST_CASES: &[((u32, u32), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn ltsf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __ltsf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Lttf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
+    }
+
+    impl TestCase for Lttf2 {
+        fn name() -> &'static str {
+            "lttf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = cmp_le_abi(F128(a), F128(b));
+
+            Some(
+                Lttf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__lttf2;
+
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn lttf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __lttf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct MathDf {
+        op: u8,
+        a: u64,  // f64
+        b: u64,  // f64, only for `pow`
+        t: u64,  // f64
+        rest: u64,  // f64
+    }
+
+    impl TestCase for MathDf {
+        fn name() -> &'static str {
+            "math_df"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let inf = ::std::f64::INFINITY;
+            let specials = [0.0, -0.0, 1.0, -1.0, 0.5, 2.0, inf, -inf, ::std::f64::NAN];
+            let op = rng.gen_range(0, 5);
+            let (a, b): (f64, f64) = if rng.gen_weighted_bool(8) {
+                (*rng.choose(&specials).unwrap(), *rng.choose(&specials).unwrap())
+            } else if rng.gen_weighted_bool(4) {
+                // Arbitrary bit patterns, which cover the whole exponent range
+                (mk_f64(rng.gen()), mk_f64(rng.gen()))
+            } else {
+                match op {
+                    0 => (rng.gen_range(-746.0, 710.0), 0.0),
+                    1 => {
+                        let a = if rng.gen() {
+                            1.0 + rng.gen_range(-0.0625, 0.0625)
+                        } else {
+                            mk_f64(rng.gen::<u64>() & !(1 << 63))
+                        };
+                        (a, 0.0)
+                    }
+                    2 => {
+                        let a = if rng.gen() {
+                            rng.gen_range(0.0, 16.0)
+                        } else {
+                            1.0 + rng.gen_range(-0.0625, 0.0625)
+                        };
+                        let b = if rng.gen() {
+                            rng.gen_range(-64.0, 64.0)
+                        } else {
+                            rng.gen_range(-64, 64) as f64
+                        };
+                        // A negative base needs an integer power
+                        if b == b.trunc() && rng.gen() { (-a, b) } else { (a, b) }
+                    }
+                    _ => {
+                        let a = if rng.gen() {
+                            rng.gen_range(-10.0, 10.0)
+                        } else {
+                            // Near multiples of pi/2, where the reduction cancels most bits
+                            let n = rng.gen_range(1, 1 << 20) as f64;
+                            let a = n * ::std::f64::consts::FRAC_PI_2;
+                            mk_f64(to_u64(a).wrapping_add(rng.gen_range(0, 5)).wrapping_sub(2))
+                        };
+                        (a, 0.0)
+                    }
+                }
+            };
+            let (t, rest) = math_ulps(op, a as f64, b as f64, 52, 11);
+
+            Some(
+                MathDf {
+                    op: op,
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    t: t as u64,
+                    rest: to_u64(rest),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {a}, {b}), ({t}, {rest})),",
+                op = self.op,
+                a = self.a,
+                b = self.b,
+                t = self.t,
+                rest = self.rest
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{exp, log, pow, trig};
+
+static TEST_CASES: &[((u8, u64, u64), (u64, u64))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn math_df() {
+    // exp, log, pow, sin and cos, which are accurate to within 1 ulp
+    for &((op, a, b), (t, rest)) in TEST_CASES {
+        let (x, y) = (mk_f64(a), mk_f64(b));
+        let c = match op {
+            0 => exp::exp(x),
+            1 => log::log(x),
+            2 => pow::pow(x, y),
+            3 => trig::sin(x),
+            _ => trig::cos(x),
+        };
+        let (t, rest) = (mk_f64(t), mk_f64(rest));
+        if rest == 0.0 {
+            // Special values are exact. Only the NaN-ness of a NaN result is checked
+            let c = if t.is_nan() && c.is_nan() { t } else { c };
+            assert_eq!(((op, a, b), to_u64(t)), ((op, a, b), to_u64(c)));
+        } else {
+            let error = ulp_error(c, t, rest);
+            assert!(error > -1.0 && error < 1.0, \"{:?}\", ((op, a, b), to_u64(c), error));
+        }
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct MathSf {
+        op: u8,
+        a: u32,  // f32
+        b: u32,  // f32, only for `pow`
+        t: u32,  // f32
+        rest: u64,  // f64
+    }
+
+    impl TestCase for MathSf {
+        fn name() -> &'static str {
+            "math_sf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let inf = ::std::f32::INFINITY;
+            let specials = [0.0, -0.0, 1.0, -1.0, 0.5, 2.0, inf, -inf, ::std::f32::NAN];
+            let op = rng.gen_range(0, 5);
+            let (a, b): (f32, f32) = if rng.gen_weighted_bool(8) {
+                (*rng.choose(&specials).unwrap(), *rng.choose(&specials).unwrap())
+            } else if rng.gen_weighted_bool(4) {
+                // Arbitrary bit patterns, which cover the whole exponent range
+                (mk_f32(rng.gen()), mk_f32(rng.gen()))
+            } else {
+                match op {
+                    0 => (rng.gen_range(-104.0, 89.0), 0.0),
+                    1 => {
+                        let a = if rng.gen() {
+                            1.0 + rng.gen_range(-0.0625, 0.0625)
+                        } else {
+                            mk_f32(rng.gen::<u32>() & !(1 << 31))
+                        };
+                        (a, 0.0)
+                    }
+                    2 => {
+                        let a = if rng.gen() {
+                            rng.gen_range(0.0, 16.0)
+                        } else {
+                            1.0 + rng.gen_range(-0.0625, 0.0625)
+                        };
+                        let b = if rng.gen() {
+                            rng.gen_range(-64.0, 64.0)
+                        } else {
+                            rng.gen_range(-64, 64) as f32
+                        };
+                        // A negative base needs an integer power
+                        if b == b.trunc() && rng.gen() { (-a, b) } else { (a, b) }
+                    }
+                    _ => {
+                        let a = if rng.gen() {
+                            rng.gen_range(-10.0, 10.0)
+                        } else {
+                            // Near multiples of pi/2, where the reduction cancels most bits
+                            let n = rng.gen_range(1, 1 << 20) as f32;
+                            let a = n * ::std::f32::consts::FRAC_PI_2;
+                            mk_f32(to_u32(a).wrapping_add(rng.gen_range(0, 5)).wrapping_sub(2))
+                        };
+                        (a, 0.0)
+                    }
+                }
+            };
+            let (t, rest) = math_ulps(op, a as f64, b as f64, 23, 8);
+
+            Some(
+                MathSf {
+                    op: op,
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    t: t as u32,
+                    rest: to_u64(rest),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {a}, {b}), ({t}, {rest})),",
+                op = self.op,
+                a = self.a,
+                b = self.b,
+                t = self.t,
+                rest = self.rest
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{exp, log, pow, trig};
+
+static TEST_CASES: &[((u8, u32, u32), (u32, u64))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn math_sf() {
+    // expf, logf, powf, sinf and cosf, which are accurate to within 1 ulp
+    for &((op, a, b), (t, rest)) in TEST_CASES {
+        let (x, y) = (mk_f32(a), mk_f32(b));
+        let c = match op {
+            0 => exp::expf(x),
+            1 => log::logf(x),
+            2 => pow::powf(x, y),
+            3 => trig::sinf(x),
+            _ => trig::cosf(x),
+        };
+        let (t, rest) = (mk_f32(t), mk_f64(rest));
+        if rest == 0.0 {
+            // Special values are exact. Only the NaN-ness of a NaN result is checked
+            let c = if t.is_nan() && c.is_nan() { t } else { c };
+            assert_eq!(((op, a, b), to_u32(t)), ((op, a, b), to_u32(c)));
+        } else {
+            let error = ulp_errorf(c, t, rest);
+            assert!(error > -1.0 && error < 1.0, \"{:?}\", ((op, a, b), to_u32(c), error));
+        }
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct MinmaxDf {
+        op: u8,
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+        flags: i32,
+    }
+
+    impl TestCase for MinmaxDf {
+        fn name() -> &'static str {
+            "minmax_df"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let op = rng.gen_range(0, 4);
+            let a = gen_f64_exc(rng, false);
+            let b = if rng.gen_weighted_bool(8) {
+                // Equal numbers and zeros of either sign
+                a ^ (rng.gen::<u64>() & (1 << 63))
+            } else {
+                gen_f64_exc(rng, false)
+            };
+            let (c, flags) = minmax_exc(op, a as u128, b as u128, 52, 11);
+
+            Some(
+                MinmaxDf {
+                    op: op,
+                    a: a,
+                    b: b,
+                    c: c as u64,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {a}, {b}), ({c}, {flags})),",
+                op = self.op,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::minmax;
+
+static TEST_CASES: &[((u8, u64, u64), (u64, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn minmax_df() {
+    // fmin, fmax, fminimum and fmaximum
+    let functions: [extern \"C\" fn(f64, f64) -> f64; 4] =
+        [minmax::fmin, minmax::fmax, minmax::fminimum, minmax::fmaximum];
+    for &((op, a, b), (c, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let c_ = functions[op as usize](mk_f64(a), mk_f64(b));
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, a, b), (c, flags)), ((op, a, b), (to_u64(c_), flags_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct MinmaxSf {
+        op: u8,
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+        flags: i32,
+    }
+
+    impl TestCase for MinmaxSf {
+        fn name() -> &'static str {
+            "minmax_sf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let op = rng.gen_range(0, 4);
+            let a = gen_f32_exc(rng, false);
+            let b = if rng.gen_weighted_bool(8) {
+                // Equal numbers and zeros of either sign
+                a ^ (rng.gen::<u32>() & (1 << 31))
+            } else {
+                gen_f32_exc(rng, false)
+            };
+            let (c, flags) = minmax_exc(op, a as u128, b as u128, 23, 8);
+
+            Some(
+                MinmaxSf {
+                    op: op,
+                    a: a,
+                    b: b,
+                    c: c as u32,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {a}, {b}), ({c}, {flags})),",
+                op = self.op,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::minmax;
+
+static TEST_CASES: &[((u8, u32, u32), (u32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn minmax_sf() {
+    // fmin, fmax, fminimum and fmaximum
+    let functions: [extern \"C\" fn(f32, f32) -> f32; 4] =
+        [minmax::fminf, minmax::fmaxf, minmax::fminimumf, minmax::fmaximumf];
+    for &((op, a, b), (c, flags)) in TEST_CASES {
+        exception::clear(exception::ALL);
+        let c_ = functions[op as usize](mk_f32(a), mk_f32(b));
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, a, b), (c, flags)), ((op, a, b), (to_u32(c_), flags_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Moddi3 {
+        a: i64,
+        b: i64,
+        c: i64,
+    }
+
+    impl TestCase for Moddi3 {
+        fn name() -> &'static str {
+            "moddi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i64(rng);
+            let b = gen_i64(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a % b;
+
+            Some(Moddi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::sdiv::__moddi3;
+
+static TEST_CASES: &[((i64, i64), i64)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn moddi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __moddi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Modsi3 {
+        a: i32,
+        b: i32,
+        c: i32,
+    }
+
+    impl TestCase for Modsi3 {
+        fn name() -> &'static str {
+            "modsi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i32(rng);
+            let b = gen_i32(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a % b;
+
+            Some(Modsi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::sdiv::__modsi3;
+
+static TEST_CASES: &[((i32, i32), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn modsi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __modsi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Modti3 {
+        a: i128,
+        b: i128,
+        c: i128,
+    }
+
+    impl TestCase for Modti3 {
+        fn name() -> &'static str {
+            "modti3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i128(rng);
+            let b = gen_i128(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a % b;
+
+            Some(Modti3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::sdiv::__modti3;
+
+static TEST_CASES: &[((i128, i128), i128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn modti3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __modti3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Muldc3 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+        d: u64,  // f64
+        e: u64,  // f64, real part
+        f: u64,  // f64, imaginary part
+    }
+
+    impl TestCase for Muldc3 {
+        fn name() -> &'static str {
+            "muldc3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_complex_f64(rng);
+            let b = gen_complex_f64(rng);
+            let c = gen_complex_f64(rng);
+            let d = gen_complex_f64(rng);
+            let (e, f) = mulc3(a, b, c, d);
+
+            Some(
+                Muldc3 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c: to_u64(c),
+                    d: to_u64(d),
+                    e: to_u64(e),
+                    f: to_u64(f),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}, {c}, {d}), ({e}, {f})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                d = self.d,
+                e = self.e,
+                f = self.f
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::complex::__muldc3;
+
+// Equal representations, or both NaN
+fn same(x: u64, y: u64) -> bool {
+    let abs_mask = !0 >> 1;
+    let inf = 0x7ff0_0000_0000_0000;
+    x == y || (x & abs_mask > inf && y & abs_mask > inf)
+}
+
+static TEST_CASES: &[((u64, u64, u64, u64), (u64, u64))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn muldc3() {
+    for &((a, b, c, d), (e, f)) in TEST_CASES {
+        let z = __muldc3(mk_f64(a), mk_f64(b), mk_f64(c), mk_f64(d));
+        let (e_, f_) = (to_u64(z.re), to_u64(z.im));
+        if !same(e, e_) || !same(f, f_) {
+            assert_eq!(((a, b, c, d), (e, f)), ((a, b, c, d), (e_, f_)));
+        }
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Muldf3 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+    }
+
+    impl TestCase for Muldf3 {
+        fn name() -> &'static str {
+            "muldf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            // Besides random operands, some whose products are below the normal range, which the
+            // original port misrounded when its sticky bit spilled into the round bit
+            const REGRESSIONS: &[(u64, u64)] = &[
+                (0x3fe0_0000_0000_0001, 0x0010_0000_0000_0001),
+                (0x0000_0000_0000_0003, 0x3fe0_0000_0000_0000),
+                (0x0000_0000_0000_0001, 0x3fe0_0000_0000_0000),
+                (0x0010_0000_0000_0000, 0x3cb0_0000_0000_0000),
+            ];
+
+            let (a, b) = if rng.gen_weighted_bool(10) {
+                let &(a, b) = rng.choose(REGRESSIONS).unwrap();
+                (mk_f64(a), mk_f64(b))
+            } else {
+                (gen_f64(rng), gen_f64(rng))
+            };
+            let c = a * b;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() || b.is_nan() || c.is_nan() {
+                return None;
+            }
+
+            Some(
+                Muldf3 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c: to_u64(c),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::mul::__muldf3;
+
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn muldf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __muldf3(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    struct Muldi3 {
+        a: u64,
+        b: u64,
+        c: u64,
+    }
+
+    impl TestCase for Muldi3 {
+        fn name() -> &'static str {
+            "muldi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            let b = gen_u64(rng);
+            let c = a.wrapping_mul(b);
+
+            Some(Muldi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::mul::__muldi3;
+
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn muldi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __muldi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Mulodi4 {
+        a: i64,
+        b: i64,
+        c: i64,
+        overflow: u32,
+    }
+
+    impl TestCase for Mulodi4 {
+        fn name() -> &'static str {
+            "mulodi4"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+        {
+            let a = gen_i64(rng);
+            let b = gen_i64(rng);
+            let c = a.wrapping_mul(b);
+            let overflow = if a.checked_mul(b).is_some() { 0 } else { 1 };
+
+            Some(Mulodi4 { a, b, c, overflow })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {overflow})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                overflow = self.overflow
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::mul::__mulodi4;
+
+static TEST_CASES: &[((i64, i64), (i64, i32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn mulodi4() {
+    let mut overflow_ = 2;
+    for &((a, b), (c, overflow)) in TEST_CASES {
+        let c_ = __mulodi4(a, b, &mut overflow_);
+        assert_eq!(((a, b), (c, overflow)), ((a, b), (c_, overflow_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Mulosi4 {
+        a: i32,
+        b: i32,
+        c: i32,
+        overflow: u32,
+    }
+
+    impl TestCase for Mulosi4 {
+        fn name() -> &'static str {
+            "mulosi4"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+        {
+            let a = gen_i32(rng);
+            let b = gen_i32(rng);
+            let c = a.wrapping_mul(b);
+            let overflow = if a.checked_mul(b).is_some() { 0 } else { 1 };
+
+            Some(Mulosi4 { a, b, c, overflow })
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::mul::__mulosi4;
+
+static TEST_CASES: &[((i32, i32), (i32, i32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn mulosi4() {
+    let mut overflow_ = 2;
+    for &((a, b), (c, overflow)) in TEST_CASES {
+        let c_ = __mulosi4(a, b, &mut overflow_);
+        assert_eq!(((a, b), (c, overflow)), ((a, b), (c_, overflow_)));
+    }
+}
+"
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {overflow})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                overflow = self.overflow
+            )
+                    .unwrap();
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Muloti4 {
+        a: i128,
+        b: i128,
+        c: i128,
+        overflow: u32,
+    }
+
+    impl TestCase for Muloti4 {
+        fn name() -> &'static str {
+            "muloti4"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+        {
+            let a = gen_i128(rng);
+            let b = gen_i128(rng);
+            let c = a.wrapping_mul(b);
+            let overflow = if a.checked_mul(b).is_some() { 0 } else { 1 };
+
+            Some(Muloti4 { a, b, c, overflow })
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::mul::__muloti4;
+
+static TEST_CASES: &[((i128, i128), (i128, i32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn muloti4() {
+    let mut overflow_ = 2;
+    for &((a, b), (c, overflow)) in TEST_CASES {
+        let c_ = __muloti4(a, b, &mut overflow_);
+        assert_eq!(((a, b), (c, overflow)), ((a, b), (c_, overflow_)));
+    }
+}
+"
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {overflow})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                overflow = self.overflow
+            )
+                    .unwrap();
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Mulsc3 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+        d: u32,  // f32
+        e: u32,  // f32, real part
+        f: u32,  // f32, imaginary part
+    }
+
+    impl TestCase for Mulsc3 {
+        fn name() -> &'static str {
+            "mulsc3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_complex_f32(rng);
+            let b = gen_complex_f32(rng);
+            let c = gen_complex_f32(rng);
+            let d = gen_complex_f32(rng);
+            let (e, f) = mulc3(a, b, c, d);
+
+            Some(
+                Mulsc3 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c: to_u32(c),
+                    d: to_u32(d),
+                    e: to_u32(e),
+                    f: to_u32(f),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}, {c}, {d}), ({e}, {f})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                d = self.d,
+                e = self.e,
+                f = self.f
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::complex::__mulsc3;
+
+// Equal representations, or both NaN
+fn same(x: u32, y: u32) -> bool {
+    let abs_mask = !0 >> 1;
+    let inf = 0x7f80_0000;
+    x == y || (x & abs_mask > inf && y & abs_mask > inf)
+}
+
+static TEST_CASES: &[((u32, u32, u32, u32), (u32, u32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn mulsc3() {
+    for &((a, b, c, d), (e, f)) in TEST_CASES {
+        let z = __mulsc3(mk_f32(a), mk_f32(b), mk_f32(c), mk_f32(d));
+        let (e_, f_) = (to_u32(z.re), to_u32(z.im));
+        if !same(e, e_) || !same(f, f_) {
+            assert_eq!(((a, b, c, d), (e, f)), ((a, b, c, d), (e_, f_)));
+        }
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Mulsf3 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+    }
+
+    impl TestCase for Mulsf3 {
+        fn name() -> &'static str {
+            "mulsf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            // Besides random operands, some whose products are below the normal range, which the
+            // original port misrounded when its sticky bit spilled into the round bit
+            const REGRESSIONS: &[(u32, u32)] = &[
+                (0x3f00_0001, 0x0080_0001),
+                (0x0000_0003, 0x3f00_0000),
+                (0x0000_0001, 0x3f00_0000),
+                (0x0080_0000, 0x3400_0000),
+            ];
+
+            let (a, b) = if rng.gen_weighted_bool(10) {
+                let &(a, b) = rng.choose(REGRESSIONS).unwrap();
+                (mk_f32(a), mk_f32(b))
+            } else {
+                (gen_f32(rng), gen_f32(rng))
+            };
+            let c = a * b;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() || b.is_nan() || c.is_nan() {
+                return None;
+            }
+
+            Some(
+                Mulsf3 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c: to_u32(c),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::mul::__mulsf3;
+
+static TEST_CASES: &[((u32, u32), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn mulsf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __mulsf3(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Multf3 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+    }
+
+    impl TestCase for Multf3 {
+        fn name() -> &'static str {
+            "multf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128(rng);
+            // Operands of similar magnitude exercise cancellation and rounding more often
+            let b = if rng.gen() {
+                gen_f128(rng)
+            } else {
+                a ^ (gen_u128(rng) >> rng.gen_range(0, 128))
+            };
+            // TODO accept NaNs. We don't do that right now because the NaN produced by the
+            // reference implementation isn't necessarily the one the intrinsic returns
+            if f128_is_nan(a) || f128_is_nan(b) {
+                return None;
+            }
+            let c = match f128_mul(Round::ToNearest, a, b) {
+                Some(c) => c,
+                None => return None,
+            };
+
+            Some(
+                Multf3 {
+                    a: a,
+                    b: b,
+                    c: c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::mul::__multf3;
+
+static TEST_CASES: &[((u128, u128), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn multf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __multf3(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Multi3 {
+        a: i128,
+        b: i128,
+        c: i128,
+    }
+
+    impl TestCase for Multi3 {
+        fn name() -> &'static str {
+            "multi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_i128(rng);
+            let b = gen_i128(rng);
+            let c = a.wrapping_mul(b);
+
+            Some(Multi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::mul::__multi3;
+
+static TEST_CASES: &[((i128, i128), i128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn multi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __multi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct NanMode {
+        op: u8,
+        mode: i32,
+        a: u128,
+        b: u128,
+        c: u128,
+        r: u128,
+        flags: i32,
+    }
+
+    impl TestCase for NanMode {
+        fn name() -> &'static str {
+            "nan_mode"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            // The significand and exponent bits of f16, f32, f64 and f128
+            const FORMATS: [(u32, u32); 4] = [(10, 5), (23, 8), (52, 11), (112, 15)];
+            // The operations `add`, `sub`, `mul`, `div`, `fma`, `fmod`, `sqrt`, `rint`,
+            // `scalbn`, `exp`, `log`, `pow`, `sin`, `cos`, `fmin` and `fmaximum` on f32 and f64
+            // take as many operands as this, then come those of f128 that exist, and the
+            // conversions between formats
+            const ARITIES: [usize; 16] = [2, 2, 2, 2, 3, 2, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2];
+            const CONVERSIONS: [(usize, usize); 9] =
+                [(0, 1), (1, 2), (1, 3), (2, 3), (1, 0), (2, 0), (2, 1), (3, 1), (3, 2)];
+
+            let op = rng.gen_range(0, 48);
+            let (arity, src, dst) = if op < 32 {
+                (ARITIES[op % 16], op / 16 + 1, op / 16 + 1)
+            } else if op < 39 {
+                (ARITIES[op - 32], 3, 3)
+            } else {
+                (1, CONVERSIONS[op - 39].0, CONVERSIONS[op - 39].1)
+            };
+            let (src_sb, src_eb) = FORMATS[src];
+            let (dst_sb, dst_eb) = FORMATS[dst];
+            let src_quiet = 1 << (src_sb - 1);
+            let src_inf = ((1 << src_eb) - 1) << src_sb;
+            let dst_quiet = 1 << (dst_sb - 1);
+            let dst_inf = ((1 << dst_eb) - 1) << dst_sb;
+
+            // NaNs with random payloads, quiet or signaling, and finite numbers, with at least one
+            // NaN among the operands
+            let mut operands = [0; 3];
+            let first_nan = rng.gen_range(0, arity);
+            for (i, x) in operands.iter_mut().enumerate().take(arity) {
+                let sign = (rng.gen::<bool>() as u128) << (src_sb + src_eb);
+                let significand = gen_u128(rng) & ((1 << src_sb) - 1);
+                *x = if i == first_nan || rng.gen() {
+                    let significand = if rng.gen() {
+                        significand | src_quiet
+                    } else {
+                        (significand & (src_quiet - 1)).max(1)
+                    };
+                    sign | src_inf | significand
+                } else {
+                    let exponent = rng.gen_range(1, (1 << src_eb) - 1) as u128;
+                    sign | exponent << src_sb | significand
+                };
+            }
+            let is_nan = |x: u128| x & (src_inf | ((1 << src_sb) - 1)) > src_inf;
+            let nans = operands[..arity].iter().cloned().filter(|&x| is_nan(x));
+            let flags = if nans.clone().any(|x| x & src_quiet == 0) { FE_INVALID } else { 0 };
+
+            // The first NaN operand, quieted and with its payload aligned to the top of the
+            // result's significand, or the default NaN. `fmin` treats a quiet NaN as missing data
+            let mode = rng.gen_range(0, 2);
+            let nan = nans.clone().next().unwrap();
+            let r = if op < 32 && op % 16 == 14 && flags == 0 && nans.count() == 1 {
+                operands[..2].iter().cloned().find(|&x| !is_nan(x)).unwrap()
+            } else if mode == 0 {
+                let sign = (nan >> (src_sb + src_eb) & 1) << (dst_sb + dst_eb);
+                let significand = (nan | src_quiet) & ((1 << src_sb) - 1);
+                let significand = if dst_sb >= src_sb {
+                    significand << (dst_sb - src_sb)
+                } else {
+                    significand >> (src_sb - dst_sb)
+                };
+                sign | dst_inf | significand
+            } else {
+                dst_inf | dst_quiet
+            };
+
+            Some(
+                NanMode {
+                    op: op as u8,
+                    mode: mode,
+                    a: operands[0],
+                    b: operands[1],
+                    c: operands[2],
+                    r: r,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}, {c}), ({r}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                r = self.r,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::add::{__adddf3, __addsf3, __addtf3};
+use compiler_builtins::float::div::{__divdf3, __divsf3, __divtf3};
+use compiler_builtins::float::exception;
+use compiler_builtins::float::exp::{exp, expf};
+use compiler_builtins::float::extend::{__extenddftf2, __extendhfsf2, __extendsfdf2,
+                                       __extendsftf2};
+use compiler_builtins::float::fma::{fma, fmaf, fmaf128};
+use compiler_builtins::float::fmod::{fmod, fmodf, fmodf128};
+use compiler_builtins::float::integral::{rint, rintf};
+use compiler_builtins::float::log::{log, logf};
+use compiler_builtins::float::minmax::{fmaximum, fmaximumf, fmin, fminf};
+use compiler_builtins::float::mul::{__muldf3, __mulsf3, __multf3};
+use compiler_builtins::float::nan::{self, Nan};
+use compiler_builtins::float::pow::{pow, powf};
+use compiler_builtins::float::scale::{scalbn, scalbnf};
+use compiler_builtins::float::sqrt::{sqrt, sqrtf, sqrtf128};
+use compiler_builtins::float::sub::{__subdf3, __subsf3, __subtf3};
+use compiler_builtins::float::trig::{cos, cosf, sin, sinf};
+use compiler_builtins::float::trunc::{__truncdfhf2, __truncdfsf2, __truncsfhf2, __trunctfdf2,
+                                      __trunctfsf2};
+
+static TEST_CASES: &[((u8, i32, u128, u128, u128), (u128, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+// The NaN each intrinsic returns for NaN operands is compared bit for bit, in both NaN modes
+#[test]
+fn nan_mode() {
+    for &((op, mode, a, b, c), (r, flags)) in TEST_CASES {
+        nan::set_mode(Nan::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let r_ = match op {
+            0 => to_u32(__addsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            1 => to_u32(__subsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            2 => to_u32(__mulsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            3 => to_u32(__divsf3(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            4 => to_u32(fmaf(mk_f32(a as u32), mk_f32(b as u32), mk_f32(c as u32))) as u128,
+            5 => to_u32(fmodf(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            6 => to_u32(sqrtf(mk_f32(a as u32))) as u128,
+            7 => to_u32(rintf(mk_f32(a as u32))) as u128,
+            8 => to_u32(scalbnf(mk_f32(a as u32), 3)) as u128,
+            9 => to_u32(expf(mk_f32(a as u32))) as u128,
+            10 => to_u32(logf(mk_f32(a as u32))) as u128,
+            11 => to_u32(powf(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            12 => to_u32(sinf(mk_f32(a as u32))) as u128,
+            13 => to_u32(cosf(mk_f32(a as u32))) as u128,
+            14 => to_u32(fminf(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            15 => to_u32(fmaximumf(mk_f32(a as u32), mk_f32(b as u32))) as u128,
+            16 => to_u64(__adddf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            17 => to_u64(__subdf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            18 => to_u64(__muldf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            19 => to_u64(__divdf3(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            20 => to_u64(fma(mk_f64(a as u64), mk_f64(b as u64), mk_f64(c as u64))) as u128,
+            21 => to_u64(fmod(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            22 => to_u64(sqrt(mk_f64(a as u64))) as u128,
+            23 => to_u64(rint(mk_f64(a as u64))) as u128,
+            24 => to_u64(scalbn(mk_f64(a as u64), 3)) as u128,
+            25 => to_u64(exp(mk_f64(a as u64))) as u128,
+            26 => to_u64(log(mk_f64(a as u64))) as u128,
+            27 => to_u64(pow(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            28 => to_u64(sin(mk_f64(a as u64))) as u128,
+            29 => to_u64(cos(mk_f64(a as u64))) as u128,
+            30 => to_u64(fmin(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            31 => to_u64(fmaximum(mk_f64(a as u64), mk_f64(b as u64))) as u128,
+            32 => __addtf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            33 => __subtf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            34 => __multf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            35 => __divtf3(f128::from_repr(a), f128::from_repr(b)).repr(),
+            36 => fmaf128(f128::from_repr(a), f128::from_repr(b), f128::from_repr(c)).repr(),
+            37 => fmodf128(f128::from_repr(a), f128::from_repr(b)).repr(),
+            38 => sqrtf128(f128::from_repr(a)).repr(),
+            39 => to_u32(__extendhfsf2(a as u16)) as u128,
+            40 => to_u64(__extendsfdf2(mk_f32(a as u32))) as u128,
+            41 => __extendsftf2(mk_f32(a as u32)).repr(),
+            42 => __extenddftf2(mk_f64(a as u64)).repr(),
+            43 => __truncsfhf2(mk_f32(a as u32)) as u128,
+            44 => __truncdfhf2(mk_f64(a as u64)) as u128,
+            45 => to_u32(__truncdfsf2(mk_f64(a as u64))) as u128,
+            46 => to_u32(__trunctfsf2(f128::from_repr(a))) as u128,
+            _ => to_u64(__trunctfdf2(f128::from_repr(a))) as u128,
+        };
+        let flags_ = exception::test(exception::ALL);
+        assert_eq!(((op, mode, a, b, c), (r, flags)), ((op, mode, a, b, c), (r_, flags_)));
+    }
+    nan::set_mode(Nan::Propagate);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Nedf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
+    }
+
+    impl TestCase for Nedf2 {
+        fn name() -> &'static str {
+            "nedf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Nedf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__nedf2;
+
+static TEST_CASES: &[((u64, u64), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn nedf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __nedf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Negdf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+    }
+
+    impl TestCase for Negdf2 {
+        fn name() -> &'static str {
+            "negdf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            Some(
+                Negdf2 {
+                    a: to_u64(a),
+                    b: to_u64(-a),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::neg::__negdf2;
+
+static TEST_CASES: &[((u64,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn negdf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __negdf2(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Negsf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+    }
+
+    impl TestCase for Negsf2 {
+        fn name() -> &'static str {
+            "negsf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            Some(
+                Negsf2 {
+                    a: to_u32(a),
+                    b: to_u32(-a),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::neg::__negsf2;
+
+static TEST_CASES: &[((u32,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn negsf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __negsf2(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Nesf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: i32,
+    }
+
+    impl TestCase for Nesf2 {
+        fn name() -> &'static str {
+            "nesf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = cmp_le_abi(a, b);
+
+            Some(
+                Nesf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__nesf2;
+
+static TEST_CASES: &[((u32, u32), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn nesf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __nesf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Netf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
+    }
+
+    impl TestCase for Netf2 {
+        fn name() -> &'static str {
+            "netf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = cmp_le_abi(F128(a), F128(b));
+
+            Some(
+                Netf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__netf2;
+
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn netf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __netf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Powidf2 {
+        a: u64,  // f64
+        b: i32,
+        c: u64,  // f64
+    }
+
+    impl TestCase for Powidf2 {
+        fn name() -> &'static str {
+            "powidf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = gen_i32(rng);
+            let c = a.powi(b);
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets
+            if a.is_nan() || c.is_nan() {
+                return None;
+            }
+            // The host inverts the positive power, which is only the same as ours while that
+            // stays normal (`ExceptionsDf3` and `ExceptionsSf3` cover the other cases)
+            if b < 0 && a != 0.0 && a.is_finite() &&
+                !b.checked_neg().map_or(false, |b| a.powi(b).is_normal()) {
+                return None;
+            }
+
+            Some(
+                Powidf2 {
+                    a: to_u64(a),
+                    b,
+                    c: to_u64(c),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::pow::__powidf2;
+
+static TEST_CASES: &[((u64, i32), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn powidf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __powidf2(mk_f64(a), b);
+        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Powisf2 {
+        a: u32,  // f32
+        b: i32,
+        c: u32,  // f32
+    }
+
+    impl TestCase for Powisf2 {
+        fn name() -> &'static str {
+            "powisf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = gen_i32(rng);
+            let c = a.powi(b);
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets
+            if a.is_nan() || c.is_nan() {
+                return None;
+            }
+            // The host inverts the positive power, which is only the same as ours while that
+            // stays normal (`ExceptionsDf3` and `ExceptionsSf3` cover the other cases)
+            if b < 0 && a != 0.0 && a.is_finite() &&
+                !b.checked_neg().map_or(false, |b| a.powi(b).is_normal()) {
+                return None;
+            }
+
+            Some(
+                Powisf2 {
+                    a: to_u32(a),
+                    b,
+                    c: to_u32(c),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::pow::__powisf2;
+
+static TEST_CASES: &[((u32, i32), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn powisf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __powisf2(mk_f32(a), b);
+        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Letf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
+    }
+
+    impl TestCase for Letf2 {
+        fn name() -> &'static str {
+            "letf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = cmp_le_abi(F128(a), F128(b));
+
+            Some(
+                Letf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__letf2;
+
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn letf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __letf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Lshrdi3 {
+        a: u64,
+        b: u32,
+        c: u64,
+    }
+
+    impl TestCase for Lshrdi3 {
+        fn name() -> &'static str {
+            "lshrdi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            let b = (rng.gen::<u8>() % 64) as u32;
+            let c = a >> b;
+
+            Some(Lshrdi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::shift::__lshrdi3;
+
+static TEST_CASES: &[((u64, u32), u64)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn lshrdi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __lshrdi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Lshrti3 {
+        a: u128,
+        b: u32,
+        c: u128,
+    }
+
+    impl TestCase for Lshrti3 {
+        fn name() -> &'static str {
+            "lshrti3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            let b = (rng.gen::<u8>() % 128) as u32;
+            let c = a >> b;
+
+            Some(Lshrti3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::shift::__lshrti3;
+
+static TEST_CASES: &[((u128, u32), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn lshrti3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __lshrti3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Powixf2 {
+        a: u128,  // f80
+        b: i32,
+        c: u128,  // f80
+    }
+
+    impl TestCase for Powixf2 {
+        fn name() -> &'static str {
+            "powixf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            // Besides random operands, some negative powers of which the positive power overflows
+            // while the result is a denormal, which the original port flushed to zero
+            const REGRESSIONS: &[(u128, i32)] = &[
+                (0x4000_8000_0000_0000_0000, -16400),
+                (0xc000_8000_0000_0000_0000, -16401),
+                (0x4000_c000_0000_0000_0000, -10370),
+                (0x3fff_c000_0000_0000_0001, -28050),
+            ];
+
+            // Mostly bases close to one so that the results aren't all zero or infinity
+            let (a, b) = if rng.gen_weighted_bool(20) {
+                *rng.choose(REGRESSIONS).unwrap()
+            } else {
+                let a = if rng.gen_weighted_bool(4) {
+                    gen_x87(rng)
+                } else {
+                    gen_x87_exponent(rng, -2, 2)
+                };
+                let b = if rng.gen_weighted_bool(4) {
+                    gen_i32(rng)
+                } else {
+                    rng.gen_range(-10000, 10000)
+                };
+                (a, b)
+            };
+            if x87_is_nan(a) {
+                return None;
+            }
+            x87_powi(a, b).map(|c| Powixf2 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::f80;
+use compiler_builtins::float::xf::__rust_powixf2;
+
+static TEST_CASES: &[((u128, i32), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn powixf2() {
+    for &((a, b), c) in TEST_CASES {
+        let mut c_ = f80::from_repr(0);
+        __rust_powixf2(&f80::from_repr(a), b, &mut c_);
+        assert_eq!(((a, b), c), ((a, b), c_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct RoundingConv {
+        op: u8,
+        mode: i32,
+        a: u128,
+        b: u128,
+    }
+
+    impl TestCase for RoundingConv {
+        fn name() -> &'static str {
+            "rounding_conv"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 6);
+            let (a, b) = match op {
+                0 => {
+                    let a = gen_u128(rng);
+                    (a, round_binary_with(mode, false, 0, a, false, 0, 23, 8))
+                }
+                1 | 2 => {
+                    let a = gen_i128(rng);
+                    let magnitude = if a < 0 { (a as u128).wrapping_neg() } else { a as u128 };
+                    let (significand_bits, exponent_bits) = if op == 1 {
+                        (52, 11)
+                    } else {
+                        (112, 15)
+                    };
+                    let b = round_binary_with(
+                        mode,
+                        a < 0,
+                        0,
+                        magnitude,
+                        false,
+                        0,
+                        significand_bits,
+                        exponent_bits,
+                    );
+                    (a as u128, b)
+                }
+                3 => {
+                    let a = gen_f64(rng);
+                    if a.is_nan() {
+                        return None;
+                    }
+                    (to_u64(a) as u128, f128_narrow_with(mode, f128_from_f64(a), 23, 8))
+                }
+                4 => {
+                    let a = gen_f128_exponent(rng, -1100, 1100);
+                    if f128_is_nan(a) {
+                        return None;
+                    }
+                    (a, f128_narrow_with(mode, a, 52, 11))
+                }
+                _ => {
+                    let a = gen_f32(rng);
+                    if a.is_nan() {
+                        return None;
+                    }
+                    (to_u32(a) as u128, f128_narrow_with(mode, f128_from_f64(a as f64), 10, 5))
+                }
+            };
+
+            Some(
+                RoundingConv {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}), {b}),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::conv::{__floattidf, __floattitf, __floatuntisf};
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::trunc::{__truncdfsf2, __truncsfhf2, __trunctfdf2};
+
+static TEST_CASES: &[((u8, i32, u128), u128)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn rounding_conv() {
+    for &((op, mode, a), b) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        let b_ = match op {
+            0 => to_u32(__floatuntisf(a)) as u128,
+            1 => to_u64(__floattidf(a as i128)) as u128,
+            2 => __floattitf(a as i128).repr(),
+            3 => to_u32(__truncdfsf2(mk_f64(a as u64))) as u128,
+            4 => to_u64(__trunctfdf2(f128::from_repr(a))) as u128,
+            _ => __truncsfhf2(mk_f32(a as u32)) as u128,
+        };
+        assert_eq!(((op, mode, a), b), ((op, mode, a), b_));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct RoundingDf3 {
+        op: u8,
+        mode: i32,
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+    }
+
+    impl TestCase for RoundingDf3 {
+        fn name() -> &'static str {
+            "rounding_df3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(8) {
+                *rng.choose(&[a, -a]).unwrap()
+            } else {
+                gen_f64(rng)
+            };
+            if a.is_nan() || b.is_nan() {
+                return None;
+            }
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 4);
+            let c = match f128_arith_exact(mode, op, f128_from_f64(a), f128_from_f64(b)) {
+                Some(c) => c.round(mode, 52, 11).0 as u64,
+                None => return None,
+            };
+
+            Some(
+                RoundingDf3 {
+                    op: op,
+                    mode: mode as i32,
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c: c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), {c}),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::add::__adddf3;
+use compiler_builtins::float::div::__divdf3;
+use compiler_builtins::float::mul::__muldf3;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::__subdf3;
+
+static TEST_CASES: &[((u8, i32, u64, u64), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn rounding_df3() {
+    for &((op, mode, a, b), c) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        let (x, y) = (mk_f64(a), mk_f64(b));
+        let c_ = match op {
+            0 => __adddf3(x, y),
+            1 => __subdf3(x, y),
+            2 => __muldf3(x, y),
+            _ => __divdf3(x, y),
+        };
+        assert_eq!(((op, mode, a, b), c), ((op, mode, a, b), to_u64(c_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct RoundingSf3 {
+        op: u8,
+        mode: i32,
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+    }
+
+    impl TestCase for RoundingSf3 {
+        fn name() -> &'static str {
+            "rounding_sf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(8) {
+                *rng.choose(&[a, -a]).unwrap()
+            } else {
+                gen_f32(rng)
+            };
+            if a.is_nan() || b.is_nan() {
+                return None;
+            }
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 4);
+            let (a_, b_) = (f128_from_f64(a as f64), f128_from_f64(b as f64));
+            let c = match f128_arith_exact(mode, op, a_, b_) {
+                Some(c) => c.round(mode, 23, 8).0 as u32,
+                None => return None,
+            };
+
+            Some(
+                RoundingSf3 {
+                    op: op,
+                    mode: mode as i32,
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c: c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), {c}),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::add::__addsf3;
+use compiler_builtins::float::div::__divsf3;
+use compiler_builtins::float::mul::__mulsf3;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::__subsf3;
+
+static TEST_CASES: &[((u8, i32, u32, u32), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn rounding_sf3() {
+    for &((op, mode, a, b), c) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        let (x, y) = (mk_f32(a), mk_f32(b));
+        let c_ = match op {
+            0 => __addsf3(x, y),
+            1 => __subsf3(x, y),
+            2 => __mulsf3(x, y),
+            _ => __divsf3(x, y),
+        };
+        assert_eq!(((op, mode, a, b), c), ((op, mode, a, b), to_u32(c_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct RoundingTf3 {
+        op: u8,
+        mode: i32,
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+    }
+
+    impl TestCase for RoundingTf3 {
+        fn name() -> &'static str {
+            "rounding_tf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128(rng);
+            let b = if rng.gen() {
+                gen_f128(rng)
+            } else {
+                a ^ (gen_u128(rng) >> rng.gen_range(0, 128))
+            };
+            if f128_is_nan(a) || f128_is_nan(b) {
+                return None;
+            }
+            let mode = *rng.choose(rounds()).unwrap();
+            let op = rng.gen_range(0, 4);
+            let c = match f128_arith(mode, op, a, b) {
+                Some(c) => c,
+                None => return None,
+            };
+
+            Some(
+                RoundingTf3 {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), {c}),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::add::__addtf3;
+use compiler_builtins::float::div::__divtf3;
+use compiler_builtins::float::mul::__multf3;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sub::__subtf3;
+
+static TEST_CASES: &[((u8, i32, u128, u128), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn rounding_tf3() {
+    for &((op, mode, a, b), c) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        let (x, y) = (f128::from_repr(a), f128::from_repr(b));
+        let c_ = match op {
+            0 => __addtf3(x, y),
+            1 => __subtf3(x, y),
+            2 => __multf3(x, y),
+            _ => __divtf3(x, y),
+        };
+        assert_eq!(((op, mode, a, b), c), ((op, mode, a, b), c_.repr()));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct ScaleDf {
+        op: u8,
+        mode: i32,
+        a: u64,  // f64
+        b: u64,  // f64, or the `i32` exponent of `scalbn` and `ldexp`
+        c: u64,  // f64
+        n: i32,
+        flags: i32,
+    }
+
+    impl TestCase for ScaleDf {
+        fn name() -> &'static str {
+            "scale_df"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let op = rng.gen_range(0, 6);
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = gen_f64_exc(rng, true);
+            let b = if op < 2 {
+                let n = if rng.gen_weighted_bool(16) {
+                    *rng.choose(&[i32::min_value(), i32::max_value()]).unwrap()
+                } else {
+                    rng.gen_range(-2102, 2102)
+                };
+                n as u32 as u64
+            } else if rng.gen_weighted_bool(8) {
+                *rng.choose(&[a, a ^ (1 << 63)]).unwrap()
+            } else {
+                gen_f64_exc(rng, true)
+            };
+            let (c, n, flags) = scale_exc(op, mode, a as u128, b as u128, 52, 11);
+
+            Some(
+                ScaleDf {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c as u64,
+                    n: n,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), ({c}, {n}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                n = self.n,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::scale;
+
+static TEST_CASES: &[((u8, i32, u64, u64), (u64, i32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn scale_df() {
+    for &((op, mode, a, b), (c, n, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let x = mk_f64(a);
+        let mut n_ = 0;
+        let c_ = match op {
+            0 => scale::scalbn(x, b as i32),
+            1 => scale::ldexp(x, b as i32),
+            2 => scale::frexp(x, &mut n_),
+            3 => {
+                n_ = scale::ilogb(x);
+                0.0
+            }
+            4 => scale::logb(x),
+            _ => scale::nextafter(x, mk_f64(b)),
+        };
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if mk_f64(c).is_nan() && c_.is_nan() { c } else { to_u64(c_) };
+        assert_eq!(((op, mode, a, b), (c, n, flags)), ((op, mode, a, b), (c_, n_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct ScaleSf {
+        op: u8,
+        mode: i32,
+        a: u32,  // f32
+        b: u32,  // f32, or the `i32` exponent of `scalbn` and `ldexp`
+        c: u32,  // f32
+        n: i32,
+        flags: i32,
+    }
+
+    impl TestCase for ScaleSf {
+        fn name() -> &'static str {
+            "scale_sf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let op = rng.gen_range(0, 6);
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = gen_f32_exc(rng, true);
+            let b = if op < 2 {
+                let n = if rng.gen_weighted_bool(16) {
+                    *rng.choose(&[i32::min_value(), i32::max_value()]).unwrap()
+                } else {
+                    rng.gen_range(-281, 281)
+                };
+                n as u32 as u32
+            } else if rng.gen_weighted_bool(8) {
+                *rng.choose(&[a, a ^ (1 << 31)]).unwrap()
+            } else {
+                gen_f32_exc(rng, true)
+            };
+            let (c, n, flags) = scale_exc(op, mode, a as u128, b as u128, 23, 8);
+
+            Some(
+                ScaleSf {
+                    op: op,
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    c: c as u32,
+                    n: n,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({op}, {mode}, {a}, {b}), ({c}, {n}, {flags})),",
+                op = self.op,
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                n = self.n,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::scale;
+
+static TEST_CASES: &[((u8, i32, u32, u32), (u32, i32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn scale_sf() {
+    for &((op, mode, a, b), (c, n, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let x = mk_f32(a);
+        let mut n_ = 0;
+        let c_ = match op {
+            0 => scale::scalbnf(x, b as i32),
+            1 => scale::ldexpf(x, b as i32),
+            2 => scale::frexpf(x, &mut n_),
+            3 => {
+                n_ = scale::ilogbf(x);
+                0.0
+            }
+            4 => scale::logbf(x),
+            _ => scale::nextafterf(x, mk_f32(b)),
+        };
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let c_ = if mk_f32(c).is_nan() && c_.is_nan() { c } else { to_u32(c_) };
+        assert_eq!(((op, mode, a, b), (c, n, flags)), ((op, mode, a, b), (c_, n_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Sqrt {
+        mode: i32,
+        a: u64,  // f64
+        b: u64,  // f64
+        flags: i32,
+    }
+
+    impl TestCase for Sqrt {
+        fn name() -> &'static str {
+            "sqrt"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = if rng.gen_weighted_bool(8) {
+                // Exact squares of numbers with short significands
+                let x = gen_f64_exc(rng, true) & !((1 << 27) - 1) & !(1 << 63);
+                fma_exc(Round::ToNearest, x as u128, x as u128, 0, 52, 11).0 as u64
+            } else if rng.gen_weighted_bool(4) {
+                gen_f64_exc(rng, true)
+            } else {
+                gen_f64_exc(rng, true) & !(1 << 63)
+            };
+            let (b, flags) = sqrt_exc(mode, a as u128, 52, 11);
+
+            Some(
+                Sqrt {
+                    mode: mode as i32,
+                    a: a,
+                    b: b as u64,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({mode}, {a}), ({b}, {flags})),",
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sqrt;
+
+static TEST_CASES: &[((i32, u64), (u64, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn sqrt() {
+    for &((mode, a), (b, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let b_ = sqrt::sqrt(mk_f64(a));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let b_ = if mk_f64(b).is_nan() && b_.is_nan() { b } else { to_u64(b_) };
+        assert_eq!(((mode, a), (b, flags)), ((mode, a), (b_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Sqrtf {
+        mode: i32,
+        a: u32,  // f32
+        b: u32,  // f32
+        flags: i32,
+    }
+
+    impl TestCase for Sqrtf {
+        fn name() -> &'static str {
+            "sqrtf"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = if rng.gen_weighted_bool(8) {
+                // Exact squares of numbers with short significands
+                let x = gen_f32_exc(rng, true) & !((1 << 12) - 1) & !(1 << 31);
+                fma_exc(Round::ToNearest, x as u128, x as u128, 0, 23, 8).0 as u32
+            } else if rng.gen_weighted_bool(4) {
+                gen_f32_exc(rng, true)
+            } else {
+                gen_f32_exc(rng, true) & !(1 << 31)
+            };
+            let (b, flags) = sqrt_exc(mode, a as u128, 23, 8);
+
+            Some(
+                Sqrtf {
+                    mode: mode as i32,
+                    a: a,
+                    b: b as u32,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({mode}, {a}), ({b}, {flags})),",
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::exception;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sqrt;
+
+static TEST_CASES: &[((i32, u32), (u32, i32))] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn sqrtf() {
+    for &((mode, a), (b, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let b_ = sqrt::sqrtf(mk_f32(a));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let b_ = if mk_f32(b).is_nan() && b_.is_nan() { b } else { to_u32(b_) };
+        assert_eq!(((mode, a), (b, flags)), ((mode, a), (b_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Sqrtf128 {
+        mode: i32,
+        a: u128,  // f128
+        b: u128,  // f128
+        flags: i32,
+    }
+
+    impl TestCase for Sqrtf128 {
+        fn name() -> &'static str {
+            "sqrtf128"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let gen = gen_f128;
+            let mode = *rng.choose(rounds()).unwrap();
+            let a = if rng.gen_weighted_bool(8) {
+                // Exact squares of numbers with short significands
+                let x = gen(rng) & !((1 << 57) - 1) & !F128_SIGN;
+                fma_exc(Round::ToNearest, x, x, 0, 112, 15).0
+            } else if rng.gen_weighted_bool(4) {
+                gen(rng)
+            } else {
+                gen(rng) & !F128_SIGN
+            };
+            let (b, flags) = sqrt_exc(mode, a, 112, 15);
+
+            Some(
+                Sqrtf128 {
+                    mode: mode as i32,
+                    a: a,
+                    b: b,
+                    flags: flags,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({mode}, {a}), ({b}, {flags})),",
+                mode = self.mode,
+                a = self.a,
+                b = self.b,
+                flags = self.flags
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::exception;
+use compiler_builtins::float::round::{self, Round};
+use compiler_builtins::float::sqrt;
+
+static TEST_CASES: &[((i32, u128), (u128, i32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn sqrtf128() {
+    let is_nan = |x: u128| x & !(1 << 127) > 0x7fff << 112;
+    for &((mode, a), (b, flags)) in TEST_CASES {
+        round::set_mode(Round::from_raw(mode).unwrap());
+        exception::clear(exception::ALL);
+        let b_ = sqrt::sqrtf128(f128::from_repr(a));
+        let flags_ = exception::test(exception::ALL);
+        // Only the NaN-ness of a NaN result is checked
+        let b_ = if is_nan(b) && is_nan(b_.repr()) { b } else { b_.repr() };
+        assert_eq!(((mode, a), (b, flags)), ((mode, a), (b_, flags_)));
+    }
+    round::set_mode(Round::ToNearest);
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Subdf3 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: u64,  // f64
+    }
+
+    impl TestCase for Subdf3 {
+        fn name() -> &'static str {
+            "subdf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = gen_f64(rng);
+            let c = a - b;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() || b.is_nan() || c.is_nan() {
+                return None;
+            }
+
+            Some(
+                Subdf3 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c: to_u64(c),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::sub::__subdf3;
+
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn subdf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __subdf3(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), to_u64(c_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Subsf3 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: u32,  // f32
+    }
+
+    impl TestCase for Subsf3 {
+        fn name() -> &'static str {
+            "subsf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = gen_f32(rng);
+            let c = a - b;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() || b.is_nan() || c.is_nan() {
+                return None;
+            }
+
+            Some(
+                Subsf3 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c: to_u32(c),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::sub::__subsf3;
+
+static TEST_CASES: &[((u32, u32), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn subsf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __subsf3(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), to_u32(c_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Subtf3 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: u128,  // f128
+    }
+
+    impl TestCase for Subtf3 {
+        fn name() -> &'static str {
+            "subtf3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128(rng);
+            // Operands of similar magnitude exercise cancellation and rounding more often
+            let b = if rng.gen() {
+                gen_f128(rng)
+            } else {
+                a ^ (gen_u128(rng) >> rng.gen_range(0, 128))
+            };
+            // TODO accept NaNs. We don't do that right now because the NaN produced by the
+            // reference implementation isn't necessarily the one the intrinsic returns
+            if f128_is_nan(a) || f128_is_nan(b) {
+                return None;
+            }
+            let c = match f128_add(Round::ToNearest, a, b ^ F128_SIGN) {
+                Some(c) => c,
+                None => return None,
+            };
+
+            Some(
+                Subtf3 {
+                    a: a,
+                    b: b,
+                    c: c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::sub::__subtf3;
+
+static TEST_CASES: &[((u128, u128), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn subtf3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __subtf3(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_.repr()));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Truncdfhf2 {
+        a: u64,  // f64
+        b: u16,
+    }
+
+    impl TestCase for Truncdfhf2 {
+        fn name() -> &'static str {
+            "truncdfhf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = if rng.gen() {
+                gen_f64(rng)
+            } else {
+                // A value around the range of half precision numbers, where the result is not just
+                // zero or infinity
+                let exponent = rng.gen_range(1023 - 26, 1023 + 17);
+                let mut bits = (rng.gen::<u64>() & 0x800f_ffff_ffff_ffff) | (exponent << 52);
+                if rng.gen_weighted_bool(4) {
+                    // Halfway between two normal half precision numbers
+                    bits = (bits & !((1 << 41) - 1)) | (1 << 41);
+                }
+                unsafe { mem::transmute::<u64, f64>(bits) }
+            };
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() {
+                return None;
+            }
+
+            Some(
+                Truncdfhf2 {
+                    a: to_u64(a),
+                    b: round_f16(a),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::extend::{__extendhfsf2, __extendsfdf2};
+use compiler_builtins::float::trunc::__truncdfhf2;
+
+static TEST_CASES: &[((u64,), u16)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn truncdfhf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __truncdfhf2(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), b_));
+    }
+}
+
+#[test]
+fn truncdfhf2_round_trip() {
+    // Every half precision number survives a round trip through `f64`; NaNs are quieted
+    for h in 0..(1 << 16) {
+        let h = h as u16;
+        let expected = if h & 0x7fff > 0x7c00 { h | 0x0200 } else { h };
+        assert_eq!((h, expected), (h, __truncdfhf2(__extendsfdf2(__extendhfsf2(h)))));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Truncdfsf2 {
+        a: u64,  // f64
+        b: u32,  // f32
+    }
+
+    impl TestCase for Truncdfsf2 {
+        fn name() -> &'static str {
+            "truncdfsf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = if rng.gen() {
+                gen_f64(rng)
+            } else {
+                // A value in the range of `f32` with random bits below its precision, to
+                // exercise the rounding
+                let bits = to_u64(f64(gen_f32(rng))) | (rng.gen::<u64>() & ((1 << 29) - 1));
+                unsafe { mem::transmute::<u64, f64>(bits) }
+            };
+            let b = a as f32;
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() {
+                return None;
+            }
+
+            Some(
+                Truncdfsf2 {
+                    a: to_u64(a),
+                    b: to_u32(b),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::trunc::__truncdfsf2;
+
+static TEST_CASES: &[((u64,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn truncdfsf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __truncdfsf2(mk_f64(a));
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Truncsfhf2 {
+        a: u32,  // f32
+        b: u16,
+    }
+
+    impl TestCase for Truncsfhf2 {
+        fn name() -> &'static str {
+            "truncsfhf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = if rng.gen() {
+                gen_f32(rng)
+            } else {
+                // A value around the range of half precision numbers, where the result is not just
+                // zero or infinity
+                let exponent = rng.gen_range(127 - 26, 127 + 17);
+                let mut bits = (rng.gen::<u32>() & 0x807f_ffff) | (exponent << 23);
+                if rng.gen_weighted_bool(4) {
+                    // Halfway between two normal half precision numbers
+                    bits = (bits & !((1 << 12) - 1)) | (1 << 12);
+                }
+                unsafe { mem::transmute::<u32, f32>(bits) }
+            };
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if a.is_nan() {
+                return None;
+            }
+
+            Some(
+                Truncsfhf2 {
+                    a: to_u32(a),
+                    b: round_f16(f64(a)),
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::extend::__extendhfsf2;
+use compiler_builtins::float::trunc::__truncsfhf2;
+
+static TEST_CASES: &[((u32,), u16)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn truncsfhf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __truncsfhf2(mk_f32(a));
+        assert_eq!(((a,), b), ((a,), b_));
+    }
+}
+
+#[test]
+fn truncsfhf2_round_trip() {
+    // Every half precision number survives a round trip through `f32`; NaNs are quieted
+    for h in 0..(1 << 16) {
+        let h = h as u16;
+        let expected = if h & 0x7fff > 0x7c00 { h | 0x0200 } else { h };
+        assert_eq!((h, expected), (h, __truncsfhf2(__extendhfsf2(h))));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Trunctfdf2 {
+        a: u128,  // f128
+        b: u64,  // f64
+    }
+
+    impl TestCase for Trunctfdf2 {
+        fn name() -> &'static str {
+            "trunctfdf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mut a = gen_f128_exponent(rng, -1077, 1026);
+            if rng.gen() {
+                // Exactly halfway between two normal numbers
+                a = (a & !((1 << 60) - 1)) | (1 << 59);
+            }
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if f128_is_nan(a) {
+                return None;
+            }
+
+            Some(
+                Trunctfdf2 {
+                    a,
+                    b: f128_narrow(a, 52, 11) as u64,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::trunc::__trunctfdf2;
+
+static TEST_CASES: &[((u128,), u64)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn trunctfdf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __trunctfdf2(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), to_u64(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Trunctfsf2 {
+        a: u128,  // f128
+        b: u32,  // f32
+    }
+
+    impl TestCase for Trunctfsf2 {
+        fn name() -> &'static str {
+            "trunctfsf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let mut a = gen_f128_exponent(rng, -152, 130);
+            if rng.gen() {
+                // Exactly halfway between two normal numbers
+                a = (a & !((1 << 89) - 1)) | (1 << 88);
+            }
+            // TODO accept NaNs. We don't do that right now because we can't check
+            // for NaN-ness on the thumb targets (due to missing intrinsics)
+            if f128_is_nan(a) {
+                return None;
+            }
+
+            Some(
+                Trunctfsf2 {
+                    a,
+                    b: f128_narrow(a, 23, 8) as u32,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(buffer, "(({a},), {b}),", a = self.a, b = self.b).unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::trunc::__trunctfsf2;
+
+static TEST_CASES: &[((u128,), u32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn trunctfsf2() {
+    for &((a,), b) in TEST_CASES {
+        let b_ = __trunctfsf2(f128::from_repr(a));
+        assert_eq!(((a,), b), ((a,), to_u32(b_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Udivdi3 {
+        a: u64,
+        b: u64,
+        c: u64,
+    }
+
+    impl TestCase for Udivdi3 {
+        fn name() -> &'static str {
+            "udivdi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            let b = gen_u64(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+
+            Some(Udivdi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__udivdi3;
+
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn udivdi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __udivdi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Udivmoddi4 {
+        a: u64,
+        b: u64,
+        c: u64,
+        rem: u64,
+    }
+
+    impl TestCase for Udivmoddi4 {
+        fn name() -> &'static str {
+            "udivmoddi4"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            let b = gen_u64(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+            let rem = a % b;
+
+            Some(Udivmoddi4 { a, b, c, rem })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {rem})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                rem = self.rem
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__udivmoddi4;
+
+static TEST_CASES: &[((u64, u64), (u64, u64))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn udivmoddi4() {
+    for &((a, b), (c, rem)) in TEST_CASES {
+        let mut rem_ = 0;
+        let c_ = __udivmoddi4(a, b, Some(&mut rem_));
+        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Udivmodsi4 {
+        a: u32,
+        b: u32,
+        c: u32,
+        rem: u32,
+    }
+
+    impl TestCase for Udivmodsi4 {
+        fn name() -> &'static str {
+            "udivmodsi4"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u32(rng);
+            let b = gen_u32(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+            let rem = a % b;
+
+            Some(Udivmodsi4 { a, b, c, rem })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {rem})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                rem = self.rem
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__udivmodsi4;
+
+static TEST_CASES: &[((u32, u32), (u32, u32))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn udivmodsi4() {
+    for &((a, b), (c, rem)) in TEST_CASES {
+        let mut rem_ = 0;
+        let c_ = __udivmodsi4(a, b, Some(&mut rem_));
+        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Udivmodti4 {
+        a: u128,
+        b: u128,
+        c: u128,
+        rem: u128,
+    }
+
+    impl TestCase for Udivmodti4 {
+        fn name() -> &'static str {
+            "udivmodti4"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            let b = gen_u128(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+            let rem = a % b;
+
+            Some(Udivmodti4 { a, b, c, rem })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), ({c}, {rem})),",
+                a = self.a,
+                b = self.b,
+                c = self.c,
+                rem = self.rem
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__udivmodti4;
+
+static TEST_CASES: &[((u128, u128), (u128, u128))] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn udivmodti4() {
+    for &((a, b), (c, rem)) in TEST_CASES {
+        let mut rem_ = 0;
+        let c_ = __udivmodti4(a, b, Some(&mut rem_));
+        assert_eq!(((a, b), (c, rem)), ((a, b), (c_, rem_)));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Udivsi3 {
+        a: u32,
+        b: u32,
+        c: u32,
+    }
+
+    impl TestCase for Udivsi3 {
+        fn name() -> &'static str {
+            "udivsi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u32(rng);
+            let b = gen_u32(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+
+            Some(Udivsi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__udivsi3;
+
+static TEST_CASES: &[((u32, u32), u32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn udivsi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __udivsi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Udivti3 {
+        a: u128,
+        b: u128,
+        c: u128,
+    }
+
+    impl TestCase for Udivti3 {
+        fn name() -> &'static str {
+            "udivti3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            let b = gen_u128(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a / b;
+
+            Some(Udivti3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__udivti3;
+
+static TEST_CASES: &[((u128, u128), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn udivti3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __udivti3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Umoddi3 {
+        a: u64,
+        b: u64,
+        c: u64,
+    }
+
+    impl TestCase for Umoddi3 {
+        fn name() -> &'static str {
+            "umoddi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u64(rng);
+            let b = gen_u64(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a % b;
+
+            Some(Umoddi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__umoddi3;
+
+static TEST_CASES: &[((u64, u64), u64)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn umoddi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __umoddi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Umodsi3 {
+        a: u32,
+        b: u32,
+        c: u32,
+    }
+
+    impl TestCase for Umodsi3 {
+        fn name() -> &'static str {
+            "umodsi3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u32(rng);
+            let b = gen_u32(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a % b;
+
+            Some(Umodsi3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__umodsi3;
+
+static TEST_CASES: &[((u32, u32), u32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn umodsi3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __umodsi3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Umodti3 {
+        a: u128,
+        b: u128,
+        c: u128,
+    }
+
+    impl TestCase for Umodti3 {
+        fn name() -> &'static str {
+            "umodti3"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_u128(rng);
+            let b = gen_u128(rng);
+            if b == 0 {
+                return None;
+            }
+            let c = a % b;
+
+            Some(Umodti3 { a, b, c })
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::int::udiv::__umodti3;
+
+static TEST_CASES: &[((u128, u128), u128)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn umodti3() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __umodti3(a, b);
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Unorddf2 {
+        a: u64,  // f64
+        b: u64,  // f64
+        c: i32,
+    }
+
+    impl TestCase for Unorddf2 {
+        fn name() -> &'static str {
+            "unorddf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f64(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f64(rng)
+            };
+            let c = (a.is_nan() || b.is_nan()) as i32;
+
+            Some(
+                Unorddf2 {
+                    a: to_u64(a),
+                    b: to_u64(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__unorddf2;
+
+static TEST_CASES: &[((u64, u64), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn unorddf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __unorddf2(mk_f64(a), mk_f64(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Unordsf2 {
+        a: u32,  // f32
+        b: u32,  // f32
+        c: i32,
+    }
+
+    impl TestCase for Unordsf2 {
+        fn name() -> &'static str {
+            "unordsf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f32(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f32(rng)
+            };
+            let c = (a.is_nan() || b.is_nan()) as i32;
+
+            Some(
+                Unordsf2 {
+                    a: to_u32(a),
+                    b: to_u32(b),
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            r#"
+use compiler_builtins::float::cmp::__unordsf2;
+
+static TEST_CASES: &[((u32, u32), i32)] = &[
+"#
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn unordsf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __unordsf2(mk_f32(a), mk_f32(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    #[derive(Eq, Hash, PartialEq)]
+    pub struct Unordtf2 {
+        a: u128,  // f128
+        b: u128,  // f128
+        c: i32,
+    }
+
+    impl TestCase for Unordtf2 {
+        fn name() -> &'static str {
+            "unordtf2"
+        }
+
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized,
+        {
+            let a = gen_f128_cmp(rng);
+            let b = if rng.gen_weighted_bool(10) {
+                a
+            } else {
+                gen_f128_cmp(rng)
+            };
+            let c = (f128_is_nan(a) || f128_is_nan(b)) as i32;
+
+            Some(
+                Unordtf2 {
+                    a,
+                    b,
+                    c,
+                },
+            )
+        }
+
+        fn to_string(&self, buffer: &mut String) {
+            writeln!(
+                buffer,
+                "(({a}, {b}), {c}),",
+                a = self.a,
+                b = self.b,
+                c = self.c
+            )
+                    .unwrap();
+        }
+
+        fn prologue() -> &'static str {
+            "
+use compiler_builtins::float::{f128, Float};
+use compiler_builtins::float::cmp::__unordtf2;
+
+static TEST_CASES: &[((u128, u128), i32)] = &[
+"
+        }
+
+        fn epilogue() -> &'static str {
+            "
+];
+
+#[test]
+fn unordtf2() {
+    for &((a, b), c) in TEST_CASES {
+        let c_ = __unordtf2(f128::from_repr(a), f128::from_repr(b));
+        assert_eq!(((a, b), c), ((a, b), c_));
+    }
+}
+"
+        }
+    }
+
+    trait TestCase {
+        /// Name of the intrinsic to test
+        fn name() -> &'static str;
+        /// Generates a valid test case
+        fn generate<R>(rng: &mut R) -> Option<Self>
+        where
+            R: Rng,
+            Self: Sized;
+        /// Stringifies a test case
+        fn to_string(&self, buffer: &mut String);
+        /// Prologue of the test file
+        fn prologue() -> &'static str;
+        /// Epilogue of the test file
+        fn epilogue() -> &'static str;
+        /// Number of test cases to generate
+        fn ntests() -> usize {
+            NTESTS
+        }
+    }
+
+    const PROLOGUE: &'static str = r#"
+extern crate compiler_builtins;
+
+// test runner
+#[cfg(all(target_arch = "arm",
+          not(any(target_env = "gnu", target_env = "musl")),
+          target_os = "linux",
+          test))]
+extern crate utest_cortex_m_qemu;
+
+// overrides `panic!`
+#[cfg(all(target_arch = "arm",
+          not(any(target_env = "gnu", target_env = "musl")),
+          target_os = "linux",
+          test))]
+#[macro_use]
+extern crate utest_macros;
+
+#[cfg(all(target_arch = "arm",
+          not(any(target_env = "gnu", target_env = "musl")),
+          target_os = "linux",
+          test))]
+macro_rules! panic {
+    ($($tt:tt)*) => {
+        upanic!($($tt)*);
+    };
+}
+
+// helpers, which not every test uses
+#[allow(unused_imports)]
+#[cfg(all(target_arch = "arm",
+          not(any(target_env = "gnu", target_env = "musl")),
+          target_os = "linux",
+          test))]
+use core::mem;
+#[allow(unused_imports)]
+#[cfg(not(all(target_arch = "arm",
+              not(any(target_env = "gnu", target_env = "musl")),
+              target_os = "linux",
+              test)))]
+use std::mem;
+
+#[allow(dead_code)]
+fn mk_f32(x: u32) -> f32 {
+    unsafe { mem::transmute(x) }
+}
+
+#[allow(dead_code)]
+fn mk_f64(x: u64) -> f64 {
+    unsafe { mem::transmute(x) }
+}
+
+#[allow(dead_code)]
+fn to_u32(x: f32) -> u32 {
+    unsafe { mem::transmute(x) }
+}
+
+#[allow(dead_code)]
+fn to_u64(x: f64) -> u64 {
+    unsafe { mem::transmute(x) }
+}
+
+/// The error of `c` in ulps of the exact result, which is `t` plus `rest` ulps of `t`
+#[allow(dead_code)]
+fn ulp_error(c: f64, t: f64, rest: f64) -> f64 {
+    let exponent = to_u64(t) >> 52 & 0x7ff;
+    let exponent = if exponent == 0 { 1 } else { exponent };
+    let ulp = if exponent > 52 {
+        mk_f64((exponent - 52) << 52)
+    } else {
+        mk_f64(1 << (exponent - 1))
+    };
+    // Infinity is one ulp past the largest finite number
+    let max = mk_f64(0x7fef_ffff_ffff_ffff);
+    let (c, past) = if c == mk_f64(0x7ff0_0000_0000_0000) {
+        (max, 1.0)
+    } else if c == -mk_f64(0x7ff0_0000_0000_0000) {
+        (-max, -1.0)
+    } else {
+        (c, 0.0)
+    };
+    (c - t) / ulp + past - rest
+}
+
+/// The error of `c` in ulps of the exact result, which is `t` plus `rest` ulps of `t`
+#[allow(dead_code)]
+fn ulp_errorf(c: f32, t: f32, rest: f64) -> f64 {
+    let exponent = to_u32(t) >> 23 & 0xff;
+    let exponent = if exponent == 0 { 1 } else { exponent };
+    let ulp = mk_f64(((exponent + 1023 - 127 - 23) as u64) << 52);
+    // Infinity is one ulp past the largest finite number
+    let max = mk_f32(0x7f7f_ffff) as f64;
+    let (c, past) = if c == mk_f32(0x7f80_0000) {
+        (max, 1.0)
+    } else if c == -mk_f32(0x7f80_0000) {
+        (-max, -1.0)
+    } else {
+        (c as f64, 0.0)
+    };
+    (c - t as f64) / ulp + past - rest
+}
+"#;
+
+    macro_rules! gen_int {
+        ($name:ident, $ity:ident, $hty:ident) => {
+            fn $name<R>(rng: &mut R) -> $ity
+                where
+                R: Rng,
+            {
+                let mut mk = || if rng.gen_weighted_bool(10) {
+                    *rng.choose(&[::std::$hty::MAX, 0, ::std::$hty::MIN]).unwrap()
+                } else {
+                    rng.gen::<$hty>()
+                };
+                unsafe { mem::transmute([mk(), mk()]) }
+            }
+
+        }
+    }
+
+    gen_int!(gen_i32, i32, i16);
+    gen_int!(gen_i64, i64, i32);
+    gen_int!(gen_i128, i128, i64);
+
+    macro_rules! gen_float {
+        ($name:ident,
+         $fty:ident,
+         $uty:ident,
+         $bits:expr,
+         $significand_bits:expr) => {
+            pub fn $name<R>(rng: &mut R) -> $fty
+            where
+                R: Rng,
+            {
+                const BITS: u8 = $bits;
+                const SIGNIFICAND_BITS: u8 = $significand_bits;
+
+                const SIGNIFICAND_MASK: $uty = (1 << SIGNIFICAND_BITS) - 1;
+                const SIGN_MASK: $uty = (1 << (BITS - 1));
+                const EXPONENT_MASK: $uty = !(SIGN_MASK | SIGNIFICAND_MASK);
+
+                fn mk_f32(sign: bool, exponent: $uty, significand: $uty) -> $fty {
+                    unsafe {
+                        mem::transmute(((sign as $uty) << (BITS - 1)) |
+                                       ((exponent & EXPONENT_MASK) <<
+                                        SIGNIFICAND_BITS) |
+                                       (significand & SIGNIFICAND_MASK))
+                    }
+                }
+
+                if rng.gen_weighted_bool(10) {
+                    // Special values
+                    *rng.choose(&[-0.0,
+                                  0.0,
+                                  ::std::$fty::NAN,
+                                  ::std::$fty::INFINITY,
+                                  -::std::$fty::INFINITY])
+                        .unwrap()
+                } else if rng.gen_weighted_bool(10) {
+                    // NaN patterns
+                    mk_f32(rng.gen(), rng.gen(), 0)
+                } else if rng.gen() {
+                    // Denormalized
+                    mk_f32(rng.gen(), 0, rng.gen())
+                } else {
+                    // Random anything
+                    mk_f32(rng.gen(), rng.gen(), rng.gen())
+                }
+            }
+        }
+    }
+
+    gen_float!(gen_f32, f32, u32, 32, 23);
+    gen_float!(gen_f64, f64, u64, 64, 52);
+
+    macro_rules! gen_float_exc {
+        ($name:ident, $gen:ident, $uty:ident, $exponent_mask:expr) => {
+            /// Returns the representation of a random number for the tests of exceptions: mostly
+            /// one that `gen_f32`/`gen_f64` returns, but also signaling NaNs, which they never
+            /// return, and with `arbitrary` arbitrary bit patterns, which cover the whole
+            /// exponent range
+            fn $name<R>(rng: &mut R, arbitrary: bool) -> $uty
+            where
+                R: Rng,
+            {
+                const EXPONENT_MASK: $uty = $exponent_mask;
+                const QUIET_BIT: $uty = EXPONENT_MASK >> 1 & !EXPONENT_MASK;
+
+                if rng.gen_weighted_bool(16) {
+                    let sign = rng.gen::<$uty>() & !(!0 >> 1);
+                    sign | EXPONENT_MASK | rng.gen_range(1, QUIET_BIT)
+                } else if arbitrary && rng.gen() {
+                    rng.gen()
+                } else {
+                    unsafe { mem::transmute($gen(rng)) }
+                }
+            }
+        }
+    }
+
+    gen_float_exc!(gen_f32_exc, gen_f32, u32, 0x7f80_0000);
+    gen_float_exc!(gen_f64_exc, gen_f64, u64, 0x7ff0_0000_0000_0000);
+
+    /// Like `gen_f32` but a quarter of the time returns an infinity or NaN, which is where the
+    /// complex arithmetic of C11 Annex G differs from the textbook formulas
+    fn gen_complex_f32<R>(rng: &mut R) -> f32
+    where
+        R: Rng,
+    {
+        if rng.gen_weighted_bool(4) {
+            *rng.choose(&[::std::f32::NAN, ::std::f32::INFINITY, -::std::f32::INFINITY])
+                .unwrap()
+        } else {
+            gen_f32(rng)
+        }
+    }
+
+    /// Like `gen_complex_f32` but for `f64`
+    fn gen_complex_f64<R>(rng: &mut R) -> f64
+    where
+        R: Rng,
+    {
+        if rng.gen_weighted_bool(4) {
+            *rng.choose(&[::std::f64::NAN, ::std::f64::INFINITY, -::std::f64::INFINITY])
+                .unwrap()
+        } else {
+            gen_f64(rng)
+        }
+    }
+
+    /// What the reference complex arithmetic needs from `f32` and `f64`
+    trait ComplexPart
+        : Copy
+        + PartialOrd
+        + Neg<Output = Self>
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + Div<Output = Self> {
+        fn zero() -> Self;
+        fn one() -> Self;
+        fn infinity() -> Self;
+        fn is_nan(self) -> bool;
+        fn is_infinite(self) -> bool;
+        fn is_finite(self) -> bool;
+        fn abs(self) -> Self;
+        fn max(self, other: Self) -> Self;
+        fn copysign(self, sign: Self) -> Self;
+        fn logb(self) -> Self;
+        fn scalbn(self, n: i32) -> Self;
+        fn to_i32(self) -> i32;
+    }
+
+    macro_rules! complex_part {
+        ($fty:ident, $uty:ident, $significand_bits:expr, $exponent_bits:expr) => {
+            impl ComplexPart for $fty {
+                fn zero() -> Self {
+                    0.0
+                }
+                fn one() -> Self {
+                    1.0
+                }
+                fn infinity() -> Self {
+                    ::std::$fty::INFINITY
+                }
+                fn is_nan(self) -> bool {
+                    self.is_nan()
+                }
+                fn is_infinite(self) -> bool {
+                    self.is_infinite()
+                }
+                fn is_finite(self) -> bool {
+                    self.is_finite()
+                }
+                fn abs(self) -> Self {
+                    self.abs()
+                }
+                fn max(self, other: Self) -> Self {
+                    self.max(other)
+                }
+                fn copysign(self, sign: Self) -> Self {
+                    if self.is_sign_negative() == sign.is_sign_negative() {
+                        self
+                    } else {
+                        -self
+                    }
+                }
+                fn logb(self) -> Self {
+                    if self == 0.0 {
+                        -::std::$fty::INFINITY
+                    } else if !self.is_finite() {
+                        self.abs()
+                    } else {
+                        let (_, m, e) = self.parts();
+                        (127 - m.leading_zeros() as i32 + e) as $fty
+                    }
+                }
+                fn scalbn(self, n: i32) -> Self {
+                    if self == 0.0 || !self.is_finite() {
+                        return self;
+                    }
+                    let (sign, m, e) = self.parts();
+                    let rep =
+                        round_binary(sign, 0, m, false, e + n, $significand_bits, $exponent_bits);
+                    unsafe { mem::transmute(rep as $uty) }
+                }
+                fn to_i32(self) -> i32 {
+                    self as i32
+                }
+            }
+
+            impl Parts for $fty {
+                fn parts(self) -> (bool, u128, i32) {
+                    let rep: $uty = unsafe { mem::transmute(self) };
+                    let bias = (1 << ($exponent_bits - 1)) - 1;
+                    let exponent = (rep >> $significand_bits) as i32 & ((1 << $exponent_bits) - 1);
+                    let mut significand = (rep & ((1 << $significand_bits) - 1)) as u128;
+                    if exponent != 0 {
+                        significand |= 1 << $significand_bits;
+                    }
+                    (
+                        self.is_sign_negative(),
+                        significand,
+                        cmp::max(exponent, 1) - bias - $significand_bits,
+                    )
+                }
+            }
+        }
+    }
+
+    /// Splits a finite float into its sign, a significand `m` and an exponent `e` such that
+    /// `|x| = m * 2^e`
+    trait Parts {
+        fn parts(self) -> (bool, u128, i32);
+    }
+
+    complex_part!(f32, u32, 23, 8);
+    complex_part!(f64, u64, 52, 11);
+
+    /// Reference `__mulsc3` and `__muldc3`, a transcription of compiler-rt's
+    fn mulc3<F: ComplexPart>(a: F, b: F, c: F, d: F) -> (F, F) {
+        let (mut a, mut b, mut c, mut d) = (a, b, c, d);
+        let ac = a * c;
+        let bd = b * d;
+        let ad = a * d;
+        let bc = b * c;
+        let mut real = ac - bd;
+        let mut imag = ad + bc;
+        let one_if_inf = |x: F| (if x.is_infinite() { F::one() } else { F::zero() }).copysign(x);
+        let zero_if_nan = |x: F| if x.is_nan() { F::zero().copysign(x) } else { x };
+        if real.is_nan() && imag.is_nan() {
+            let mut recalc = false;
+            if a.is_infinite() || b.is_infinite() {
+                a = one_if_inf(a);
+                b = one_if_inf(b);
+                c = zero_if_nan(c);
+                d = zero_if_nan(d);
+                recalc = true;
+            }
+            if c.is_infinite() || d.is_infinite() {
+                c = one_if_inf(c);
+                d = one_if_inf(d);
+                a = zero_if_nan(a);
+                b = zero_if_nan(b);
+                recalc = true;
+            }
+            if !recalc &&
+                (ac.is_infinite() || bd.is_infinite() || ad.is_infinite() || bc.is_infinite())
+            {
+                a = zero_if_nan(a);
+                b = zero_if_nan(b);
+                c = zero_if_nan(c);
+                d = zero_if_nan(d);
+                recalc = true;
+            }
+            if recalc {
+                real = F::infinity() * (a * c - b * d);
+                imag = F::infinity() * (a * d + b * c);
+            }
+        }
+        (real, imag)
+    }
+
+    /// Reference `__divsc3` and `__divdc3`, a transcription of compiler-rt's
+    fn divc3<F: ComplexPart>(a: F, b: F, c: F, d: F) -> (F, F) {
+        let (mut a, mut b, mut c, mut d) = (a, b, c, d);
+        let one_if_inf = |x: F| (if x.is_infinite() { F::one() } else { F::zero() }).copysign(x);
+        let logbw = c.abs().max(d.abs()).logb();
+        let mut ilogbw = 0;
+        if logbw.is_finite() {
+            ilogbw = logbw.to_i32();
+            c = c.scalbn(-ilogbw);
+            d = d.scalbn(-ilogbw);
+        }
+        let denom = c * c + d * d;
+        let mut real = ((a * c + b * d) / denom).scalbn(-ilogbw);
+        let mut imag = ((b * c - a * d) / denom).scalbn(-ilogbw);
+        if real.is_nan() && imag.is_nan() {
+            if denom == F::zero() && (!a.is_nan() || !b.is_nan()) {
+                real = F::infinity().copysign(c) * a;
+                imag = F::infinity().copysign(c) * b;
+            } else if (a.is_infinite() || b.is_infinite()) && c.is_finite() && d.is_finite() {
+                a = one_if_inf(a);
+                b = one_if_inf(b);
+                real = F::infinity() * (a * c + b * d);
+                imag = F::infinity() * (b * c - a * d);
+            } else if logbw.is_infinite() && logbw > F::zero() && a.is_finite() && b.is_finite() {
+                c = one_if_inf(c);
+                d = one_if_inf(d);
+                real = F::zero() * (a * c + b * d);
+                imag = F::zero() * (b * c - a * d);
+            }
+        }
+        (real, imag)
+    }
+
+    const F128_SIGN: u128 = 1 << 127;
+    const F128_INF: u128 = 0x7fff << 112;
+
+    /// Like `gen_f64` but returns the representation of a quadruple precision number
+    fn gen_f128<R>(rng: &mut R) -> u128
+    where
+        R: Rng,
+    {
+        fn mk_f128(sign: bool, exponent: u128, significand: u128) -> u128 {
+            ((sign as u128) << 127) | ((exponent & 0x7fff) << 112) |
+                (significand & ((1 << 112) - 1))
+        }
+
+        if rng.gen_weighted_bool(10) {
+            // Special values
+            *rng.choose(&[F128_SIGN, 0, F128_INF | (1 << 111), F128_INF, F128_SIGN | F128_INF])
+                .unwrap()
+        } else if rng.gen_weighted_bool(10) {
+            // NaN patterns
+            mk_f128(rng.gen(), rng.gen::<u16>() as u128, 0)
+        } else if rng.gen() {
+            // Denormalized
+            mk_f128(rng.gen(), 0, gen_u128(rng))
+        } else {
+            // Random anything
+            mk_f128(rng.gen(), rng.gen::<u16>() as u128, gen_u128(rng))
+        }
+    }
+
+    /// Like `gen_f128` but half of the time returns one of the values comparisons have to treat
+    /// specially: zeros, infinities, NaNs (quiet and signaling) and the extremes of each range
+    fn gen_f128_cmp<R>(rng: &mut R) -> u128
+    where
+        R: Rng,
+    {
+        if rng.gen() {
+            let special = *rng.choose(
+                &[
+                    0,
+                    1,
+                    (1 << 112) - 1,
+                    1 << 112,
+                    0x3fff << 112,
+                    F128_INF - 1,
+                    F128_INF,
+                    F128_INF | 1,
+                    F128_INF | (1 << 111),
+                ],
+            )
+                .unwrap();
+            special | if rng.gen() { F128_SIGN } else { 0 }
+        } else {
+            gen_f128(rng)
+        }
+    }
+
+    /// Like `gen_f128` but mostly returns numbers with an unbiased exponent in `[min, max)`, e.g.
+    /// the range of a narrower type
+    fn gen_f128_exponent<R>(rng: &mut R, min: i32, max: i32) -> u128
+    where
+        R: Rng,
+    {
+        if rng.gen_weighted_bool(10) {
+            gen_f128(rng)
+        } else {
+            let exponent = (rng.gen_range(min, max) + 16383) as u128;
+            ((rng.gen::<bool>() as u128) << 127) | (exponent << 112) |
+                (gen_u128(rng) & ((1 << 112) - 1))
+        }
+    }
+
+    pub fn gen_u128<R>(rng: &mut R) -> u128
+    where
+        R: Rng,
+    {
+        gen_i128(rng) as u128
+    }
+
+    pub fn gen_u32<R>(rng: &mut R) -> u32
+    where
+        R: Rng,
+    {
+        gen_i32(rng) as u32
+    }
+
+    fn gen_u64<R>(rng: &mut R) -> u64
+    where
+        R: Rng,
+    {
+        gen_i64(rng) as u64
+    }
+
+    pub fn to_u32(x: f32) -> u32 {
+        unsafe { mem::transmute(x) }
+    }
+
+    pub fn to_u64(x: f64) -> u64 {
+        unsafe { mem::transmute(x) }
+    }
+
+    fn mk_f32(x: u32) -> f32 {
+        unsafe { mem::transmute(x) }
+    }
+
+    fn mk_f64(x: u64) -> f64 {
+        unsafe { mem::transmute(x) }
+    }
+
+    // Expected result of `__le*f2` and its aliases: unordered operands compare as "greater"
+    fn cmp_le_abi<F: PartialOrd>(a: F, b: F) -> i32 {
+        match a.partial_cmp(&b) {
+            Some(Ordering::Less) => -1,
+            Some(Ordering::Equal) => 0,
+            Some(Ordering::Greater) | None => 1,
+        }
+    }
+
+    // Expected result of `__ge*f2` and its aliases: unordered operands compare as "less"
+    fn cmp_ge_abi<F: PartialOrd>(a: F, b: F) -> i32 {
+        match a.partial_cmp(&b) {
+            Some(Ordering::Less) | None => -1,
+            Some(Ordering::Equal) => 0,
+            Some(Ordering::Greater) => 1,
+        }
+    }
+
+    /// A quadruple precision number, ordered like the primitive floats
+    #[derive(Clone, Copy)]
+    struct F128(u128);
+
+    impl PartialEq for F128 {
+        fn eq(&self, other: &F128) -> bool {
+            self.partial_cmp(other) == Some(Ordering::Equal)
+        }
+    }
+
+    impl PartialOrd for F128 {
+        fn partial_cmp(&self, other: &F128) -> Option<Ordering> {
+            if f128_is_nan(self.0) || f128_is_nan(other.0) {
+                return None;
+            }
+
+            // Sign-magnitude to two's complement, which also makes both zeros equal
+            let key = |x: u128| if x & F128_SIGN == 0 {
+                x as i128
+            } else {
+                -((x & !F128_SIGN) as i128)
+            };
+            key(self.0).partial_cmp(&key(other.0))
+        }
+    }
+
+    /// Returns the value of the half precision number with representation `h`, which must not be
+    /// NaN
+    fn f16_value(h: u16) -> f64 {
+        let sign = if h & 0x8000 == 0 { 1.0 } else { -1.0 };
+        let exponent = ((h >> 10) & 0x1f) as i32;
+        let significand = (h & 0x3ff) as f64;
+        sign *
+            match exponent {
+                0 => significand * 2f64.powi(-24),
+                0x1f => ::std::f64::INFINITY,
+                _ => (significand + 1024.0) * 2f64.powi(exponent - 25),
+            }
+    }
+
+    /// Returns the representation of the half precision number `h` converted to `f32`
+    fn extend_f16(h: u16) -> u32 {
+        if h & 0x7fff > 0x7c00 {
+            // NaN: quieted, with the rest of the payload left-aligned
+            ((h as u32 & 0x8000) << 16) | 0x7fc0_0000 | ((h as u32 & 0x1ff) << 13)
+        } else {
+            to_u32(f16_value(h) as f32)
+        }
+    }
+
+    /// Returns the representation of `x` rounded to the nearest half precision number (ties to
+    /// even). `x` must not be NaN
+    fn round_f16(x: f64) -> u16 {
+        let sign = if x.is_sign_negative() { 0x8000 } else { 0 };
+        let x = x.abs();
+
+        // Infinity stands in for 2^16, which is where the next half precision number after the
+        // largest finite one would be if the exponent range were unbounded
+        let value = |h: u16| if h == 0x7c00 { 65536.0 } else { f16_value(h) };
+        if x >= 65536.0 {
+            return sign | 0x7c00;
+        }
+
+        // Find the neighbors `value(lo) <= x < value(hi)`
+        let (mut lo, mut hi) = (0, 0x7c00);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if value(mid) <= x {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let (below, above) = (x - value(lo), value(hi) - x);
+        sign |
+            if below < above || (below == above && lo & 1 == 0) {
+                lo
+            } else {
+                hi
+            }
+    }
+
+    fn f128_is_nan(x: u128) -> bool {
+        x & !F128_SIGN > F128_INF
+    }
+
+    /// Splits the finite quadruple precision number `x` into its sign, a significand `m` and an
+    /// exponent `e` such that `|x| = m * 2^e`
+    fn f128_parts(x: u128) -> (bool, u128, i32) {
+        let sign = x & F128_SIGN != 0;
+        let exponent = ((x >> 112) & 0x7fff) as i32;
+        let significand = x & ((1 << 112) - 1);
+        if exponent == 0 {
+            (sign, significand, -16494)
+        } else {
+            (sign, significand | (1 << 112), exponent - 16495)
+        }
+    }
+
+    /// The rounding directions of `float::round::Round`, with the same discriminants
+    #[derive(Clone, Copy, PartialEq)]
+    enum Round {
+        ToNearest = 0,
+        Downward = 1,
+        Upward = 2,
+        TowardZero = 3,
+    }
+
+    // The exceptions of `float::exception`
+    const FE_INVALID: i32 = 0x1;
+    const FE_DIVBYZERO: i32 = 0x2;
+    const FE_OVERFLOW: i32 = 0x4;
+    const FE_UNDERFLOW: i32 = 0x8;
+    const FE_INEXACT: i32 = 0x10;
+
+    /// Returns whether `__powisf2` and `__powidf2` multiply and divide with the soft float
+    /// intrinsics, and so raise exceptions, rather than with the FPU (see `float::pow`)
+    fn soft_powi() -> bool {
+        let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+        let features = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
+        env::var_os("CARGO_FEATURE_ROUNDING_MODES").is_some() ||
+            (arch == "arm" && !features.split(',').any(|f| f == "vfp2"))
+    }
+
+    /// Returns the rounding directions the tests exercise. Without the `rounding-modes` feature
+    /// the soft float routines always round to nearest
+    fn rounds() -> &'static [Round] {
+        if env::var_os("CARGO_FEATURE_ROUNDING_MODES").is_some() {
+            &[Round::ToNearest, Round::Downward, Round::Upward, Round::TowardZero]
+        } else {
+            &[Round::ToNearest]
+        }
+    }
+
+    /// Returns the representation of `(m + t) * 2^e` rounded to the nearest number (ties to even)
+    /// of the binary interchange format with the given field widths, where `m = hi * 2^128 + lo`
+    /// and `t` is some number in `(0, 1)` if `sticky` is set and `0` otherwise
+    fn round_binary(
+        sign: bool,
+        hi: u128,
+        lo: u128,
+        sticky: bool,
+        e: i32,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> u128 {
+        round_binary_with(
+            Round::ToNearest,
+            sign,
+            hi,
+            lo,
+            sticky,
+            e,
+            significand_bits,
+            exponent_bits,
+        )
+    }
+
+    /// Like `round_binary` but rounds in the direction `mode`
+    fn round_binary_with(
+        mode: Round,
+        sign: bool,
+        hi: u128,
+        lo: u128,
+        sticky: bool,
+        e: i32,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> u128 {
+        round_binary_exc(mode, sign, hi, lo, sticky, e, significand_bits, exponent_bits).0
+    }
+
+    /// Like `round_binary_with` but also returns the exceptions the rounding raises, with
+    /// tininess detected before rounding
+    fn round_binary_exc(
+        mode: Round,
+        sign: bool,
+        hi: u128,
+        lo: u128,
+        sticky: bool,
+        e: i32,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> (u128, i32) {
+        // Whether the magnitude goes up to the next representable number
+        let away = |half: bool, below: bool, odd: bool| match mode {
+            Round::ToNearest => half && (below || odd),
+            Round::Downward => sign && (half || below),
+            Round::Upward => !sign && (half || below),
+            Round::TowardZero => false,
+        };
+        let negative = sign;
+        let sign = (sign as u128) << (significand_bits + exponent_bits);
+        let max_exponent = (1 << exponent_bits) - 1;
+        let bias = max_exponent >> 1;
+        let significand_bits = significand_bits as i32;
+
+        let bit = |n: i32| if n < 128 { lo >> n & 1 == 1 } else { hi >> (n - 128) & 1 == 1 };
+        let any_below = |n: i32| if n <= 0 {
+            false
+        } else if n < 128 {
+            lo << (128 - n) != 0
+        } else {
+            lo != 0 || (n > 128 && hi << (256 - n) != 0)
+        };
+
+        let top = if hi != 0 {
+            255 - hi.leading_zeros() as i32
+        } else if lo != 0 {
+            127 - lo.leading_zeros() as i32
+        } else {
+            return (sign, 0);
+        };
+        let tiny = top + e < 1 - bias;
+
+        // Weight of the last bit of the result, which is fixed for denormals
+        let mut ulp = cmp::max(top + e - significand_bits, 1 - bias - significand_bits);
+        let shift = ulp - e;
+        let mut inexact = false;
+        let mut significand = if shift <= 0 {
+            assert!(!sticky);
+            lo << -shift
+        } else if shift > top + 1 {
+            // Less than half of the smallest denormal
+            return (sign | away(false, true, false) as u128, FE_UNDERFLOW | FE_INEXACT);
+        } else {
+            let kept = if shift >= 256 {
+                0
+            } else if shift >= 128 {
+                hi >> (shift - 128)
+            } else {
+                (hi << (128 - shift)) | (lo >> shift)
+            };
+            let (half, below) = (bit(shift - 1), any_below(shift - 1) || sticky);
+            inexact = half || below;
+            kept + away(half, below, kept & 1 == 1) as u128
+        };
+        let flags = match (inexact, tiny) {
+            (false, _) => 0,
+            (true, false) => FE_INEXACT,
+            (true, true) => FE_UNDERFLOW | FE_INEXACT,
+        };
+
+        if significand < 1 << significand_bits {
+            // Denormal
+            return (sign | significand, flags);
+        }
+        if significand == 1 << (significand_bits + 1) {
+            significand >>= 1;
+            ulp += 1;
+        }
+        let exponent = ulp + bias + significand_bits;
+        if exponent >= max_exponent {
+            let infinity = (max_exponent as u128) << significand_bits;
+            let to_infinity = match mode {
+                Round::ToNearest => true,
+                Round::Downward => negative,
+                Round::Upward => !negative,
+                Round::TowardZero => false,
+            };
+            let rep = sign | if to_infinity { infinity } else { infinity - 1 };
+            (rep, FE_OVERFLOW | FE_INEXACT)
+        } else {
+            let rep = sign | ((exponent as u128) << significand_bits) |
+                (significand & ((1 << significand_bits) - 1));
+            (rep, flags)
+        }
+    }
+
+    fn f128_round(sign: bool, hi: u128, lo: u128, sticky: bool, e: i32) -> u128 {
+        round_binary(sign, hi, lo, sticky, e, 112, 15)
+    }
+
+    /// Returns the representation of `a` as a quadruple precision number, rounded if necessary
+    fn f128_from_i128(a: i128) -> u128 {
+        let magnitude = if a < 0 { (a as u128).wrapping_neg() } else { a as u128 };
+        f128_round(a < 0, 0, magnitude, false, 0)
+    }
+
+    /// Returns the representation of `x`, which must not be NaN, as a quadruple precision number
+    fn f128_from_f64(x: f64) -> u128 {
+        let rep = to_u64(x);
+        let sign = rep >> 63 != 0;
+        let exponent = ((rep >> 52) & 0x7ff) as i32;
+        let significand = (rep & ((1 << 52) - 1)) as u128;
+        match exponent {
+            0 => f128_round(sign, 0, significand, false, -1074),
+            0x7ff => ((sign as u128) << 127) | F128_INF,
+            _ => f128_round(sign, 0, significand | (1 << 52), false, exponent - 1075),
+        }
+    }
+
+    /// Returns the quadruple precision number `x`, which must not be NaN, rounded to the binary
+    /// format with the given field widths
+    fn f128_narrow(x: u128, significand_bits: u32, exponent_bits: u32) -> u128 {
+        f128_narrow_with(Round::ToNearest, x, significand_bits, exponent_bits)
+    }
+
+    /// Like `f128_narrow` but rounds in the direction `mode`
+    fn f128_narrow_with(mode: Round, x: u128, significand_bits: u32, exponent_bits: u32) -> u128 {
+        let (sign, m, e) = f128_parts(x);
+        if x & !F128_SIGN == F128_INF {
+            let infinity = ((1 << exponent_bits) - 1) << significand_bits;
+            ((sign as u128) << (significand_bits + exponent_bits)) | infinity
+        } else {
+            round_binary_with(mode, sign, 0, m, false, e, significand_bits, exponent_bits)
+        }
+    }
+
+    /// Returns the quadruple precision number `x` truncated to an integer if that is in the range
+    /// of `i128`
+    fn f128_to_i128(x: u128) -> Option<i128> {
+        f128_trunc(x).and_then(
+            |(sign, m)| if !sign {
+                i128(m).ok()
+            } else if m <= 1 << 127 {
+                Some((m as i128).wrapping_neg())
+            } else {
+                None
+            },
+        )
+    }
+
+    /// Returns the quadruple precision number `x` truncated to an integer if that is in the range
+    /// of `u128`
+    fn f128_to_u128(x: u128) -> Option<u128> {
+        f128_trunc(x).and_then(|(sign, m)| if sign && m != 0 { None } else { Some(m) })
+    }
+
+    /// Returns the quadruple precision number `x` truncated to an integer, as a sign and magnitude,
+    /// or `None` if it is infinite, NaN or its magnitude is at least `2^128`
+    fn f128_trunc(x: u128) -> Option<(bool, u128)> {
+        if x & !F128_SIGN >= F128_INF {
+            return None;
+        }
+        let (sign, m, e) = f128_parts(x);
+        if e >= 16 {
+            None
+        } else if e >= 0 {
+            Some((sign, m << e))
+        } else if e > -128 {
+            Some((sign, m >> -e))
+        } else {
+            Some((sign, 0))
+        }
+    }
+
+    /// The exact result of an arithmetic operation
+    enum Exact {
+        /// A zero, an infinity or one of the operands, which the format of the operands can
+        /// represent, and the exceptions the operation raises
+        Special(u128, i32),
+        /// `(-1)^sign * (m + t) * 2^e` with `m = hi * 2^128 + lo`, see `round_binary`
+        Finite(bool, u128, u128, bool, i32),
+    }
+
+    impl Exact {
+        /// Returns `self` rounded in the direction `mode` to the binary format with the given
+        /// field widths, and the exceptions the operation raises
+        fn round(self, mode: Round, significand_bits: u32, exponent_bits: u32) -> (u128, i32) {
+            let (sb, eb) = (significand_bits, exponent_bits);
+            match self {
+                Exact::Special(x, flags) => (f128_narrow(x, sb, eb), flags),
+                Exact::Finite(sign, hi, lo, sticky, e) => {
+                    round_binary_exc(mode, sign, hi, lo, sticky, e, sb, eb)
+                }
+            }
+        }
+
+        /// Like `round`, but a nonzero result that is tiny before rounding is flushed to a zero
+        /// of the same sign, which raises only the underflow exception
+        fn round_flush(self, mode: Round, sb: u32, eb: u32) -> (u128, i32) {
+            if let Exact::Finite(sign, hi, lo, sticky, e) = self {
+                // The exponent of the smallest normal number
+                let min_exponent = 2 - (1 << (eb - 1));
+                let tiny = if hi != 0 {
+                    255 - hi.leading_zeros() as i32 + e < min_exponent
+                } else if lo != 0 {
+                    127 - lo.leading_zeros() as i32 + e < min_exponent
+                } else {
+                    sticky && e <= min_exponent
+                };
+                if tiny {
+                    return ((sign as u128) << (sb + eb), FE_UNDERFLOW);
+                }
+            }
+            self.round(mode, sb, eb)
+        }
+    }
+
+    /// Returns the number `x` of the binary format with the given field widths, or a zero of the
+    /// same sign if `x` is subnormal
+    fn flush_operand(x: u128, significand_bits: u32, exponent_bits: u32) -> u128 {
+        if x >> significand_bits & ((1 << exponent_bits) - 1) == 0 {
+            x & (1 << (significand_bits + exponent_bits))
+        } else {
+            x
+        }
+    }
+
+    /// Reference quadruple precision addition, rounding in the direction `mode`. Neither operand
+    /// may be NaN; returns `None` if the result is NaN
+    fn f128_add(mode: Round, a: u128, b: u128) -> Option<u128> {
+        f128_add_exact(mode, a, b).map(|x| x.round(mode, 112, 15).0)
+    }
+
+    /// The exact result of `a + b`, the operands being quadruple precision numbers that may
+    /// come from a narrower format. `mode` decides the sign of an exact zero
+    fn f128_add_exact(mode: Round, a: u128, b: u128) -> Option<Exact> {
+        let (a_abs, b_abs) = (a & !F128_SIGN, b & !F128_SIGN);
+        if a_abs == F128_INF || b_abs == F128_INF {
+            return if a_abs != F128_INF {
+                Some(Exact::Special(b, 0))
+            } else if b_abs != F128_INF || a == b {
+                Some(Exact::Special(a, 0))
+            } else {
+                None
+            };
+        }
+        if a_abs == 0 && b_abs == 0 {
+            let zero = if mode == Round::Downward { a | b } else { a & b };
+            return Some(Exact::Special(zero, 0));
+        }
+
+        // `x` is the operand with the larger magnitude
+        let (x, y) = if a_abs >= b_abs { (a, b) } else { (b, a) };
+        if y & !F128_SIGN == 0 {
+            return Some(Exact::Special(x, 0));
+        }
+        let (x_sign, x_m, x_e) = f128_parts(x);
+        let (y_sign, y_m, y_e) = f128_parts(y);
+        let subtraction = x_sign != y_sign;
+
+        let d = (x_e - y_e) as u32;
+        let (hi, lo, sticky, e) = if d <= 128 {
+            // The exact result fits in 256 bits
+            let (hi, lo) = if d == 0 {
+                (0, x_m)
+            } else if d < 128 {
+                (x_m >> (128 - d), x_m << d)
+            } else {
+                (x_m, 0)
+            };
+            let (hi, lo) = if subtraction {
+                let (lo, borrow) = lo.overflowing_sub(y_m);
+                (hi - borrow as u128, lo)
+            } else {
+                let (lo, carry) = lo.overflowing_add(y_m);
+                (hi + carry as u128, lo)
+            };
+            (hi, lo, false, y_e)
+        } else {
+            // The bits of `y` below `2^(x_e - 128)` only affect the result through the sticky
+            // bit, which is far below the rounding position
+            let s = d - 128;
+            let (y_m, lost) = if s >= 128 {
+                (0, true)
+            } else {
+                (y_m >> s, y_m << (128 - s) != 0)
+            };
+            let (hi, lo) = if subtraction {
+                let (lo, borrow) = 0u128.overflowing_sub(y_m + lost as u128);
+                (x_m - borrow as u128, lo)
+            } else {
+                (x_m, y_m)
+            };
+            (hi, lo, lost, x_e - 128)
+        };
+
+        if hi == 0 && lo == 0 {
+            // Exact cancellation
+            Some(Exact::Special(if mode == Round::Downward { F128_SIGN } else { 0 }, 0))
+        } else {
+            Some(Exact::Finite(x_sign, hi, lo, sticky, e))
+        }
+    }
+
+    /// Reference quadruple precision multiplication, rounding in the direction `mode`. Neither
+    /// operand may be NaN; returns `None` if the result is NaN
+    fn f128_mul(mode: Round, a: u128, b: u128) -> Option<u128> {
+        f128_mul_exact(a, b).map(|x| x.round(mode, 112, 15).0)
+    }
+
+    /// The exact result of `a * b`, see `f128_add_exact`
+    fn f128_mul_exact(a: u128, b: u128) -> Option<Exact> {
+        let (a_abs, b_abs) = (a & !F128_SIGN, b & !F128_SIGN);
+        let sign = (a ^ b) & F128_SIGN;
+        if a_abs == F128_INF || b_abs == F128_INF {
+            return if a_abs == 0 || b_abs == 0 {
+                None
+            } else {
+                Some(Exact::Special(sign | F128_INF, 0))
+            };
+        }
+
+        let (_, a_m, a_e) = f128_parts(a);
+        let (_, b_m, b_e) = f128_parts(b);
+
+        // Schoolbook multiplication of the 64-bit halves
+        let mask = (1 << 64) - 1;
+        let (ah, al, bh, bl) = (a_m >> 64, a_m & mask, b_m >> 64, b_m & mask);
+        let (ll, lh, hl, hh) = (al * bl, al * bh, ah * bl, ah * bh);
+        let mid = (ll >> 64) + (lh & mask) + (hl & mask);
+        let lo = (ll & mask) | (mid << 64);
+        let hi = hh + (lh >> 64) + (hl >> 64) + (mid >> 64);
+
+        Some(Exact::Finite(sign != 0, hi, lo, false, a_e + b_e))
+    }
+
+    /// Reference quadruple precision division, rounding in the direction `mode`. Neither operand
+    /// may be NaN; returns `None` if the result is NaN
+    fn f128_div(mode: Round, a: u128, b: u128) -> Option<u128> {
+        f128_div_exact(a, b).map(|x| x.round(mode, 112, 15).0)
+    }
+
+    /// The exact result of `a / b`, see `f128_add_exact`
+    fn f128_div_exact(a: u128, b: u128) -> Option<Exact> {
+        let (a_abs, b_abs) = (a & !F128_SIGN, b & !F128_SIGN);
+        let sign = (a ^ b) & F128_SIGN;
+        if a_abs == F128_INF {
+            return if b_abs == F128_INF {
+                None
+            } else {
+                Some(Exact::Special(sign | F128_INF, 0))
+            };
+        }
+        if b_abs == F128_INF {
+            return Some(Exact::Special(sign, 0));
+        }
+        if b_abs == 0 {
+            return if a_abs == 0 {
+                None
+            } else {
+                Some(Exact::Special(sign | F128_INF, FE_DIVBYZERO))
+            };
+        }
+        if a_abs == 0 {
+            return Some(Exact::Special(sign, 0));
+        }
+
+        let (_, a_m, a_e) = f128_parts(a);
+        let (_, b_m, b_e) = f128_parts(b);
+        let (a_shift, b_shift) = (a_m.leading_zeros() - 15, b_m.leading_zeros() - 15);
+        let (a_m, a_e) = (a_m << a_shift, a_e - a_shift as i32);
+        let (b_m, b_e) = (b_m << b_shift, b_e - b_shift as i32);
+
+        // Long division, computing 120 bits after the binary point
+        let (mut q, mut r) = (a_m / b_m, a_m % b_m);
+        for _ in 0..120 {
+            q <<= 1;
+            r <<= 1;
+            if r >= b_m {
+                r -= b_m;
+                q |= 1;
+            }
+        }
+
+        Some(Exact::Finite(sign != 0, 0, q, r != 0, a_e - b_e - 120))
+    }
+
+    /// Reference quadruple precision `a + b`, `a - b`, `a * b` or `a / b` (`op` 0 to 3), rounding
+    /// in the direction `mode`. Neither operand may be NaN; returns `None` if the result is NaN
+    fn f128_arith(mode: Round, op: u8, a: u128, b: u128) -> Option<u128> {
+        f128_arith_exact(mode, op, a, b).map(|x| x.round(mode, 112, 15).0)
+    }
+
+    /// The exact result of `f128_arith`, see `f128_add_exact`
+    fn f128_arith_exact(mode: Round, op: u8, a: u128, b: u128) -> Option<Exact> {
+        match op {
+            0 => f128_add_exact(mode, a, b),
+            1 => f128_add_exact(mode, a, b ^ F128_SIGN),
+            2 => f128_mul_exact(a, b),
+            _ => f128_div_exact(a, b),
+        }
+    }
+
+    /// Splits the finite number `x` of the binary format with the given field widths into its sign,
+    /// a significand `m` and an exponent `e` such that `|x| = m * 2^e`
+    fn binary_parts(x: u128, significand_bits: u32, exponent_bits: u32) -> (bool, u128, i32) {
+        let max_exponent = (1 << exponent_bits) - 1;
+        let bias = max_exponent >> 1;
+        let sign = x >> (significand_bits + exponent_bits) & 1 != 0;
+        let exponent = (x >> significand_bits) as i32 & max_exponent;
+        let significand = x & ((1 << significand_bits) - 1);
+        if exponent == 0 {
+            (sign, significand, 1 - bias - significand_bits as i32)
+        } else {
+            let e = exponent - bias - significand_bits as i32;
+            (sign, significand | (1 << significand_bits), e)
+        }
+    }
+
+    /// Returns the representation `x` of a number of the binary format with the given field
+    /// widths, which must not be NaN, as a quadruple precision number
+    fn f128_from_binary(x: u128, significand_bits: u32, exponent_bits: u32) -> u128 {
+        let max_exponent = (1 << exponent_bits) - 1;
+        let sign = x >> (significand_bits + exponent_bits) & 1 != 0;
+        if (x >> significand_bits) as i32 & max_exponent == max_exponent {
+            ((sign as u128) << 127) | F128_INF
+        } else {
+            let (sign, m, e) = binary_parts(x, significand_bits, exponent_bits);
+            f128_round(sign, 0, m, false, e)
+        }
+    }
+
+    /// Reference `a + b`, `a - b`, `a * b`, `a / b` (`op` 0 to 3) or `__powi*f2(a, b)` (`op` 4,
+    /// with `b` an `i32`) for the binary format with the given field widths. Returns the result,
+    /// which is the default NaN if it is a NaN, and the exceptions the operation raises
+    fn arith_exc(
+        mode: Round,
+        op: u8,
+        a: u128,
+        b: u128,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> (u128, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let nan = infinity | quiet_bit;
+        let abs = |x: u128| x & ((1 << (sb + eb)) - 1);
+        let signaling = |x: u128| abs(x) > infinity && x & quiet_bit == 0;
+        let widen = |x: u128| f128_from_binary(x, sb, eb);
+
+        if op == 4 {
+            assert!(abs(a) <= infinity);
+            let mut flags = 0;
+            let mut mul = |x: u128, y: u128| {
+                let (r, f) = f128_mul_exact(widen(x), widen(y)).unwrap().round(mode, sb, eb);
+                flags |= f;
+                r
+            };
+            let bias = (1 << (eb - 1)) - 1;
+            let one = (bias as u128) << sb;
+            let (mut a, mut b) = (a, b as i32);
+            let recip = b < 0;
+
+            // For a negative power the products are rounded as if the exponent range had no
+            // limits, by keeping their exponents apart, and the reciprocal is rounded once
+            let finite = abs(a) != 0 && abs(a) != infinity;
+            let scale = |x: u128, exp: &mut i32| if recip && finite {
+                let (sign, m, e) = binary_parts(x, sb, eb);
+                let shift = sb + 1 - (128 - m.leading_zeros());
+                *exp += e + (sb + 1 - shift) as i32;
+                ((sign as u128) << (sb + eb)) | ((bias as u128 - 1) << sb) |
+                    ((m << shift) & ((1 << sb) - 1))
+            } else {
+                x
+            };
+            let mut r = one;
+            let (mut r_exp, mut a_exp) = (0, 0);
+            a = scale(a, &mut a_exp);
+            loop {
+                if (b & 1) != 0 {
+                    r_exp += a_exp;
+                    r = scale(mul(r, a), &mut r_exp);
+                }
+                b /= 2;
+                if b == 0 {
+                    break;
+                }
+                a_exp *= 2;
+                a = scale(mul(a, a), &mut a_exp);
+            }
+            if recip {
+                let (r, f) = match f128_div_exact(widen(one), widen(r)).unwrap() {
+                    Exact::Finite(sign, hi, lo, sticky, e) => {
+                        Exact::Finite(sign, hi, lo, sticky, e - r_exp).round(mode, sb, eb)
+                    }
+                    special => special.round(mode, sb, eb),
+                };
+                return (r, flags | f);
+            }
+            return (r, flags);
+        }
+
+        if abs(a) > infinity || abs(b) > infinity {
+            let invalid = signaling(a) || signaling(b);
+            return (nan, if invalid { FE_INVALID } else { 0 });
+        }
+        match f128_arith_exact(mode, op, widen(a), widen(b)) {
+            Some(x) => x.round(mode, sb, eb),
+            None => (nan, FE_INVALID),
+        }
+    }
+
+    /// Reference exceptions raised by the conversion of the quadruple precision number `x` to an
+    /// integer with `bits` bits. NaN and out of range values are invalid, and truncating a
+    /// fraction is inexact
+    fn fix_exc(x: u128, signed: bool, bits: u32) -> i32 {
+        if f128_is_nan(x) {
+            return FE_INVALID;
+        }
+        let (sign, m) = match f128_trunc(x) {
+            Some(t) => t,
+            None => return FE_INVALID,
+        };
+        let fits = if signed {
+            m <= (1 << (bits - 1)) - (!sign) as u128
+        } else {
+            (!sign || m == 0) && (bits == 128 || m < 1 << bits)
+        };
+        if !fits {
+            return FE_INVALID;
+        }
+        let (_, significand, e) = f128_parts(x);
+        let fraction = if e >= 0 {
+            0
+        } else if e > -128 {
+            significand & ((1 << -e) - 1)
+        } else {
+            significand
+        };
+        if fraction != 0 { FE_INEXACT } else { 0 }
+    }
+
+    // The two's complement result of converting the binary128 `x` to a `bits` wide integer, as
+    // the saturating conversions do it if `saturating` is set, and as `__fix*` do otherwise
+    fn fix_result(x: u128, signed: bool, bits: u32, saturating: bool) -> u128 {
+        let sign = x & F128_SIGN != 0;
+        let (min, max) = if signed {
+            ((1u128 << (bits - 1)).wrapping_neg(), (1 << (bits - 1)) - 1)
+        } else if bits == 128 {
+            (0, !0)
+        } else {
+            (0, (1 << bits) - 1)
+        };
+        if f128_is_nan(x) {
+            return if saturating || !signed && sign {
+                0
+            } else if sign {
+                min
+            } else {
+                max
+            };
+        }
+        match f128_trunc(x) {
+            None => if sign { min } else { max },
+            Some((true, m)) => {
+                if !signed {
+                    0
+                } else if m > 1 << (bits - 1) {
+                    min
+                } else {
+                    m.wrapping_neg()
+                }
+            }
+            Some((false, m)) => if m > max { max } else { m },
+        }
+    }
+
+    // Big unsigned integers, as little endian 32-bit limbs. Only the references that need more
+    // than 256 bits use them
+
+    fn big(x: u128) -> Vec<u32> {
+        (0..4).map(|i| (x >> (32 * i)) as u32).collect()
+    }
+
+    fn big_bits(a: &[u32]) -> u32 {
+        match a.iter().rposition(|&limb| limb != 0) {
+            Some(i) => 32 * i as u32 + 32 - a[i].leading_zeros(),
+            None => 0,
+        }
+    }
+
+    fn big_bit(a: &[u32], n: u32) -> bool {
+        a.get((n / 32) as usize).map_or(false, |&limb| limb >> (n % 32) & 1 != 0)
+    }
+
+    fn big_shl(a: &[u32], n: u32) -> Vec<u32> {
+        let mut r = vec![0; (n / 32) as usize];
+        let mut carry = 0;
+        for &limb in a {
+            r.push(limb << (n % 32) | carry);
+            carry = if n % 32 == 0 { 0 } else { limb >> (32 - n % 32) };
+        }
+        r.push(carry);
+        r
+    }
+
+    fn big_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut r = vec![0u32; a.len() + b.len()];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &y) in b.iter().enumerate() {
+                let t = r[i + j] as u64 + x as u64 * y as u64 + carry;
+                r[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            r[i + b.len()] = carry as u32;
+        }
+        r
+    }
+
+    fn big_cmp(a: &[u32], b: &[u32]) -> Ordering {
+        let n = cmp::max(a.len(), b.len());
+        for i in (0..n).rev() {
+            let (x, y) = (*a.get(i).unwrap_or(&0), *b.get(i).unwrap_or(&0));
+            if x != y {
+                return x.cmp(&y);
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// Returns `a + b` if `add` is set and `a - b` otherwise, which requires `a >= b`
+    fn big_add_sub(a: &[u32], b: &[u32], add: bool) -> Vec<u32> {
+        let mut r = Vec::new();
+        let mut carry = 0i64;
+        for i in 0..cmp::max(a.len(), b.len()) + 1 {
+            let (x, y) = (*a.get(i).unwrap_or(&0) as i64, *b.get(i).unwrap_or(&0) as i64);
+            let t = if add { x + y + carry } else { x - y + carry };
+            r.push(t as u32);
+            carry = t >> 32;
+        }
+        assert_eq!(carry, 0);
+        r
+    }
+
+    /// Reference fused multiply-add `a * b + c` for the binary format with the given field widths,
+    /// computed exactly with big integers and rounded once in the direction `mode`. Returns the
+    /// result, which is the default NaN if it is a NaN, and the exceptions the operation raises
+    fn fma_exc(
+        mode: Round,
         a: u128,
         b: u128,
         c: u128,
-    }
-
-    impl TestCase for Umodti3 {
-        fn name() -> &'static str {
-            "umodti3"
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> (u128, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let nan = infinity | quiet_bit;
+        let sign_bit = 1 << (sb + eb);
+        let abs = |x: u128| x & (sign_bit - 1);
+        let is_nan = |x: u128| abs(x) > infinity;
+        let signaling = |x: u128| is_nan(x) && x & quiet_bit == 0;
+
+        // As on ARM, infinity * zero is invalid even if `c` is a quiet NaN
+        let invalid = signaling(a) || signaling(b) || signaling(c) ||
+            (abs(a) == infinity && abs(b) == 0) || (abs(a) == 0 && abs(b) == infinity);
+        if invalid || is_nan(a) || is_nan(b) || is_nan(c) {
+            return (nan, if invalid { FE_INVALID } else { 0 });
+        }
+        let product_sign = (a ^ b) & sign_bit;
+        if abs(a) == infinity || abs(b) == infinity {
+            if abs(c) == infinity && c & sign_bit != product_sign {
+                return (nan, FE_INVALID);
+            }
+            return (product_sign | infinity, 0);
+        }
+        if abs(c) == infinity {
+            return (c, 0);
         }
 
-        fn generate<R>(rng: &mut R) -> Option<Self>
-        where
-            R: Rng,
-            Self: Sized,
-        {
-            let a = gen_u128(rng);
-            let b = gen_u128(rng);
-            if b == 0 {
-                return None;
+        // `a * b = p * 2^p_e` and `c = q * 2^q_e`, aligned to the smaller exponent
+        let (_, a_m, a_e) = binary_parts(a, sb, eb);
+        let (_, b_m, b_e) = binary_parts(b, sb, eb);
+        let (c_sign, c_m, c_e) = binary_parts(c, sb, eb);
+        let p = big_mul(&big(a_m), &big(b_m));
+        let p_e = a_e + b_e;
+        let e = cmp::min(p_e, c_e);
+        let p = big_shl(&p, (p_e - e) as u32);
+        let q = big_shl(&big(c_m), (c_e - e) as u32);
+        let p_sign = product_sign != 0;
+
+        let (sign, m) = if p_sign == c_sign {
+            (p_sign, big_add_sub(&p, &q, true))
+        } else {
+            match big_cmp(&p, &q) {
+                Ordering::Less => (c_sign, big_add_sub(&q, &p, false)),
+                _ => (p_sign, big_add_sub(&p, &q, false)),
             }
-            let c = a % b;
+        };
 
-            Some(Umodti3 { a, b, c })
+        let bits = big_bits(&m);
+        if bits == 0 {
+            // Zeros of the same sign keep it, any other zero sum is +0, or -0 when rounding
+            // downward
+            let zeros = (abs(a) == 0 || abs(b) == 0) && abs(c) == 0;
+            let sign = if zeros && p_sign == c_sign {
+                p_sign
+            } else {
+                mode == Round::Downward
+            };
+            return (if sign { sign_bit } else { 0 }, 0);
+        }
+
+        // Keep the leading 256 bits, which is more than enough to round
+        let shift = bits.saturating_sub(256);
+        let limbs = (shift / 32) as usize;
+        let sticky = m[..limbs].iter().any(|&limb| limb != 0) ||
+            m[limbs] & ((1 << (shift % 32)) - 1) != 0;
+        let word = |k: u32| {
+            (0..128).fold(0, |w, i| w | (big_bit(&m, shift + k + i) as u128) << i)
+        };
+        round_binary_exc(mode, sign, word(128), word(0), sticky, e + shift as i32, sb, eb)
+    }
+
+    /// Reference square root for the binary format with the given field widths, computed one bit
+    /// at a time with big integers and rounded in the direction `mode`. Returns the result, which
+    /// is the default NaN if it is a NaN, and the exceptions the operation raises
+    fn sqrt_exc(mode: Round, a: u128, significand_bits: u32, exponent_bits: u32) -> (u128, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let sign_bit = 1 << (sb + eb);
+        let abs = a & (sign_bit - 1);
+        if abs > infinity {
+            return (infinity | quiet_bit, if a & quiet_bit == 0 { FE_INVALID } else { 0 });
+        }
+        if abs == 0 || a == infinity {
+            return (a, 0);
+        }
+        if a & sign_bit != 0 {
+            return (infinity | quiet_bit, FE_INVALID);
+        }
+
+        // `a = m * 2^e` with an even `e`, scaled up so that the root has a few more bits than the
+        // significand
+        let (_, m, e) = binary_parts(a, sb, eb);
+        let (m, e) = if e & 1 != 0 { (m << 1, e - 1) } else { (m, e) };
+        let k = sb + 4 - big_bits(&big(m)) / 2;
+        let n = big_shl(&big(m), 2 * k);
+
+        let mut root = 0u128;
+        for i in (0..128).rev() {
+            let r = root | 1 << i;
+            if big_cmp(&big_mul(&big(r), &big(r)), &n) != Ordering::Greater {
+                root = r;
+            }
         }
+        let sticky = big_cmp(&big_mul(&big(root), &big(root)), &n) != Ordering::Equal;
+        round_binary_exc(mode, false, 0, root, sticky, e / 2 - k as i32, sb, eb)
+    }
 
-        fn to_string(&self, buffer: &mut String) {
-            writeln!(
-                buffer,
-                "(({a}, {b}), {c}),",
-                a = self.a,
-                b = self.b,
-                c = self.c
-            )
-                    .unwrap();
+    /// Reference for `float::integral`: rounds `a`, of the binary format with the given field
+    /// widths, to an integral value with the function `op` (floor, ceil, trunc, round, rint and
+    /// nearbyint, in that order). Returns the result, which is the default NaN if it is a NaN, and
+    /// the exceptions the operation raises
+    fn integral_exc(
+        op: u8,
+        mode: Round,
+        a: u128,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> (u128, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let sign_bit = 1 << (sb + eb);
+        let abs = a & (sign_bit - 1);
+        if abs > infinity {
+            return (infinity | quiet_bit, if a & quiet_bit == 0 { FE_INVALID } else { 0 });
+        }
+        let (sign, m, e) = binary_parts(a, sb, eb);
+        if abs == 0 || abs == infinity || e >= 0 {
+            return (a, 0);
+        }
+
+        // `a = (q + f) * 2^0` with the fraction `f` split into its first bit and the rest
+        let n = -e as u32;
+        let (q, half, below) = if n > sb + 1 {
+            (0, false, true)
+        } else {
+            (m >> n, m >> (n - 1) & 1 != 0, m & ((1 << (n - 1)) - 1) != 0)
+        };
+        if !half && !below {
+            return (a, 0);
+        }
+        let away = match op {
+            0 => sign,
+            1 => !sign,
+            2 => false,
+            3 => half,
+            _ => match mode {
+                Round::ToNearest => half && (below || q & 1 != 0),
+                Round::Downward => sign,
+                Round::Upward => !sign,
+                Round::TowardZero => false,
+            },
+        };
+        let flags = if op == 4 { FE_INEXACT } else { 0 };
+        let q = q + away as u128;
+        if q == 0 {
+            ((sign as u128) << (sb + eb), flags)
+        } else {
+            (round_binary_exc(mode, sign, 0, q, false, 0, sb, eb).0, flags)
         }
+    }
 
-        fn prologue() -> &'static str {
-            "
-use compiler_builtins::int::udiv::__umodti3;
-
-static TEST_CASES: &[((u128, u128), u128)] = &[
-"
+    /// Reference for `float::minmax`: returns `fmin`, `fmax`, `fminimum` or `fmaximum` (`op` 0
+    /// to 3) of `a` and `b`, of the binary format with the given field widths, and the exceptions
+    /// the operation raises. A NaN result is the first NaN operand, quieted
+    fn minmax_exc(
+        op: u8,
+        a: u128,
+        b: u128,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> (u128, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let sign_bit = 1 << (sb + eb);
+        let is_nan = |x: u128| x & (sign_bit - 1) > infinity;
+        let is_snan = |x: u128| is_nan(x) && x & quiet_bit == 0;
+        let flags = if is_snan(a) || is_snan(b) { FE_INVALID } else { 0 };
+        if is_nan(a) || is_nan(b) {
+            let nan = if is_nan(a) { a } else { b } | quiet_bit;
+            // `fmin` and `fmax` only return a NaN for a signaling NaN or two NaNs
+            return if op >= 2 || flags != 0 || (is_nan(a) && is_nan(b)) {
+                (nan, flags)
+            } else if is_nan(a) {
+                (b, 0)
+            } else {
+                (a, 0)
+            };
+        }
+
+        // Orders the numbers, with -0 below +0
+        let key = |x: u128| if x & sign_bit != 0 {
+            -((x & (sign_bit - 1)) as i128) - 1
+        } else {
+            x as i128
+        };
+        let maximum = op & 1 != 0;
+        (if (key(a) < key(b)) != maximum { a } else { b }, 0)
+    }
+
+    /// Reference for `float::scale`: `scalbn(a, b)`, `ldexp(a, b)`, `frexp(a)`, `ilogb(a)`,
+    /// `logb(a)` or `nextafter(a, b)` (`op` 0 to 5) for the binary format with the given field
+    /// widths, with `b` an `i32` for `scalbn` and `ldexp`. Returns the float result, which is the
+    /// default NaN if it is a NaN, the integer result of `frexp` and `ilogb`, and the exceptions
+    /// the operation raises
+    fn scale_exc(
+        op: u8,
+        mode: Round,
+        a: u128,
+        b: u128,
+        significand_bits: u32,
+        exponent_bits: u32,
+    ) -> (u128, i32, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let nan = infinity | quiet_bit;
+        let sign_bit = 1 << (sb + eb);
+        let bias = (1 << (eb - 1)) - 1;
+        let abs = |x: u128| x & (sign_bit - 1);
+        let is_nan = |x: u128| abs(x) > infinity;
+        let invalid = |x: u128| if is_nan(x) && x & quiet_bit == 0 { FE_INVALID } else { 0 };
+
+        if op == 3 {
+            return match abs(a) {
+                0 => (0, i32::min_value(), FE_INVALID),
+                x if x == infinity => (0, i32::max_value(), FE_INVALID),
+                x if x > infinity => (0, i32::min_value(), FE_INVALID),
+                _ => {
+                    let (_, m, e) = binary_parts(a, sb, eb);
+                    (0, e + 127 - m.leading_zeros() as i32, 0)
+                }
+            };
+        }
+        if op == 5 {
+            if is_nan(a) || is_nan(b) {
+                return (nan, 0, invalid(a) | invalid(b));
+            }
+            let key = |x: u128| if x & sign_bit != 0 { -(abs(x) as i128) } else { x as i128 };
+            let (a_key, b_key) = (key(a), key(b));
+            if a_key == b_key {
+                return (b, 0, 0);
+            }
+            // The representations of the numbers of each sign are consecutive integers, ordered
+            // by magnitude
+            let c = if abs(a) == 0 {
+                (b & sign_bit) | 1
+            } else if (a_key < b_key) == (a & sign_bit == 0) {
+                a + 1
+            } else {
+                a - 1
+            };
+            let flags = if abs(c) == infinity {
+                FE_OVERFLOW | FE_INEXACT
+            } else if abs(c) < 1 << sb {
+                FE_UNDERFLOW | FE_INEXACT
+            } else {
+                0
+            };
+            return (c, 0, flags);
+        }
+        if is_nan(a) {
+            return (nan, 0, invalid(a));
+        }
+        if abs(a) == 0 || abs(a) == infinity {
+            return match op {
+                4 if abs(a) == 0 => (sign_bit | infinity, 0, FE_DIVBYZERO),
+                4 => (infinity, 0, 0),
+                _ => (a, 0, 0),
+            };
+        }
+
+        let (sign, m, e) = binary_parts(a, sb, eb);
+        // The exponent of the leading bit
+        let top = e + 127 - m.leading_zeros() as i32;
+        match op {
+            0 | 1 => {
+                let n = cmp::max(cmp::min(b as i32, 100_000), -100_000);
+                let (c, flags) = round_binary_exc(mode, sign, 0, m, false, e + n, sb, eb);
+                (c, 0, flags)
+            }
+            2 => {
+                let fraction = m << (sb + 1 - (128 - m.leading_zeros()));
+                let c = ((sign as u128) << (sb + eb)) | ((bias as u128 - 1) << sb) |
+                    (fraction & ((1 << sb) - 1));
+                (c, top + 1, 0)
+            }
+            _ => {
+                let c = round_binary(top < 0, 0, top.abs() as u128, false, 0, sb, eb);
+                (c, 0, 0)
+            }
         }
+    }
 
-        fn epilogue() -> &'static str {
-            "
-];
+    fn big_trim(mut a: Vec<u32>) -> Vec<u32> {
+        while a.last() == Some(&0) {
+            a.pop();
+        }
+        a
+    }
 
-#[test]
-fn umodti3() {
-    for &((a, b), c) in TEST_CASES {
-        let c_ = __umodti3(a, b);
-        assert_eq!(((a, b), c), ((a, b), c_));
+    fn big_shr(a: &[u32], n: u32) -> Vec<u32> {
+        let (skip, shift) = ((n / 32) as usize, n % 32);
+        (skip..a.len())
+            .map(|i| {
+                let next = a.get(i + 1).map_or(0, |&limb| limb);
+                if shift == 0 { a[i] } else { a[i] >> shift | next << (32 - shift) }
+            })
+            .collect()
+    }
+
+    /// Returns `a mod 2^n`
+    fn big_low(a: &[u32], n: u32) -> Vec<u32> {
+        a.iter()
+            .enumerate()
+            .map(|(i, &limb)| {
+                let bit = 32 * i as u32;
+                if bit + 32 <= n {
+                    limb
+                } else if bit >= n {
+                    0
+                } else {
+                    limb & ((1 << (n - bit)) - 1)
+                }
+            })
+            .collect()
     }
-}
-"
+
+    /// Returns `a / d`, rounded down
+    fn big_div_small(a: &[u32], d: u32) -> Vec<u32> {
+        let mut q = vec![0; a.len()];
+        let mut rem = 0u64;
+        for i in (0..a.len()).rev() {
+            let t = rem << 32 | a[i] as u64;
+            q[i] = (t / d as u64) as u32;
+            rem = t % d as u64;
         }
+        q
     }
 
-    trait TestCase {
-        /// Name of the intrinsic to test
-        fn name() -> &'static str;
-        /// Generates a valid test case
-        fn generate<R>(rng: &mut R) -> Option<Self>
-        where
-            R: Rng,
-            Self: Sized;
-        /// Stringifies a test case
-        fn to_string(&self, buffer: &mut String);
-        /// Prologue of the test file
-        fn prologue() -> &'static str;
-        /// Epilogue of the test file
-        fn epilogue() -> &'static str;
+    /// Returns `a / b`, rounded down
+    fn big_div(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut q = vec![0; a.len()];
+        let mut r = Vec::new();
+        for n in (0..big_bits(a)).rev() {
+            r = big_shl(&r, 1);
+            if big_bit(a, n) {
+                r[0] |= 1;
+            }
+            if big_cmp(&r, b) != Ordering::Less {
+                r = big_add_sub(&r, b, false);
+                q[(n / 32) as usize] |= 1 << (n % 32);
+            }
+            r = big_trim(r);
+        }
+        q
+    }
+
+    /// Returns `atan(1/n)*2^bits`, or `atanh(1/n)*2^bits` if `hyperbolic` is set, from their
+    /// series, which is a few units short
+    fn big_atan_inv(n: u32, bits: u32, hyperbolic: bool) -> Vec<u32> {
+        let mut power = big_div_small(&big_shl(&[1], bits), n);
+        let (mut plus, mut minus) = (Vec::new(), Vec::new());
+        let mut k = 0;
+        while big_bits(&power) != 0 {
+            let term = big_div_small(&power, 2 * k + 1);
+            if k % 2 == 0 || hyperbolic {
+                plus = big_trim(big_add_sub(&plus, &term, true));
+            } else {
+                minus = big_trim(big_add_sub(&minus, &term, true));
+            }
+            power = big_trim(big_div_small(&power, n * n));
+            k += 1;
+        }
+        big_trim(big_add_sub(&plus, &minus, false))
     }
 
-    const PROLOGUE: &'static str = r#"
-extern crate compiler_builtins;
+    // The fixed point constants of the big floats, with `BIG_CONSTANT_BITS` fraction bits:
+    // `pi` from Machin's formula, `16*atan(1/5) - 4*atan(1/239)`, `ln(2) = 2*atanh(1/3)` and `2/pi`
+    // with `BIG_TWO_OVER_PI_BITS` fraction bits instead, which leave more than 400 bits after the
+    // point when the largest double is reduced modulo pi/2
 
-// test runner
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-extern crate utest_cortex_m_qemu;
+    const BIG_CONSTANT_BITS: u32 = 1600;
+    const BIG_TWO_OVER_PI_BITS: u32 = 1400;
 
-// overrides `panic!`
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-#[macro_use]
-extern crate utest_macros;
+    fn big_constants() -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        thread_local! {
+            static CONSTANTS: (Vec<u32>, Vec<u32>, Vec<u32>) = {
+                let bits = BIG_CONSTANT_BITS;
+                let pi = big_add_sub(
+                    &big_shl(&big_atan_inv(5, bits, false), 4),
+                    &big_shl(&big_atan_inv(239, bits, false), 2),
+                    false,
+                );
+                let pi = big_trim(pi);
+                let ln2 = big_trim(big_shl(&big_atan_inv(3, bits, true), 1));
+                let two_over_pi = big_div(&big_shl(&[1], bits + 1 + BIG_TWO_OVER_PI_BITS), &pi);
+                (pi, ln2, big_trim(two_over_pi))
+            };
+        }
+        CONSTANTS.with(|constants| constants.clone())
+    }
+
+    /// A real number `(-1)^neg * m * 2^e` for the references of the transcendental functions, with
+    /// the significand `m` truncated to `BIG_PRECISION` bits
+    #[derive(Clone)]
+    struct BigFloat {
+        neg: bool,
+        m: Vec<u32>,
+        e: i32,
+    }
+
+    const BIG_PRECISION: u32 = 256;
+
+    impl BigFloat {
+        fn new(neg: bool, m: Vec<u32>, e: i32) -> BigFloat {
+            let bits = big_bits(&m);
+            let (m, e) = if bits > BIG_PRECISION {
+                let shift = bits - BIG_PRECISION;
+                (big_shr(&m, shift), e + shift as i32)
+            } else {
+                (m, e)
+            };
+            BigFloat {
+                neg: neg,
+                m: big_trim(m),
+                e: e,
+            }
+        }
 
-#[cfg(all(target_arch = "arm",
-          not(any(target_env = "gnu", target_env = "musl")),
-          target_os = "linux",
-          test))]
-macro_rules! panic {
-    ($($tt:tt)*) => {
-        upanic!($($tt)*);
-    };
-}
-"#;
+        fn from_f64(x: f64) -> BigFloat {
+            let (neg, m, e) = binary_parts(to_u64(x) as u128, 52, 11);
+            BigFloat::new(neg, big(m), e)
+        }
 
-    macro_rules! gen_int {
-        ($name:ident, $ity:ident, $hty:ident) => {
-            fn $name<R>(rng: &mut R) -> $ity
-                where
-                R: Rng,
-            {
-                let mut mk = || if rng.gen_weighted_bool(10) {
-                    *rng.choose(&[::std::$hty::MAX, 0, ::std::$hty::MIN]).unwrap()
-                } else {
-                    rng.gen::<$hty>()
-                };
-                unsafe { mem::transmute([mk(), mk()]) }
-            }
+        fn from_i64(x: i64) -> BigFloat {
+            BigFloat::new(x < 0, big((x as i128).abs() as u128), 0)
+        }
 
+        fn is_zero(&self) -> bool {
+            self.m.is_empty()
         }
-    }
 
-    gen_int!(gen_i32, i32, i16);
-    gen_int!(gen_i64, i64, i32);
-    gen_int!(gen_i128, i128, i64);
+        /// The exponent past the most significant bit, `2^(top - 1) <= |self| < 2^top`
+        fn top(&self) -> i32 {
+            self.e + big_bits(&self.m) as i32
+        }
 
-    macro_rules! gen_float {
-        ($name:ident,
-         $fty:ident,
-         $uty:ident,
-         $bits:expr,
-         $significand_bits:expr) => {
-            pub fn $name<R>(rng: &mut R) -> $fty
-            where
-                R: Rng,
-            {
-                const BITS: u8 = $bits;
-                const SIGNIFICAND_BITS: u8 = $significand_bits;
+        fn neg(&self) -> BigFloat {
+            BigFloat { neg: !self.neg, ..self.clone() }
+        }
 
-                const SIGNIFICAND_MASK: $uty = (1 << SIGNIFICAND_BITS) - 1;
-                const SIGN_MASK: $uty = (1 << (BITS - 1));
-                const EXPONENT_MASK: $uty = !(SIGN_MASK | SIGNIFICAND_MASK);
+        /// Returns `self*2^n`
+        fn scale(&self, n: i32) -> BigFloat {
+            BigFloat { e: self.e + n, ..self.clone() }
+        }
 
-                fn mk_f32(sign: bool, exponent: $uty, significand: $uty) -> $fty {
-                    unsafe {
-                        mem::transmute(((sign as $uty) << (BITS - 1)) |
-                                       ((exponent & EXPONENT_MASK) <<
-                                        SIGNIFICAND_BITS) |
-                                       (significand & SIGNIFICAND_MASK))
-                    }
-                }
+        fn add(&self, other: &BigFloat) -> BigFloat {
+            // A number too small to reach the other's significand is as good as truncated
+            let limit = BIG_PRECISION as i32 + 2;
+            if other.is_zero() || (!self.is_zero() && self.top() > other.top() + limit) {
+                return self.clone();
+            }
+            if self.is_zero() || other.top() > self.top() + limit {
+                return other.clone();
+            }
+            let e = cmp::min(self.e, other.e);
+            let a = big_shl(&self.m, (self.e - e) as u32);
+            let b = big_shl(&other.m, (other.e - e) as u32);
+            if self.neg == other.neg {
+                BigFloat::new(self.neg, big_add_sub(&a, &b, true), e)
+            } else if big_cmp(&a, &b) == Ordering::Less {
+                BigFloat::new(other.neg, big_add_sub(&b, &a, false), e)
+            } else {
+                BigFloat::new(self.neg, big_add_sub(&a, &b, false), e)
+            }
+        }
 
-                if rng.gen_weighted_bool(10) {
-                    // Special values
-                    *rng.choose(&[-0.0,
-                                  0.0,
-                                  ::std::$fty::NAN,
-                                  ::std::$fty::INFINITY,
-                                  -::std::$fty::INFINITY])
-                        .unwrap()
-                } else if rng.gen_weighted_bool(10) {
-                    // NaN patterns
-                    mk_f32(rng.gen(), rng.gen(), 0)
-                } else if rng.gen() {
-                    // Denormalized
-                    mk_f32(rng.gen(), 0, rng.gen())
-                } else {
-                    // Random anything
-                    mk_f32(rng.gen(), rng.gen(), rng.gen())
+        fn mul(&self, other: &BigFloat) -> BigFloat {
+            BigFloat::new(self.neg != other.neg, big_mul(&self.m, &other.m), self.e + other.e)
+        }
+
+        fn div_small(&self, d: u32) -> BigFloat {
+            BigFloat::new(self.neg, big_div_small(&big_shl(&self.m, 64), d), self.e - 64)
+        }
+
+        /// An approximation, which is only good for estimates
+        fn to_f64(&self) -> f64 {
+            let shift = big_bits(&self.m).saturating_sub(64);
+            let top = big_shr(&self.m, shift)
+                .iter()
+                .rev()
+                .fold(0, |acc, &limb| acc << 32 | limb as u64);
+            let x = top as f64 * 2f64.powi(self.e + shift as i32);
+            if self.neg { -x } else { x }
+        }
+    }
+
+    fn big_exp(x: &BigFloat) -> BigFloat {
+        let (_, ln2, _) = big_constants();
+        let ln2 = BigFloat::new(false, ln2, -(BIG_CONSTANT_BITS as i32));
+        // exp(x) = 2^k*exp(r)^(2^16), with r = (x - k*ln(2))/2^16, so that the Taylor series
+        // converges quickly
+        let k = (x.to_f64() / ::std::f64::consts::LN_2).round() as i64;
+        let r = x.add(&ln2.mul(&BigFloat::from_i64(k)).neg()).scale(-16);
+        let mut sum = BigFloat::from_i64(1);
+        let mut term = sum.clone();
+        for n in 1.. {
+            term = term.mul(&r).div_small(n);
+            if term.is_zero() || term.top() < -(BIG_PRECISION as i32) - 8 {
+                break;
+            }
+            sum = sum.add(&term);
+        }
+        for _ in 0..16 {
+            sum = sum.mul(&sum);
+        }
+        sum.scale(k as i32)
+    }
+
+    fn big_ln(x: f64) -> BigFloat {
+        // Newton's iteration for `exp(y) = x`, `y' = y + x*exp(-y) - 1`, doubles the correct bits
+        // of the double precision logarithm each time
+        let minus_one = BigFloat::from_i64(-1);
+        let x_big = BigFloat::from_f64(x);
+        let mut y = BigFloat::from_f64(x.ln());
+        for _ in 0..4 {
+            y = y.add(&x_big.mul(&big_exp(&y.neg())).add(&minus_one));
+        }
+        y
+    }
+
+    /// Returns `sin(x)`, or `cos(x)` if `cosine` is set
+    fn big_sin_cos(x: f64, cosine: bool) -> BigFloat {
+        let (pi, _, two_over_pi) = big_constants();
+
+        // |x| = n*pi/2 + r with |r| <= pi/4, from the integer and the fraction parts of
+        // `|x|*2/pi = product*2^-point`
+        let (neg, m, e) = binary_parts(to_u64(x) as u128, 52, 11);
+        let product = big_mul(&big(m), &two_over_pi);
+        let point = (BIG_TWO_OVER_PI_BITS as i32 - e) as u32;
+        let mut n = big_bit(&product, point) as u32 | (big_bit(&product, point + 1) as u32) << 1;
+        let mut fraction = big_low(&product, point);
+        let round_up = big_bit(&product, point - 1);
+        if round_up {
+            n += 1;
+            fraction = big_add_sub(&big_shl(&[1], point), &fraction, false);
+        }
+        let pio2 = BigFloat::new(false, pi, -(BIG_CONSTANT_BITS as i32) - 1);
+        let r = BigFloat::new(round_up, fraction, -(point as i32)).mul(&pio2);
+        let (n, r) = if neg { (4 - n % 4, r.neg()) } else { (n, r) };
+
+        // sin(x) is sin(r), cos(r), -sin(r) or -cos(r) depending on n, and cos(x) = sin(x + pi/2)
+        let n = if cosine { n + 1 } else { n };
+        let r2 = r.mul(&r).neg();
+        let (mut term, mut k) = if n % 2 == 0 { (r, 1) } else { (BigFloat::from_i64(1), 0) };
+        let mut sum = term.clone();
+        loop {
+            term = term.mul(&r2).div_small((k + 1) * (k + 2));
+            k += 2;
+            if term.is_zero() || term.top() < sum.top() - BIG_PRECISION as i32 - 8 {
+                break;
+            }
+            sum = sum.add(&term);
+        }
+        if n % 4 >= 2 { sum.neg() } else { sum }
+    }
+
+    /// Reference `exp(a)`, `log(a)`, `pow(a, b)`, `sin(a)` or `cos(a)` (`op` 0 to 4) for the
+    /// binary format with the given field widths, of which `a` and `b` are numbers. Returns the
+    /// exact result truncated toward zero to the format, which is infinity past the largest finite
+    /// number, and the rest in ulps of it, which is zero only for special values that have to be
+    /// returned exactly
+    fn math_ulps(op: u8, a: f64, b: f64, significand_bits: u32, exponent_bits: u32) -> (u128, f64) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let sign = 1 << (sb + eb);
+
+        // Special cases, which the C standard specifies and the host library gets right
+        let special = match op {
+            0 => !a.is_finite() || a == 0.0,
+            1 => !a.is_finite() || a <= 0.0 || a == 1.0,
+            2 => {
+                !a.is_finite() || !b.is_finite() || a == 0.0 || b == 0.0 || a == 1.0 ||
+                (a < 0.0 && b.fract() != 0.0)
+            }
+            _ => !a.is_finite() || a == 0.0,
+        };
+        if special {
+            let c = match op {
+                0 => a.exp(),
+                1 => a.ln(),
+                2 => a.powf(b),
+                3 => a.sin(),
+                _ => a.cos(),
+            };
+            if c.is_nan() {
+                return (infinity | (1 << (sb - 1)), 0.0);
+            }
+            return (f128_narrow(f128_from_f64(c), sb, eb), 0.0);
+        }
+
+        // Far past the exponent range, the result is infinity or nearly zero
+        let huge = (infinity, 0.0);
+        let tiny = (0, ::std::f64::MIN_POSITIVE);
+        let v = match op {
+            0 if a > 1000.0 => return huge,
+            0 if a < -1100.0 => return tiny,
+            0 => big_exp(&BigFloat::from_f64(a)),
+            1 => big_ln(a),
+            2 => {
+                let odd = a < 0.0 && b % 2.0 != 0.0;
+                let (t, r) = if odd { (sign, -1.0) } else { (0, 1.0) };
+                let y = BigFloat::from_f64(b).mul(&big_ln(a.abs()));
+                if y.to_f64() > 1000.0 {
+                    return (t | infinity, 0.0);
+                }
+                if y.to_f64() < -1100.0 {
+                    return (t, r * tiny.1);
                 }
+                let v = big_exp(&y);
+                if odd { v.neg() } else { v }
+            }
+            _ => big_sin_cos(a, op == 4),
+        };
+
+        // Truncate `v` to the format, with the exponent of its ulp clamped at the denormals
+        let t_sign = if v.neg { sign } else { 0 };
+        if v.is_zero() {
+            return (t_sign, 0.0);
+        }
+        let bias = (1 << (eb - 1)) - 1;
+        let exponent = v.top() - 1;
+        if exponent > bias {
+            return (t_sign | infinity, 0.0);
+        }
+        let exponent = cmp::max(exponent, 1 - bias);
+        let ulp = exponent - sb as i32;
+        let abs = BigFloat { neg: false, ..v };
+        let significand = if abs.e >= ulp {
+            big_shl(&abs.m, (abs.e - ulp) as u32)
+        } else {
+            big_shr(&abs.m, (ulp - abs.e) as u32)
+        };
+        let significand = (0..4).fold(0, |acc, i| {
+            acc | (*significand.get(i).unwrap_or(&0) as u128) << (32 * i)
+        });
+        let rest = abs.add(&BigFloat::new(true, big(significand), ulp)).scale(-ulp).to_f64();
+        // The implicit bit of a normal number carries into the exponent field
+        let t = (((exponent + bias - 1) as u128) << sb) + significand;
+        if v.neg { (sign | t, -rest) } else { (t, rest) }
+    }
+
+    /// Returns `a * b mod m`, for `a, b < m < 2^126`
+    fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+        (0..128).rev().fold(0, |r, i| {
+            let r = (r << 1) % m;
+            if b >> i & 1 != 0 { (r + a) % m } else { r }
+        })
+    }
+
+    /// Reference `fmod` for the binary format with the given field widths. Returns the result,
+    /// which is the default NaN if it is a NaN, and the exceptions the operation raises
+    fn fmod_exc(a: u128, b: u128, significand_bits: u32, exponent_bits: u32) -> (u128, i32) {
+        let (sb, eb) = (significand_bits, exponent_bits);
+        let infinity = ((1 << eb) - 1) << sb;
+        let quiet_bit = 1 << (sb - 1);
+        let nan = infinity | quiet_bit;
+        let sign_bit = 1 << (sb + eb);
+        let (a_abs, b_abs) = (a & (sign_bit - 1), b & (sign_bit - 1));
+        let signaling = |x: u128| x > infinity && x & quiet_bit == 0;
+
+        if a_abs > infinity || b_abs > infinity {
+            let invalid = signaling(a_abs) || signaling(b_abs);
+            return (nan, if invalid { FE_INVALID } else { 0 });
+        }
+        if a_abs == infinity || b_abs == 0 {
+            return (nan, FE_INVALID);
+        }
+        if a_abs < b_abs {
+            return (a, 0);
+        }
+
+        // `|a| = m * 2^e` and `|b| = n * 2^f` with `e >= f`, so the remainder is
+        // `(m * 2^(e - f) mod n) * 2^f`
+        let (sign, m, e) = binary_parts(a, sb, eb);
+        let (_, n, f) = binary_parts(b, sb, eb);
+        let mut power = 1 % n;
+        let mut square = 2 % n;
+        let mut k = e - f;
+        while k != 0 {
+            if k & 1 != 0 {
+                power = mul_mod(power, square, n);
             }
+            square = mul_mod(square, square, n);
+            k >>= 1;
+        }
+        let r = mul_mod(m % n, power, n);
+        if r == 0 {
+            return ((sign as u128) << (sb + eb), 0);
         }
+        round_binary_exc(Round::ToNearest, sign, 0, r, false, f, sb, eb)
     }
 
-    gen_float!(gen_f32, f32, u32, 32, 23);
-    gen_float!(gen_f64, f64, u64, 64, 52);
+    const X87_SIGN: u128 = 1 << 79;
+    const X87_INF: u128 = 0x7fff << 64 | 1 << 63;
+    const X87_ONE: u128 = 0x3fff << 64 | 1 << 63;
 
-    pub fn gen_u128<R>(rng: &mut R) -> u128
+    /// Returns the representation of a random x87 extended precision number. Only the canonical
+    /// encodings are generated, i.e. the integer bit is set exactly for normal numbers
+    fn gen_x87<R>(rng: &mut R) -> u128
     where
         R: Rng,
     {
-        gen_i128(rng) as u128
+        let exponent = if rng.gen_weighted_bool(10) {
+            *rng.choose(&[0, 0x7fff]).unwrap()
+        } else {
+            rng.gen_range(1u32, 0x7fff) as u128
+        };
+        x87_from_fields(rng.gen(), exponent, gen_u64(rng))
     }
 
-    pub fn gen_u32<R>(rng: &mut R) -> u32
+    /// Like `gen_x87` but mostly returns numbers with an unbiased exponent in `[min, max)`
+    fn gen_x87_exponent<R>(rng: &mut R, min: i32, max: i32) -> u128
     where
         R: Rng,
     {
-        gen_i32(rng) as u32
+        if rng.gen_weighted_bool(10) {
+            gen_x87(rng)
+        } else {
+            let exponent = (rng.gen_range(min, max) + 16383) as u128;
+            x87_from_fields(rng.gen(), exponent, gen_u64(rng))
+        }
     }
 
-    fn gen_u64<R>(rng: &mut R) -> u64
-    where
-        R: Rng,
-    {
-        gen_i64(rng) as u64
+    /// Assembles a canonical x87 extended precision number; infinities get a zero fraction
+    fn x87_from_fields(sign: bool, exponent: u128, fraction: u64) -> u128 {
+        let fraction = fraction as u128 & ((1 << 63) - 1);
+        let integer_bit = (exponent != 0) as u128;
+        match exponent {
+            0x7fff => ((sign as u128) << 79) | X87_INF,
+            _ => ((sign as u128) << 79) | (exponent << 64) | (integer_bit << 63) | fraction,
+        }
     }
 
-    pub fn to_u32(x: f32) -> u32 {
-        unsafe { mem::transmute(x) }
+    fn x87_is_nan(x: u128) -> bool {
+        x & !X87_SIGN > X87_INF
     }
 
-    pub fn to_u64(x: f64) -> u64 {
-        unsafe { mem::transmute(x) }
+    /// Splits the finite x87 extended precision number `x` into its sign, a significand `m` and
+    /// an exponent `e` such that `|x| = m * 2^e`
+    fn x87_parts(x: u128) -> (bool, u128, i32) {
+        let sign = x & X87_SIGN != 0;
+        let exponent = ((x >> 64) & 0x7fff) as i32;
+        (sign, x & ((1 << 64) - 1), cmp::max(exponent, 1) - 16446)
+    }
+
+    /// Like `f128_round` but rounds to the x87 extended precision format, which has the same
+    /// exponent range and a 64-bit significand with an explicit integer bit
+    fn x87_round(sign: bool, hi: u128, lo: u128, sticky: bool, e: i32) -> u128 {
+        let r = round_binary(sign, hi, lo, sticky, e, 63, 15);
+        let exponent = (r >> 63) & 0x7fff;
+        ((r >> 78) << 79) | (exponent << 64) | ((exponent != 0) as u128) << 63 |
+            (r & ((1 << 63) - 1))
+    }
+
+    /// Returns the representation of `a` as an x87 extended precision number, rounded if necessary
+    fn x87_from_i128(a: i128) -> u128 {
+        let magnitude = if a < 0 { (a as u128).wrapping_neg() } else { a as u128 };
+        x87_round(a < 0, 0, magnitude, false, 0)
+    }
+
+    /// Returns the x87 extended precision number `x`, which must not be NaN, as a quadruple
+    /// precision number (which is exact)
+    fn x87_to_f128(x: u128) -> u128 {
+        let (sign, m, e) = x87_parts(x);
+        if x & !X87_SIGN == X87_INF {
+            ((sign as u128) << 127) | F128_INF
+        } else {
+            f128_round(sign, 0, m, false, e)
+        }
+    }
+
+    /// Reference x87 extended precision multiplication. Neither operand may be NaN; returns `None`
+    /// if the result is NaN
+    fn x87_mul(a: u128, b: u128) -> Option<u128> {
+        let sign = (a ^ b) & X87_SIGN;
+        let (_, a_m, a_e) = x87_parts(a);
+        let (_, b_m, b_e) = x87_parts(b);
+        if a & !X87_SIGN == X87_INF || b & !X87_SIGN == X87_INF {
+            return if a_m == 0 || b_m == 0 {
+                None
+            } else {
+                Some(sign | X87_INF)
+            };
+        }
+        Some(x87_round(sign != 0, 0, a_m * b_m, false, a_e + b_e))
+    }
+
+    /// Reference x87 extended precision division of `a * 2^n` by `b`. Neither operand may be NaN;
+    /// returns `None` if the result is NaN
+    fn x87_div(a: u128, b: u128, n: i32) -> Option<u128> {
+        let sign = (a ^ b) & X87_SIGN;
+        let (a_inf, b_inf) = (a & !X87_SIGN == X87_INF, b & !X87_SIGN == X87_INF);
+        let (_, a_m, a_e) = x87_parts(a);
+        let (_, b_m, b_e) = x87_parts(b);
+        if (a_inf && b_inf) || (a_m == 0 && b_m == 0) {
+            return None;
+        }
+        if a_inf || b_m == 0 {
+            return Some(sign | X87_INF);
+        }
+        if b_inf || a_m == 0 {
+            return Some(sign);
+        }
+
+        // Normalize denormals so that the quotient has at most one integer bit
+        let (a_shift, b_shift) = (a_m.leading_zeros() - 64, b_m.leading_zeros() - 64);
+        let (a_m, a_e) = (a_m << a_shift, a_e - a_shift as i32);
+        let (b_m, b_e) = (b_m << b_shift, b_e - b_shift as i32);
+
+        // Long division, 70 quotient bits past the binary point
+        let (mut q, mut r) = (a_m / b_m, a_m % b_m);
+        for _ in 0..70 {
+            r <<= 1;
+            q <<= 1;
+            if r >= b_m {
+                r -= b_m;
+                q |= 1;
+            }
+        }
+        Some(x87_round(sign != 0, 0, q, r != 0, a_e - b_e - 70 + n))
+    }
+
+    /// Reference `__powixf2`, which rounds after every multiplication. For a negative power the
+    /// products are rounded as if the exponent range had no limits, and the reciprocal once
+    fn x87_powi(a: u128, b: i32) -> Option<u128> {
+        let (mut a, mut b) = (a, b);
+        let recip = b < 0;
+        let finite = a & !X87_SIGN != 0 && a & !X87_SIGN != X87_INF;
+        let scale = |x: u128, exp: &mut i64| if recip && finite {
+            let (sign, m, e) = x87_parts(x);
+            let shift = m.leading_zeros() - 64;
+            *exp += e as i64 + 64 - shift as i64;
+            ((sign as u128) << 79) | (0x3ffe << 64) | (m << shift)
+        } else {
+            x
+        };
+        let mut r = X87_ONE;
+        let (mut r_exp, mut a_exp) = (0, 0);
+        a = scale(a, &mut a_exp);
+        loop {
+            if b & 1 != 0 {
+                r_exp += a_exp;
+                r = match x87_mul(r, a) {
+                    Some(r) => scale(r, &mut r_exp),
+                    None => return None,
+                };
+            }
+            b /= 2;
+            if b == 0 {
+                break;
+            }
+            a_exp *= 2;
+            a = match x87_mul(a, a) {
+                Some(a) => scale(a, &mut a_exp),
+                None => return None,
+            };
+        }
+        if recip {
+            x87_div(X87_ONE, r, cmp::max(cmp::min(-r_exp, 1 << 20), -(1 << 20)) as i32)
+        } else {
+            Some(r)
+        }
     }
 
     fn mk_tests<T, R>(mut n: usize, rng: &mut R) -> String
@@ -3941,7 +13702,7 @@ macro_rules! panic {
         let out_file_name = format!("{}.rs", T::name());
         let out_file = out_dir.join(&out_file_name);
         println!("Generating {}", out_file_name);
-        let contents = mk_tests::<T, _>(NTESTS, rng);
+        let contents = mk_tests::<T, _>(T::ntests(), rng);
 
         File::create(out_file)
             .unwrap()
@@ -3999,6 +13760,7 @@ mod c {
     /// Compile intrinsics from the compiler-rt C source code
     pub fn compile(llvm_target: &[&str]) {
         let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+        let target_endian = env::var("CARGO_CFG_TARGET_ENDIAN").unwrap();
         let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap();
         let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
         let target_vendor = env::var("CARGO_CFG_TARGET_VENDOR").unwrap();
@@ -4055,62 +13817,23 @@ mod c {
                 "clzdi2.c",
                 "clzsi2.c",
                 "cmpdi2.c",
-                "comparedf2.c",
-                "comparesf2.c",
                 "ctzdi2.c",
                 "ctzsi2.c",
-                "divdc3.c",
-                "divdf3.c",
-                "divsc3.c",
-                "divsf3.c",
                 "divxc3.c",
-                "extendsfdf2.c",
-                "extendhfsf2.c",
                 "ffsdi2.c",
-                "fixdfdi.c",
-                "fixdfsi.c",
-                "fixsfdi.c",
-                "fixsfsi.c",
-                "fixunsdfdi.c",
-                "fixunsdfsi.c",
-                "fixunssfdi.c",
-                "fixunssfsi.c",
-                "fixunsxfdi.c",
-                "fixunsxfsi.c",
-                "fixxfdi.c",
-                "floatdidf.c",
-                "floatdisf.c",
-                "floatdixf.c",
-                "floatsidf.c",
-                "floatsisf.c",
-                "floatundidf.c",
-                "floatundisf.c",
-                "floatundixf.c",
-                "floatunsidf.c",
-                "floatunsisf.c",
                 "int_util.c",
-                "muldc3.c",
-                "muldf3.c",
-                "mulsc3.c",
-                "mulsf3.c",
                 "mulvdi3.c",
                 "mulvsi3.c",
                 "mulxc3.c",
-                "negdf2.c",
                 "negdi2.c",
-                "negsf2.c",
                 "negvdi2.c",
                 "negvsi2.c",
                 "paritydi2.c",
                 "paritysi2.c",
                 "popcountdi2.c",
                 "popcountsi2.c",
-                "powixf2.c",
                 "subvdi3.c",
                 "subvsi3.c",
-                "truncdfhf2.c",
-                "truncdfsf2.c",
-                "truncsfhf2.c",
                 "ucmpdi2.c",
             ],
         );
@@ -4124,18 +13847,6 @@ mod c {
                     "cmpti2.c",
                     "ctzti2.c",
                     "ffsti2.c",
-                    "fixdfti.c",
-                    "fixsfti.c",
-                    "fixunsdfti.c",
-                    "fixunssfti.c",
-                    "fixunsxfti.c",
-                    "fixxfti.c",
-                    "floattidf.c",
-                    "floattisf.c",
-                    "floattixf.c",
-                    "floatuntidf.c",
-                    "floatuntisf.c",
-                    "floatuntixf.c",
                     "mulvti3.c",
                     "negti2.c",
                     "negvti2.c",
@@ -4147,6 +13858,17 @@ mod c {
             );
         }
 
+        // The Rust versions only exist where C returns `_Complex` values like a struct, see
+        // `float::complex`
+        let complex_in_rust = match &*target_arch {
+            "aarch64" | "arm" | "mips64" | "x86_64" => true,
+            "powerpc64" => target_endian == "little",
+            _ => false,
+        };
+        if !complex_in_rust {
+            sources.extend(&["divdc3.c", "divsc3.c", "muldc3.c", "mulsc3.c"]);
+        }
+
         if target_vendor == "apple" {
             sources.extend(
                 &[
@@ -4182,10 +13904,8 @@ mod c {
                         "x86_64/chkstk2.S",
                         "x86_64/floatdidf.c",
                         "x86_64/floatdisf.c",
-                        "x86_64/floatdixf.c",
                         "x86_64/floatundidf.S",
                         "x86_64/floatundisf.S",
-                        "x86_64/floatundixf.S",
                     ],
                 );
             }
@@ -4200,10 +13920,8 @@ mod c {
                         "i386/divdi3.S",
                         "i386/floatdidf.S",
                         "i386/floatdisf.S",
-                        "i386/floatdixf.S",
                         "i386/floatundidf.S",
                         "i386/floatundisf.S",
-                        "i386/floatundixf.S",
                         "i386/lshrdi3.S",
                         "i386/moddi3.S",
                         "i386/muldi3.S",
@@ -4217,20 +13935,11 @@ mod c {
         if target_arch == "arm" && target_os != "ios" {
             sources.extend(
                 &[
-                    "arm/aeabi_cdcmp.S",
-                    "arm/aeabi_cdcmpeq_check_nan.c",
-                    "arm/aeabi_cfcmp.S",
-                    "arm/aeabi_cfcmpeq_check_nan.c",
-                    "arm/aeabi_dcmp.S",
                     "arm/aeabi_div0.c",
-                    "arm/aeabi_drsub.c",
-                    "arm/aeabi_fcmp.S",
-                    "arm/aeabi_frsub.c",
                     "arm/bswapdi2.S",
                     "arm/bswapsi2.S",
                     "arm/clzdi2.S",
                     "arm/clzsi2.S",
-                    "arm/comparesf2.S",
                     "arm/divmodsi4.S",
                     "arm/divsi3.S",
                     "arm/modsi3.S",
@@ -4312,29 +14021,12 @@ mod c {
                 );
             }
 
-            sources.extend(&["arm/negdf2vfp.S", "arm/negsf2vfp.S"]);
-
         }
 
         if target_arch == "aarch64" {
             sources.extend(
                 &[
-                    "comparetf2.c",
-                    "extenddftf2.c",
-                    "extendsftf2.c",
-                    "fixtfdi.c",
-                    "fixtfsi.c",
-                    "fixtfti.c",
-                    "fixunstfdi.c",
-                    "fixunstfsi.c",
-                    "fixunstfti.c",
-                    "floatditf.c",
-                    "floatsitf.c",
-                    "floatunditf.c",
-                    "floatunsitf.c",
                     "multc3.c",
-                    "trunctfdf2.c",
-                    "trunctfsf2.c",
                 ],
             );
         }
@@ -4343,13 +14035,8 @@ mod c {
         if llvm_target[0] == "thumbv6m" {
             sources.remove(
                 &[
-                    "aeabi_cdcmp",
-                    "aeabi_cfcmp",
-                    "aeabi_dcmp",
-                    "aeabi_fcmp",
                     "clzdi2",
                     "clzsi2",
-                    "comparesf2",
                     "divmodsi4",
                     "divsi3",
                     "modsi3",
@@ -4367,10 +14054,6 @@ mod c {
             sources.extend(&["clzdi2.c", "clzsi2.c"])
         }
 
-        if llvm_target[0] == "thumbv7m" || llvm_target[0] == "thumbv7em" {
-            sources.remove(&["aeabi_cdcmp", "aeabi_cfcmp"]);
-        }
-
         let root = if env::var_os("CARGO_FEATURE_RUSTBUILD").is_some() {
             Path::new("../../libcompiler_builtins")
         } else {
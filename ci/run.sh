@@ -6,13 +6,6 @@ case $1 in
         for t in $(ls tests); do
             t=${t%.rs}
 
-            # TODO(#154) enable these tests when aeabi_*mul are implemented
-            case $t in
-                powi*f2)
-                    continue
-                    ;;
-            esac
-
             # FIXME(#150) debug assertion in divmoddi4
             case $1 in
                 thumbv6m-*)
@@ -42,6 +35,11 @@ esac
 case $1 in
     thumb*)
         xargo build --features c --target $1 --example intrinsics
+        xargo build --target $1 --example intrinsics
+        ;;
+    arm*)
+        cargo build --no-default-features --features c --target $1 --example intrinsics
+        cargo build --no-default-features --target $1 --example intrinsics
         ;;
     *)
         cargo build --no-default-features --features c --target $1 --example intrinsics
@@ -19,15 +19,11 @@
 extern crate libc;
 extern crate compiler_builtins;
 
-// NOTE cfg(not(thumbv6m)) means that the operation is not supported on ARMv6-M at all. Not even
-// compiler-rt provides a C/assembly implementation.
-
 // Every function in this module maps will be lowered to an intrinsic by LLVM, if the platform
 // doesn't have native support for the operation used in the function. ARM has a naming convention
 // convention for its intrinsics that's different from other architectures; that's why some function
 // have an additional comment: the function name is the ARM name for the intrinsic and the comment
 // in the non-ARM name for the intrinsic.
-#[cfg(feature = "c")]
 mod intrinsics {
     use core::num::Float;
 
@@ -42,68 +38,53 @@ mod intrinsics {
     }
 
     // fixdfdi
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_d2l(x: f64) -> i64 {
         x as i64
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_d2l(_: f64) -> i64 {
-        0
-    }
-
     // fixunsdfsi
     pub fn aeabi_d2uiz(x: f64) -> u32 {
         x as u32
     }
 
     // fixunsdfdi
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_d2ulz(x: f64) -> u64 {
         x as u64
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_d2ulz(_: f64) -> u64 {
-        0
-    }
-
     // adddf3
     pub fn aeabi_dadd(a: f64, b: f64) -> f64 {
         a + b
     }
 
     // eqdf2
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_dcmpeq(a: f64, b: f64) -> bool {
         a == b
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_dcmpeq(_: f64, _: f64) -> bool {
-        true
+    // gedf2
+    pub fn aeabi_dcmpge(a: f64, b: f64) -> bool {
+        a >= b
     }
 
     // gtdf2
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_dcmpgt(a: f64, b: f64) -> bool {
         a > b
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_dcmpgt(_: f64, _: f64) -> bool {
-        true
+    // ledf2
+    pub fn aeabi_dcmple(a: f64, b: f64) -> bool {
+        a <= b
     }
 
     // ltdf2
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_dcmplt(a: f64, b: f64) -> bool {
         a < b
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_dcmplt(_: f64, _: f64) -> bool {
-        true
+    // unorddf2
+    pub fn aeabi_dcmpun(a: f64, b: f64) -> bool {
+        a.is_nan() || b.is_nan()
     }
 
     // divdf3
@@ -132,68 +113,53 @@ mod intrinsics {
     }
 
     // fixsfdi
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_f2lz(x: f32) -> i64 {
         x as i64
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_f2lz(_: f32) -> i64 {
-        0
-    }
-
     // fixunssfsi
     pub fn aeabi_f2uiz(x: f32) -> u32 {
         x as u32
     }
 
     // fixunssfdi
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_f2ulz(x: f32) -> u64 {
         x as u64
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_f2ulz(_: f32) -> u64 {
-        0
-    }
-
     // addsf3
     pub fn aeabi_fadd(a: f32, b: f32) -> f32 {
         a + b
     }
 
     // eqsf2
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_fcmpeq(a: f32, b: f32) -> bool {
         a == b
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_fcmpeq(_: f32, _: f32) -> bool {
-        true
+    // gesf2
+    pub fn aeabi_fcmpge(a: f32, b: f32) -> bool {
+        a >= b
     }
 
     // gtsf2
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_fcmpgt(a: f32, b: f32) -> bool {
         a > b
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_fcmpgt(_: f32, _: f32) -> bool {
-        true
+    // lesf2
+    pub fn aeabi_fcmple(a: f32, b: f32) -> bool {
+        a <= b
     }
 
     // ltsf2
-    #[cfg(not(thumbv6m))]
     pub fn aeabi_fcmplt(a: f32, b: f32) -> bool {
         a < b
     }
 
-    #[cfg(thumbv6m)]
-    pub fn aeabi_fcmplt(_: f32, _: f32) -> bool {
-        true
+    // unordsf2
+    pub fn aeabi_fcmpun(a: f32, b: f32) -> bool {
+        a.is_nan() || b.is_nan()
     }
 
     // divsf3
@@ -339,7 +305,6 @@ mod intrinsics {
     }
 }
 
-#[cfg(feature = "c")]
 fn run() {
     use intrinsics::*;
 
@@ -356,8 +321,11 @@ fn run() {
     bb(aeabi_d2ulz(bb(2.)));
     bb(aeabi_dadd(bb(2.), bb(3.)));
     bb(aeabi_dcmpeq(bb(2.), bb(3.)));
+    bb(aeabi_dcmpge(bb(2.), bb(3.)));
     bb(aeabi_dcmpgt(bb(2.), bb(3.)));
+    bb(aeabi_dcmple(bb(2.), bb(3.)));
     bb(aeabi_dcmplt(bb(2.), bb(3.)));
+    bb(aeabi_dcmpun(bb(2.), bb(3.)));
     bb(aeabi_ddiv(bb(2.), bb(3.)));
     bb(aeabi_dmul(bb(2.), bb(3.)));
     bb(aeabi_dsub(bb(2.), bb(3.)));
@@ -368,8 +336,11 @@ fn run() {
     bb(aeabi_f2ulz(bb(2.)));
     bb(aeabi_fadd(bb(2.), bb(3.)));
     bb(aeabi_fcmpeq(bb(2.), bb(3.)));
+    bb(aeabi_fcmpge(bb(2.), bb(3.)));
     bb(aeabi_fcmpgt(bb(2.), bb(3.)));
+    bb(aeabi_fcmple(bb(2.), bb(3.)));
     bb(aeabi_fcmplt(bb(2.), bb(3.)));
+    bb(aeabi_fcmpun(bb(2.), bb(3.)));
     bb(aeabi_fdiv(bb(2.), bb(3.)));
     bb(aeabi_fmul(bb(2.), bb(3.)));
     bb(aeabi_fsub(bb(2.), bb(3.)));
@@ -404,7 +375,7 @@ fn run() {
     bb(modti3(bb(2), bb(2)));
 }
 
-#[cfg(all(feature = "c", not(thumb)))]
+#[cfg(not(thumb))]
 #[start]
 fn main(_: isize, _: *const *const u8) -> isize {
     run();
@@ -412,25 +383,13 @@ fn main(_: isize, _: *const *const u8) -> isize {
     0
 }
 
-#[cfg(all(not(feature = "c"), not(thumb)))]
-#[start]
-fn main(_: isize, _: *const *const u8) -> isize {
-    0
-}
-
-#[cfg(all(feature = "c", thumb))]
+#[cfg(thumb)]
 #[no_mangle]
 pub fn _start() -> ! {
     run();
     loop {}
 }
 
-#[cfg(all(not(feature = "c"), thumb))]
-#[no_mangle]
-pub fn _start() -> ! {
-    loop {}
-}
-
 // ARM targets need these symbols
 #[no_mangle]
 pub fn __aeabi_unwind_cpp_pr0() {}
@@ -60,6 +60,96 @@ pub unsafe fn __aeabi_ldivmod() {
     intrinsics::unreachable();
 }
 
+// NOTE The `__aeabi_c{d,f}*cmp*` functions return their result in the Z and C flags of the APSR
+// and must preserve r0-r3 (and r4-r11), so they can't be normal Rust functions either. They forward
+// their core register operands to the three-way comparison `__le{d,f}f2`, which returns `-1`, `0`
+// or `1`, and turn that into flags by comparing `result + 1` against `1`:
+//
+// - `a < b`: Z = 0, C = 0
+// - `a == b`: Z = 1, C = 1
+// - `a > b` or unordered: Z = 0, C = 1
+//
+// r4 is only pushed to keep the stack 8-byte aligned across the call.
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __aeabi_cdcmple() {
+    asm!("push {r0, r1, r2, r3, r4, lr}
+          bl __ledf2
+          adds r0, r0, #1
+          cmp r0, #1
+          pop {r0, r1, r2, r3, r4, pc}");
+    intrinsics::unreachable();
+}
+
+// NOTE Without floating point exceptions quiet and signaling NaNs compare the same way, so this is
+// the same as `__aeabi_cdcmple`
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __aeabi_cdcmpeq() {
+    asm!("push {r0, r1, r2, r3, r4, lr}
+          bl __ledf2
+          adds r0, r0, #1
+          cmp r0, #1
+          pop {r0, r1, r2, r3, r4, pc}");
+    intrinsics::unreachable();
+}
+
+// Same as `__aeabi_cdcmple` but with the operands swapped
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __aeabi_cdrcmple() {
+    asm!("push {r0, r1, r2, r3, r4, lr}
+          mov r4, r0
+          mov r0, r2
+          mov r2, r4
+          mov r4, r1
+          mov r1, r3
+          mov r3, r4
+          bl __ledf2
+          adds r0, r0, #1
+          cmp r0, #1
+          pop {r0, r1, r2, r3, r4, pc}");
+    intrinsics::unreachable();
+}
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __aeabi_cfcmple() {
+    asm!("push {r0, r1, r2, r3, r4, lr}
+          bl __lesf2
+          adds r0, r0, #1
+          cmp r0, #1
+          pop {r0, r1, r2, r3, r4, pc}");
+    intrinsics::unreachable();
+}
+
+// NOTE See `__aeabi_cdcmpeq`
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __aeabi_cfcmpeq() {
+    asm!("push {r0, r1, r2, r3, r4, lr}
+          bl __lesf2
+          adds r0, r0, #1
+          cmp r0, #1
+          pop {r0, r1, r2, r3, r4, pc}");
+    intrinsics::unreachable();
+}
+
+// Same as `__aeabi_cfcmple` but with the operands swapped
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __aeabi_cfrcmple() {
+    asm!("push {r0, r1, r2, r3, r4, lr}
+          mov r4, r0
+          mov r0, r1
+          mov r1, r4
+          bl __lesf2
+          adds r0, r0, #1
+          cmp r0, #1
+          pop {r0, r1, r2, r3, r4, pc}");
+    intrinsics::unreachable();
+}
+
 #[cfg_attr(not(test), no_mangle)]
 pub extern "aapcs" fn __aeabi_dadd(a: f64, b: f64) -> f64 {
     ::float::add::__adddf3(a, b)
@@ -80,6 +170,181 @@ pub extern "aapcs" fn __aeabi_fsub(a: f32, b: f32) -> f32 {
     ::float::sub::__subsf3(a, b)
 }
 
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dmul(a: f64, b: f64) -> f64 {
+    ::float::mul::__muldf3(a, b)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fmul(a: f32, b: f32) -> f32 {
+    ::float::mul::__mulsf3(a, b)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_ddiv(a: f64, b: f64) -> f64 {
+    ::float::div::__divdf3(a, b)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fdiv(a: f32, b: f32) -> f32 {
+    ::float::div::__divsf3(a, b)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_drsub(a: f64, b: f64) -> f64 {
+    ::float::sub::__subdf3(b, a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_frsub(a: f32, b: f32) -> f32 {
+    ::float::sub::__subsf3(b, a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dcmpeq(a: f64, b: f64) -> i32 {
+    (::float::cmp::__eqdf2(a, b) == 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dcmplt(a: f64, b: f64) -> i32 {
+    (::float::cmp::__ltdf2(a, b) < 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dcmple(a: f64, b: f64) -> i32 {
+    (::float::cmp::__ledf2(a, b) <= 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dcmpge(a: f64, b: f64) -> i32 {
+    (::float::cmp::__gedf2(a, b) >= 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dcmpgt(a: f64, b: f64) -> i32 {
+    (::float::cmp::__gtdf2(a, b) > 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_dcmpun(a: f64, b: f64) -> i32 {
+    ::float::cmp::__unorddf2(a, b)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fcmpeq(a: f32, b: f32) -> i32 {
+    (::float::cmp::__eqsf2(a, b) == 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fcmplt(a: f32, b: f32) -> i32 {
+    (::float::cmp::__ltsf2(a, b) < 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fcmple(a: f32, b: f32) -> i32 {
+    (::float::cmp::__lesf2(a, b) <= 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fcmpge(a: f32, b: f32) -> i32 {
+    (::float::cmp::__gesf2(a, b) >= 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fcmpgt(a: f32, b: f32) -> i32 {
+    (::float::cmp::__gtsf2(a, b) > 0) as i32
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_fcmpun(a: f32, b: f32) -> i32 {
+    ::float::cmp::__unordsf2(a, b)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_d2f(a: f64) -> f32 {
+    ::float::trunc::__truncdfsf2(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_d2h(a: f64) -> u16 {
+    ::float::trunc::__truncdfhf2(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_d2iz(a: f64) -> i32 {
+    ::float::conv::__fixdfsi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_d2uiz(a: f64) -> u32 {
+    ::float::conv::__fixunsdfsi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_d2lz(a: f64) -> i64 {
+    ::float::conv::__fixdfdi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_d2ulz(a: f64) -> u64 {
+    ::float::conv::__fixunsdfdi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_f2d(a: f32) -> f64 {
+    ::float::extend::__extendsfdf2(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_f2h(a: f32) -> u16 {
+    ::float::trunc::__truncsfhf2(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_f2iz(a: f32) -> i32 {
+    ::float::conv::__fixsfsi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_f2uiz(a: f32) -> u32 {
+    ::float::conv::__fixunssfsi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_f2lz(a: f32) -> i64 {
+    ::float::conv::__fixsfdi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_f2ulz(a: f32) -> u64 {
+    ::float::conv::__fixunssfdi(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_h2f(a: u16) -> f32 {
+    ::float::extend::__extendhfsf2(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_i2d(a: i32) -> f64 {
+    ::float::conv::__floatsidf(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_i2f(a: i32) -> f32 {
+    ::float::conv::__floatsisf(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_l2d(a: i64) -> f64 {
+    ::float::conv::__floatdidf(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_l2f(a: i64) -> f32 {
+    ::float::conv::__floatdisf(a)
+}
+
 #[cfg(not(all(feature = "c", target_arch = "arm", not(target_os = "ios"), not(thumbv6m))))]
 #[cfg_attr(not(test), no_mangle)]
 pub extern "aapcs" fn __aeabi_idiv(a: i32, b: i32) -> i32 {
@@ -112,12 +377,26 @@ pub extern "aapcs" fn __aeabi_uidiv(a: u32, b: u32) -> u32 {
     ::int::udiv::__udivsi3(a, b)
 }
 
-#[cfg(not(feature = "c"))]
 #[cfg_attr(not(test), no_mangle)]
-pub extern "C" fn __aeabi_ui2d(a: u32) -> f64 {
+pub extern "aapcs" fn __aeabi_ui2d(a: u32) -> f64 {
     ::float::conv::__floatunsidf(a)
 }
 
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_ui2f(a: u32) -> f32 {
+    ::float::conv::__floatunsisf(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_ul2d(a: u64) -> f64 {
+    ::float::conv::__floatundidf(a)
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "aapcs" fn __aeabi_ul2f(a: u64) -> f32 {
+    ::float::conv::__floatundisf(a)
+}
+
 // TODO: These aeabi_* functions should be defined as aliases
 #[cfg(not(feature = "mem"))]
 extern "C" {
@@ -1,7 +1,11 @@
 use core::mem;
 use core::num::Wrapping;
 
-use float::Float;
+use float::{f128, Float};
+use float::exception;
+use float::flush::{self, Flush};
+use float::nan;
+use float::round::{self, Round};
 
 macro_rules! add {
     ($abi:tt, $intrinsic:ident: $ty:ty) => {
@@ -26,6 +30,12 @@ macro_rules! add {
             let quiet_bit =        implicit_bit >> 1;
             let qnan_rep =         exponent_mask | quiet_bit;
 
+            let mode = round::mode();
+
+            // Subnormal operands are zeros in the flush to zero mode
+            let ftz = flush::enabled();
+            let (a, b) = if ftz { (a.flush(), b.flush()) } else { (a, b) };
+
             let mut a_rep = Wrapping(a.repr());
             let mut b_rep = Wrapping(b.repr());
             let a_abs = a_rep & abs_mask;
@@ -34,18 +44,27 @@ macro_rules! add {
             // Detect if a or b is zero, infinity, or NaN.
             if a_abs - one >= inf_rep - one ||
                 b_abs - one >= inf_rep - one {
+                // Signaling NaN operands are invalid
+                if (a_abs > inf_rep && (a_abs & quiet_bit) == zero) ||
+                    (b_abs > inf_rep && (b_abs & quiet_bit) == zero) {
+                    exception::raise(exception::INVALID);
+                }
+
                 // NaN + anything = qNaN
                 if a_abs > inf_rep {
-                    return (<$ty as Float>::from_repr((a_abs | quiet_bit).0));
+                    let nan_rep = nan::propagate(a_rep | quiet_bit, qnan_rep);
+                    return (<$ty as Float>::from_repr(nan_rep.0));
                 }
                 // anything + NaN = qNaN
                 if b_abs > inf_rep {
-                    return (<$ty as Float>::from_repr((b_abs | quiet_bit).0));
+                    let nan_rep = nan::propagate(b_rep | quiet_bit, qnan_rep);
+                    return (<$ty as Float>::from_repr(nan_rep.0));
                 }
 
                 if a_abs == inf_rep {
                     // +/-infinity + -/+infinity = qNaN
                     if (a.repr() ^ b.repr()) == sign_bit.0 {
+                        exception::raise(exception::INVALID);
                         return (<$ty as Float>::from_repr(qnan_rep.0));
                     } else {
                         // +/-infinity + anything remaining = +/- infinity
@@ -60,8 +79,12 @@ macro_rules! add {
 
                 // zero + anything = anything
                 if a_abs.0 == 0 {
-                    // but we need to get the sign right for zero + zero
+                    // but we need to get the sign right for zero + zero: it is only negative if
+                    // both are, except when rounding downward where it is only positive if both are
                     if b_abs.0 == 0 {
+                        if mode == Round::Downward {
+                            return (<$ty as Float>::from_repr(a.repr() | b.repr()));
+                        }
                         return (<$ty as Float>::from_repr(a.repr() & b.repr()));
                     } else {
                         return b;
@@ -122,8 +145,11 @@ macro_rules! add {
             }
             if subtraction {
                 a_significand -= b_significand;
-                // If a == -b, return +zero.
+                // If a == -b, return +zero (-zero when rounding downward).
                 if a_significand.0 == 0 {
+                    if mode == Round::Downward {
+                        return (<$ty as Float>::from_repr(sign_bit.0));
+                    }
                     return (<$ty as Float>::from_repr(0));
                 }
 
@@ -147,12 +173,22 @@ macro_rules! add {
                 }
             }
 
-            // If we have overflowed the type, return +/- infinity:
+            // If we have overflowed the type, return +/- infinity, or the largest finite number
+            // if the rounding direction is toward zero for this sign:
             if a_exponent >= Wrapping(max_exponent.0 as i32) {
-                return (<$ty>::from_repr((inf_rep | result_sign).0));
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+                if mode.overflows_to_infinity(result_sign.0 != zero.0) {
+                    return (<$ty>::from_repr((inf_rep | result_sign).0));
+                }
+                return (<$ty>::from_repr((inf_rep - one | result_sign).0));
             }
 
             if a_exponent.0 <= 0 {
+                if ftz {
+                    exception::raise(exception::UNDERFLOW);
+                    return (<$ty>::from_repr(result_sign.0));
+                }
+
                 // Result is denormal before rounding; the exponent is zero and we
                 // need to shift the significand.
                 let shift = Wrapping((Wrapping(1) - a_exponent).0 as <$ty as Float>::Int);
@@ -172,9 +208,22 @@ macro_rules! add {
             result |= result_sign;
 
             // Final rounding.  The result may overflow to infinity, but that is the
-            // correct result in that case.
-            if round_guard_sticky > 0x4 { result += one; }
-            if round_guard_sticky == 0x4 { result += result & one; }
+            // correct result in that case: only rounding away from zero increments.
+            if mode.increment(result_sign.0 != zero.0,
+                              (result & one).0 != 0,
+                              round_guard_sticky & 0x4 != 0,
+                              round_guard_sticky & 0x3 != 0) {
+                result += one;
+            }
+
+            // A denormal sum is always exact, so addition never underflows.
+            if round_guard_sticky != 0 {
+                if result & abs_mask == inf_rep {
+                    exception::raise(exception::OVERFLOW | exception::INEXACT);
+                } else {
+                    exception::raise(exception::INEXACT);
+                }
+            }
 
             <$ty>::from_repr(result.0)
         }
@@ -192,3 +241,9 @@ add!("aapcs", __adddf3: f64);
 
 #[cfg(not(target_arch = "arm"))]
 add!("C", __adddf3: f64);
+
+#[cfg(target_arch = "arm")]
+add!("aapcs", __addtf3: f128);
+
+#[cfg(not(target_arch = "arm"))]
+add!("C", __addtf3: f128);
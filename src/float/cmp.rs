@@ -0,0 +1,186 @@
+use float::{f128, Float};
+
+#[derive(Clone, Copy)]
+enum Result {
+    Less,
+    Equal,
+    Greater,
+    Unordered,
+}
+
+impl Result {
+    /// Encoding used by `__le*f2` and its aliases: unordered compares as "greater"
+    fn to_le_abi(self) -> i32 {
+        match self {
+            Result::Less => -1,
+            Result::Equal => 0,
+            Result::Greater => 1,
+            Result::Unordered => 1,
+        }
+    }
+
+    /// Encoding used by `__ge*f2` and its aliases: unordered compares as "less"
+    fn to_ge_abi(self) -> i32 {
+        match self {
+            Result::Less => -1,
+            Result::Equal => 0,
+            Result::Greater => 1,
+            Result::Unordered => -1,
+        }
+    }
+
+    /// Encoding used by `__unord*f2`
+    fn to_unord_abi(self) -> i32 {
+        match self {
+            Result::Unordered => 1,
+            _ => 0,
+        }
+    }
+}
+
+macro_rules! cmp {
+    ($cmp:ident: $fty:ty, $sty:ty) => {
+        fn $cmp(a: $fty, b: $fty) -> Result {
+            let sign_bit = <$fty as Float>::sign_mask();
+            let abs_mask = sign_bit - 1;
+            let inf_rep = <$fty as Float>::exponent_mask();
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let a_abs = a_rep & abs_mask;
+            let b_abs = b_rep & abs_mask;
+
+            // If either a or b is NaN, they are unordered.
+            if a_abs > inf_rep || b_abs > inf_rep {
+                return Result::Unordered;
+            }
+
+            // If a and b are both zeros, they are equal.
+            if (a_abs | b_abs) == 0 {
+                return Result::Equal;
+            }
+
+            let a_srep = a_rep as $sty;
+            let b_srep = b_rep as $sty;
+
+            // If at least one of a and b is positive, we get the same result comparing
+            // a and b as signed integers as we would with a floating-point compare.
+            if (a_srep & b_srep) >= 0 {
+                if a_srep < b_srep {
+                    Result::Less
+                } else if a_srep == b_srep {
+                    Result::Equal
+                } else {
+                    Result::Greater
+                }
+            } else {
+                // Otherwise, both are negative, so we need to flip the sense of the
+                // comparison to get the correct result.  (This assumes a twos- or ones-
+                // complement integer representation; if integers are represented in a
+                // sign-magnitude representation, then this flip is incorrect).
+                if a_srep > b_srep {
+                    Result::Less
+                } else if a_srep == b_srep {
+                    Result::Equal
+                } else {
+                    Result::Greater
+                }
+            }
+        }
+    }
+}
+
+// NOTE(aapcs) compiler-rt gives every builtin the base AAPCS calling convention on ARM (see
+// `COMPILER_RT_ABI`), even on hard float targets. The flag-setting `__aeabi_c{d,f}*cmp*` routines
+// in `arm.rs` also rely on this to forward their core register operands to `__le{d,f}f2`
+macro_rules! cmp_intrinsic {
+    ($(#[$attr:meta])*
+     | $intrinsic:ident: $fty:ty => $cmp:ident, $encode:ident) => {
+        $(#[$attr])*
+        #[cfg(target_arch = "arm")]
+        #[cfg_attr(not(test), no_mangle)]
+        pub extern "aapcs" fn $intrinsic(a: $fty, b: $fty) -> i32 {
+            $cmp(a, b).$encode()
+        }
+
+        $(#[$attr])*
+        #[cfg(not(target_arch = "arm"))]
+        #[cfg_attr(not(test), no_mangle)]
+        pub extern "C" fn $intrinsic(a: $fty, b: $fty) -> i32 {
+            $cmp(a, b).$encode()
+        }
+    }
+}
+
+cmp!(cmpsf: f32, i32);
+cmp!(cmpdf: f64, i64);
+cmp!(cmptf: f128, i128);
+
+cmp_intrinsic!(/// Returns `-1` if `a < b`, `0` if `a == b` and `1` if `a > b` or either operand is NaN
+               | __lesf2: f32 => cmpsf, to_le_abi);
+
+cmp_intrinsic!(/// Returns `-1` if `a < b` or either operand is NaN, `0` if `a == b` and `1` if `a > b`
+               | __gesf2: f32 => cmpsf, to_ge_abi);
+
+cmp_intrinsic!(/// Returns a nonzero value if either `a` or `b` is NaN
+               | __unordsf2: f32 => cmpsf, to_unord_abi);
+
+cmp_intrinsic!(/// Returns `0` if `a == b`; a nonzero value otherwise or if either operand is NaN
+               | __eqsf2: f32 => cmpsf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a negative value if `a < b`; a non-negative value otherwise or if either
+               /// operand is NaN
+               | __ltsf2: f32 => cmpsf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a nonzero value if `a != b` or either operand is NaN; `0` otherwise
+               | __nesf2: f32 => cmpsf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a positive value if `a > b`; a non-positive value otherwise or if either
+               /// operand is NaN
+               | __gtsf2: f32 => cmpsf, to_ge_abi);
+
+cmp_intrinsic!(/// Returns `-1` if `a < b`, `0` if `a == b` and `1` if `a > b` or either operand is NaN
+               | __ledf2: f64 => cmpdf, to_le_abi);
+
+cmp_intrinsic!(/// Returns `-1` if `a < b` or either operand is NaN, `0` if `a == b` and `1` if `a > b`
+               | __gedf2: f64 => cmpdf, to_ge_abi);
+
+cmp_intrinsic!(/// Returns a nonzero value if either `a` or `b` is NaN
+               | __unorddf2: f64 => cmpdf, to_unord_abi);
+
+cmp_intrinsic!(/// Returns `0` if `a == b`; a nonzero value otherwise or if either operand is NaN
+               | __eqdf2: f64 => cmpdf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a negative value if `a < b`; a non-negative value otherwise or if either
+               /// operand is NaN
+               | __ltdf2: f64 => cmpdf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a nonzero value if `a != b` or either operand is NaN; `0` otherwise
+               | __nedf2: f64 => cmpdf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a positive value if `a > b`; a non-positive value otherwise or if either
+               /// operand is NaN
+               | __gtdf2: f64 => cmpdf, to_ge_abi);
+
+cmp_intrinsic!(/// Returns `-1` if `a < b`, `0` if `a == b` and `1` if `a > b` or either operand is NaN
+               | __letf2: f128 => cmptf, to_le_abi);
+
+cmp_intrinsic!(/// Returns `-1` if `a < b` or either operand is NaN, `0` if `a == b` and `1` if `a > b`
+               | __getf2: f128 => cmptf, to_ge_abi);
+
+cmp_intrinsic!(/// Returns a nonzero value if either `a` or `b` is NaN
+               | __unordtf2: f128 => cmptf, to_unord_abi);
+
+cmp_intrinsic!(/// Returns `0` if `a == b`; a nonzero value otherwise or if either operand is NaN
+               | __eqtf2: f128 => cmptf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a negative value if `a < b`; a non-negative value otherwise or if either
+               /// operand is NaN
+               | __lttf2: f128 => cmptf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a nonzero value if `a != b` or either operand is NaN; `0` otherwise
+               | __netf2: f128 => cmptf, to_le_abi);
+
+cmp_intrinsic!(/// Returns a positive value if `a > b`; a non-positive value otherwise or if either
+               /// operand is NaN
+               | __gttf2: f128 => cmptf, to_ge_abi);
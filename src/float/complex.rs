@@ -0,0 +1,233 @@
+use core::ops::{Add, Div, Mul, Sub};
+
+use float::Float;
+use float::nan::{self, Nan};
+use float::scale::{ilogb, ilogbf, scalbn, scalbnf};
+
+/// A complex number, laid out like the C99 `_Complex` types
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct Complex<F> {
+    pub re: F,
+    pub im: F,
+}
+
+/// What the Annex G algorithms need from the parts of a complex number, on top of `Float`
+trait Part
+    : Float
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn infinity() -> Self;
+    fn default_nan() -> Self;
+    fn is_nan(self) -> bool;
+    fn is_infinite(self) -> bool;
+    fn is_finite(self) -> bool;
+    fn abs(self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    /// Returns the unbiased exponent of `self` as `logb` would, or `None` if `logb` isn't finite
+    /// (zero, infinity and NaN)
+    fn ilogb(self) -> Option<i32>;
+    /// Returns `self * 2^n`, rounded once
+    fn scalbn(self, n: i32) -> Self;
+}
+
+macro_rules! part {
+    ($fty:ty, $ilogb:ident, $scalbn:ident) => {
+        impl Part for $fty {
+            fn zero() -> Self {
+                0.0
+            }
+            fn one() -> Self {
+                1.0
+            }
+            fn infinity() -> Self {
+                Self::from_repr(Self::exponent_mask())
+            }
+            fn default_nan() -> Self {
+                Self::from_repr(Self::exponent_mask() | Self::implicit_bit() >> 1)
+            }
+            fn is_nan(self) -> bool {
+                self.repr() & !Self::sign_mask() > Self::exponent_mask()
+            }
+            fn is_infinite(self) -> bool {
+                self.repr() & !Self::sign_mask() == Self::exponent_mask()
+            }
+            fn is_finite(self) -> bool {
+                self.repr() & Self::exponent_mask() != Self::exponent_mask()
+            }
+            fn abs(self) -> Self {
+                Self::from_repr(self.repr() & !Self::sign_mask())
+            }
+            fn copysign(self, sign: Self) -> Self {
+                let sign_mask = Self::sign_mask();
+                Self::from_repr((self.repr() & !sign_mask) | (sign.repr() & sign_mask))
+            }
+            fn ilogb(self) -> Option<i32> {
+                if self.is_finite() && self.repr() & !Self::sign_mask() != 0 {
+                    Some($ilogb(self))
+                } else {
+                    None
+                }
+            }
+            fn scalbn(self, n: i32) -> Self {
+                $scalbn(self, n)
+            }
+        }
+    }
+}
+
+part!(f32, ilogbf, scalbnf);
+part!(f64, ilogb, scalbn);
+
+/// Returns `1` for infinities and `0` otherwise, with the sign of `x`
+fn boxed<F: Part>(x: F) -> F {
+    (if x.is_infinite() { F::one() } else { F::zero() }).copysign(x)
+}
+
+/// Returns `0` with the sign of `x` if `x` is NaN, and `x` otherwise
+fn nan_to_zero<F: Part>(x: F) -> F {
+    if x.is_nan() { F::zero().copysign(x) } else { x }
+}
+
+/// Returns the default NaN in place of a NaN `x` if the NaN mode asks for it. The parts are
+/// computed with the target's arithmetic, which may propagate NaNs its own way
+fn canonical<F: Part>(x: F) -> F {
+    if x.is_nan() && nan::mode() == Nan::Default { F::default_nan() } else { x }
+}
+
+/// `(a + ib) * (c + id)`, recovering infinities from NaN results as C11 Annex G (G.5.1) requires
+fn mul<F: Part>(a: F, b: F, c: F, d: F) -> Complex<F> {
+    let (mut a, mut b, mut c, mut d) = (a, b, c, d);
+    let ac = a * c;
+    let bd = b * d;
+    let ad = a * d;
+    let bc = b * c;
+    let mut re = ac - bd;
+    let mut im = ad + bc;
+
+    if re.is_nan() && im.is_nan() {
+        let mut recalc = false;
+        if a.is_infinite() || b.is_infinite() {
+            a = boxed(a);
+            b = boxed(b);
+            c = nan_to_zero(c);
+            d = nan_to_zero(d);
+            recalc = true;
+        }
+        if c.is_infinite() || d.is_infinite() {
+            c = boxed(c);
+            d = boxed(d);
+            a = nan_to_zero(a);
+            b = nan_to_zero(b);
+            recalc = true;
+        }
+        if !recalc && (ac.is_infinite() || bd.is_infinite() || ad.is_infinite() ||
+                       bc.is_infinite()) {
+            // Overflow in the partial products
+            a = nan_to_zero(a);
+            b = nan_to_zero(b);
+            c = nan_to_zero(c);
+            d = nan_to_zero(d);
+            recalc = true;
+        }
+        if recalc {
+            re = F::infinity() * (a * c - b * d);
+            im = F::infinity() * (a * d + b * c);
+        }
+    }
+
+    Complex { re: canonical(re), im: canonical(im) }
+}
+
+/// `(a + ib) / (c + id)`, scaling the divisor by a power of two to avoid spurious overflow and
+/// underflow, and recovering infinities and zeros from NaN results as C11 Annex G (G.5.1) requires
+fn div<F: Part>(a: F, b: F, c: F, d: F) -> Complex<F> {
+    let (mut a, mut b, mut c, mut d) = (a, b, c, d);
+
+    // `fmax(|c|, |d|)`, which ignores NaNs
+    let max = if c.is_nan() || (!d.is_nan() && c.abs() < d.abs()) {
+        d.abs()
+    } else {
+        c.abs()
+    };
+    let mut ilogbw = 0;
+    if let Some(logbw) = max.ilogb() {
+        ilogbw = logbw;
+        c = c.scalbn(-ilogbw);
+        d = d.scalbn(-ilogbw);
+    }
+
+    let denom = c * c + d * d;
+    let mut re = ((a * c + b * d) / denom).scalbn(-ilogbw);
+    let mut im = ((b * c - a * d) / denom).scalbn(-ilogbw);
+
+    if re.is_nan() && im.is_nan() {
+        if denom == F::zero() && (!a.is_nan() || !b.is_nan()) {
+            re = F::infinity().copysign(c) * a;
+            im = F::infinity().copysign(c) * b;
+        } else if (a.is_infinite() || b.is_infinite()) && c.is_finite() && d.is_finite() {
+            a = boxed(a);
+            b = boxed(b);
+            re = F::infinity() * (a * c + b * d);
+            im = F::infinity() * (b * c - a * d);
+        } else if max.is_infinite() && a.is_finite() && b.is_finite() {
+            c = boxed(c);
+            d = boxed(d);
+            re = F::zero() * (a * c + b * d);
+            im = F::zero() * (b * c - a * d);
+        }
+    }
+
+    Complex { re: canonical(re), im: canonical(im) }
+}
+
+// NOTE(abi) C returns `_Complex float` and `_Complex double` values like the `#[repr(C)]` struct
+// `Complex` only on some targets: in registers on x86_64, AArch64, hard float ARM, MIPS64 (n64) and
+// little endian PowerPC64 (ELFv2), and through a hidden pointer on soft float ARM. Elsewhere, for
+// instance on x86, MIPS (o32), PowerPC and big endian PowerPC64 (ELFv1), C returns them in
+// registers where the struct goes through memory, so those targets keep using compiler-rt
+
+intrinsic!(/// Returns the product of the single precision complex numbers `a + ib` and `c + id`
+           #[cfg(any(target_arch = "aarch64",
+                     target_arch = "arm",
+                     target_arch = "mips64",
+                     all(target_arch = "powerpc64", target_endian = "little"),
+                     target_arch = "x86_64"))]
+           | __mulsc3(a: f32, b: f32, c: f32, d: f32) -> Complex<f32> {
+               mul(a, b, c, d)
+           });
+
+intrinsic!(/// Returns the product of the double precision complex numbers `a + ib` and `c + id`
+           #[cfg(any(target_arch = "aarch64",
+                     target_arch = "arm",
+                     target_arch = "mips64",
+                     all(target_arch = "powerpc64", target_endian = "little"),
+                     target_arch = "x86_64"))]
+           | __muldc3(a: f64, b: f64, c: f64, d: f64) -> Complex<f64> {
+               mul(a, b, c, d)
+           });
+
+intrinsic!(/// Returns the quotient of the single precision complex numbers `a + ib` and `c + id`
+           #[cfg(any(target_arch = "aarch64",
+                     target_arch = "arm",
+                     target_arch = "mips64",
+                     all(target_arch = "powerpc64", target_endian = "little"),
+                     target_arch = "x86_64"))]
+           | __divsc3(a: f32, b: f32, c: f32, d: f32) -> Complex<f32> {
+               div(a, b, c, d)
+           });
+
+intrinsic!(/// Returns the quotient of the double precision complex numbers `a + ib` and `c + id`
+           #[cfg(any(target_arch = "aarch64",
+                     target_arch = "arm",
+                     target_arch = "mips64",
+                     all(target_arch = "powerpc64", target_endian = "little"),
+                     target_arch = "x86_64"))]
+           | __divdc3(a: f64, b: f64, c: f64, d: f64) -> Complex<f64> {
+               div(a, b, c, d)
+           });
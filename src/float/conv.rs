@@ -1,4 +1,7 @@
-use float::Float;
+use float::{f128, Float};
+use float::exception;
+use float::flush;
+use float::round;
 use int::Int;
 
 macro_rules! fp_overflow {
@@ -21,7 +24,7 @@ macro_rules! int_to_float {
     #[no_mangle]
     pub extern $abi fn $intrinsic(i: $ity) -> $fty {
         if i == 0 {
-            return 0.0
+            return <$fty>::from_repr(0)
         }
 
         let mant_dig = <$fty>::significand_bits() + 1;
@@ -64,14 +67,22 @@ macro_rules! int_to_float {
             };
 
             /* finish: */
-            a |= ((a & 4) != 0) as <$ity as Int>::UnsignedInt; /* Or P into R */
-            a += 1; /* round - this step may add a significant bit */
+            let round = round::mode().increment(s, (a & 4) != 0, (a & 2) != 0, (a & 1) != 0);
+            let inexact = (a & 3) != 0;
             a >>= 2; /* dump Q and R */
+            a += round as <$ity as Int>::UnsignedInt; /* round - this step may add a significant bit */
 
             /* a is now rounded to mant_dig or mant_dig+1 bits */
             if (a & (1 << mant_dig)) != 0 {
                 a >>= 1; e += 1;
             }
+
+            /* only u128 -> f32 can overflow, by rounding up to 2^128 */
+            if e + exponent_bias >= <$fty>::exponent_max() {
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+            } else if inexact {
+                exception::raise(exception::INEXACT);
+            }
             a
             /* a is now rounded to mant_dig bits */
         } else {
@@ -97,6 +108,7 @@ macro_rules! int_to_float_unadj_on_win {
 
 int_to_float!(__floatsisf: i32, f32);
 int_to_float!(__floatsidf: i32, f64);
+int_to_float!(__floatdisf: i64, f32);
 int_to_float!(__floatdidf: i64, f64);
 int_to_float_unadj_on_win!(__floattisf: i128, f32);
 int_to_float_unadj_on_win!(__floattidf: i128, f64);
@@ -106,6 +118,12 @@ int_to_float!(__floatundidf: u64, f64);
 int_to_float!(__floatundisf: u64, f32);
 int_to_float_unadj_on_win!(__floatuntisf: u128, f32);
 int_to_float_unadj_on_win!(__floatuntidf: u128, f64);
+int_to_float!(__floatsitf: i32, f128);
+int_to_float!(__floatditf: i64, f128);
+int_to_float_unadj_on_win!(__floattitf: i128, f128);
+int_to_float!(__floatunsitf: u32, f128);
+int_to_float!(__floatunditf: u64, f128);
+int_to_float_unadj_on_win!(__floatuntitf: u128, f128);
 
 #[derive(PartialEq, Debug)]
 enum Sign {
@@ -114,10 +132,14 @@ enum Sign {
 }
 
 macro_rules! float_to_int {
+    (saturating $intrinsic:ident: $fty:ty, $ity:ty) => {
+        float_to_int!($intrinsic: $fty, $ity, "C", true);
+    };
     ($intrinsic:ident: $fty:ty, $ity:ty) => {
-        float_to_int!($intrinsic: $fty, $ity, "C");
+        float_to_int!($intrinsic: $fty, $ity, "C", false);
     };
-    ($intrinsic:ident: $fty:ty, $ity:ty, $abi:tt) => {
+    ($intrinsic:ident: $fty:ty, $ity:ty, $abi:tt, $saturating:expr) => {
+        #[cfg_attr(not(test), no_mangle)]
         pub extern $abi fn $intrinsic(f: $fty) -> $ity {
             let fixint_min = <$ity>::min_value();
             let fixint_max = <$ity>::max_value();
@@ -138,22 +160,57 @@ macro_rules! float_to_int {
             let mut exponent = (a_abs >> significand_bits) as usize;
             let significand = (a_abs & <$fty>::significand_mask()) | <$fty>::implicit_bit();
 
-            // if < 1 or unsigned & negative
-            if  exponent < exponent_bias ||
-                fixint_unsigned && sign == Sign::Negative {
+            // NaN is invalid. compiler-rt saturates it like an infinity of the same sign, while
+            // Rust's `as` defines it to be 0.
+            if a_abs > <$fty>::exponent_mask() {
+                exception::raise(exception::INVALID);
+                return if $saturating || fixint_unsigned && sign == Sign::Negative {
+                    0
+                } else if sign == Sign::Positive {
+                    fixint_max
+                } else {
+                    fixint_min
+                }
+            }
+
+            // if < 1, truncating any fraction is inexact, except that subnormals are zeros in the
+            // flush to zero mode
+            if exponent < exponent_bias {
+                if a_abs != 0 && (exponent != 0 || !flush::enabled()) {
+                    exception::raise(exception::INEXACT);
+                }
+                return 0
+            }
+            // if unsigned & negative (-1 or below), the value is out of range
+            if fixint_unsigned && sign == Sign::Negative {
+                exception::raise(exception::INVALID);
                 return 0
             }
             exponent -= exponent_bias;
 
             // If the value is infinity, saturate.
             // If the value is too large for the integer type, 0.
+            // Both are invalid, except for values that truncate to the minimum of a signed type.
             if exponent >= (if fixint_unsigned {fixint_bits} else {fixint_bits -1}) {
+                let fraction_bits = significand_bits.saturating_sub(exponent);
+                let fraction = a_abs & ((1 << fraction_bits) - 1);
+                let integer = (a_abs & <$fty>::significand_mask()) >> fraction_bits;
+                if sign == Sign::Negative && exponent == fixint_bits - 1 && integer == 0 {
+                    if fraction != 0 {
+                        exception::raise(exception::INEXACT);
+                    }
+                } else {
+                    exception::raise(exception::INVALID);
+                }
                 return if sign == Sign::Positive {fixint_max} else {fixint_min}
             }
             // If 0 <= exponent < significand_bits, right shift to get the result.
             // Otherwise, shift left.
             // (sign - 1) will never overflow as negative signs are already returned as 0 for unsigned
             let r = if exponent < significand_bits {
+                if significand << (<$fty>::bits() as usize - (significand_bits - exponent)) != 0 {
+                    exception::raise(exception::INEXACT);
+                }
                 (significand >> (significand_bits - exponent)) as $ity
             } else {
                 (significand as $ity) << (exponent - significand_bits)
@@ -169,11 +226,17 @@ macro_rules! float_to_int {
 }
 
 macro_rules! float_to_int_unadj_on_win {
+    (saturating $intrinsic:ident: $fty:ty, $ity:ty) => {
+        #[cfg(all(windows, target_pointer_width="64"))]
+        float_to_int!($intrinsic: $fty, $ity, "unadjusted", true);
+        #[cfg(not(all(windows, target_pointer_width="64")))]
+        float_to_int!($intrinsic: $fty, $ity, "C", true);
+    };
     ($intrinsic:ident: $fty:ty, $ity:ty) => {
         #[cfg(all(windows, target_pointer_width="64"))]
-        float_to_int!($intrinsic: $fty, $ity, "unadjusted");
+        float_to_int!($intrinsic: $fty, $ity, "unadjusted", false);
         #[cfg(not(all(windows, target_pointer_width="64")))]
-        float_to_int!($intrinsic: $fty, $ity, "C");
+        float_to_int!($intrinsic: $fty, $ity, "C", false);
     };
 }
 
@@ -183,6 +246,9 @@ float_to_int_unadj_on_win!(__fixsfti: f32, i128);
 float_to_int!(__fixdfsi: f64, i32);
 float_to_int!(__fixdfdi: f64, i64);
 float_to_int_unadj_on_win!(__fixdfti: f64, i128);
+float_to_int!(__fixtfsi: f128, i32);
+float_to_int!(__fixtfdi: f128, i64);
+float_to_int_unadj_on_win!(__fixtfti: f128, i128);
 
 float_to_int!(__fixunssfsi: f32, u32);
 float_to_int!(__fixunssfdi: f32, u64);
@@ -190,3 +256,29 @@ float_to_int_unadj_on_win!(__fixunssfti: f32, u128);
 float_to_int!(__fixunsdfsi: f64, u32);
 float_to_int!(__fixunsdfdi: f64, u64);
 float_to_int_unadj_on_win!(__fixunsdfti: f64, u128);
+float_to_int!(__fixunstfsi: f128, u32);
+float_to_int!(__fixunstfdi: f128, u64);
+float_to_int_unadj_on_win!(__fixunstfti: f128, u128);
+
+// The saturating conversions have the semantics of Rust's `as` casts: NaN converts to 0 and any
+// other value out of range clamps to the nearest bound. Soft float targets can lower `as` to
+// these instead of the `__fix*` set, which follow compiler-rt and saturate NaN by its sign.
+float_to_int!(saturating __rust_fixsfsi_sat: f32, i32);
+float_to_int!(saturating __rust_fixsfdi_sat: f32, i64);
+float_to_int_unadj_on_win!(saturating __rust_fixsfti_sat: f32, i128);
+float_to_int!(saturating __rust_fixdfsi_sat: f64, i32);
+float_to_int!(saturating __rust_fixdfdi_sat: f64, i64);
+float_to_int_unadj_on_win!(saturating __rust_fixdfti_sat: f64, i128);
+float_to_int!(saturating __rust_fixtfsi_sat: f128, i32);
+float_to_int!(saturating __rust_fixtfdi_sat: f128, i64);
+float_to_int_unadj_on_win!(saturating __rust_fixtfti_sat: f128, i128);
+
+float_to_int!(saturating __rust_fixunssfsi_sat: f32, u32);
+float_to_int!(saturating __rust_fixunssfdi_sat: f32, u64);
+float_to_int_unadj_on_win!(saturating __rust_fixunssfti_sat: f32, u128);
+float_to_int!(saturating __rust_fixunsdfsi_sat: f64, u32);
+float_to_int!(saturating __rust_fixunsdfdi_sat: f64, u64);
+float_to_int_unadj_on_win!(saturating __rust_fixunsdfti_sat: f64, u128);
+float_to_int!(saturating __rust_fixunstfsi_sat: f128, u32);
+float_to_int!(saturating __rust_fixunstfdi_sat: f128, u64);
+float_to_int_unadj_on_win!(saturating __rust_fixunstfti_sat: f128, u128);
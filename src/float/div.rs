@@ -1,128 +1,262 @@
-use core::num::Wrapping;
-
-use float::Float;
+use float::{f128, Float};
+use float::exception;
+use float::flush::{self, Flush};
+use float::nan;
+use float::round;
 
 macro_rules! div {
     ($abi:tt, $intrinsic:ident: $ty:ty) => {
-        /// Returns `a * b`
-        #[allow(unused_parens)]
+        /// Returns `a / b`
         #[cfg_attr(not(test), no_mangle)]
         pub extern $abi fn $intrinsic(a: $ty, b: $ty) -> $ty {
-            let one = Wrapping(1 as <$ty as Float>::Int);
-            let zero = Wrapping(0 as <$ty as Float>::Int);
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
 
-            let bits =             Wrapping(<$ty>::bits() as <$ty as Float>::Int);
-            let significand_bits = Wrapping(<$ty>::significand_bits() as <$ty as Float>::Int);
-            let exponent_bits =    bits - significand_bits - one;
-            let max_exponent =     (one << exponent_bits.0 as usize) - one;
+            let significand_bits = <$ty>::significand_bits();
+            let max_exponent =     <$ty>::exponent_max();
+            let exponent_bias =    <$ty>::exponent_bias();
 
-            let implicit_bit =     one << significand_bits.0 as usize;
-            let significand_mask = implicit_bit - one;
-            let sign_bit =         one << (significand_bits + exponent_bits).0 as usize;
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
             let abs_mask =         sign_bit - one;
-            let exponent_mask =    abs_mask ^ significand_mask;
+            let exponent_mask =    <$ty>::exponent_mask();
             let inf_rep =          exponent_mask;
             let quiet_bit =        implicit_bit >> 1;
             let qnan_rep =         exponent_mask | quiet_bit;
-            let exponent_bias =    max_exponent >> 1;
 
-            let shift_div = if <$ty as Float>::bits() == 32 { 1 } else { 2 };
+            // The reciprocal of `b` is computed to (about) the width of the type, so `a` is
+            // left-aligned by one extra bit for single precision and by two otherwise
+            let shift_div = if <$ty>::bits() == 32 { 1 } else { 2 };
+
+            // Subnormal operands are zeros in the flush to zero mode
+            let ftz = flush::enabled();
+            let (a, b) = if ftz { (a.flush(), b.flush()) } else { (a, b) };
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let a_exponent = (a_rep >> significand_bits) as u32 & max_exponent;
+            let b_exponent = (b_rep >> significand_bits) as u32 & max_exponent;
+            let quotient_sign = (a_rep ^ b_rep) & sign_bit;
 
-            let a_rep = Wrapping(a.repr());
-            let b_rep = Wrapping(b.repr());
-            let a_exponent = Wrapping((a_rep >> significand_bits.0 as usize & max_exponent).0 as <$ty as Float>::Int);
-            let b_exponent = Wrapping((b_rep >> significand_bits.0 as usize & max_exponent).0 as <$ty as Float>::Int);
             let mut a_significand = a_rep & significand_mask;
             let mut b_significand = b_rep & significand_mask;
-            let a_abs = a_rep & abs_mask;
-            let b_abs = b_rep & abs_mask;
-            let quotient_sign = (a_rep ^ b_rep) & sign_bit;
-            let mut scale : i32 = 0;
+            let mut scale = 0;
+
+            // Detect if a or b is zero, denormal, infinity, or NaN.
+            if a_exponent.wrapping_sub(1) >= max_exponent - 1 ||
+                b_exponent.wrapping_sub(1) >= max_exponent - 1 {
+                let a_abs = a_rep & abs_mask;
+                let b_abs = b_rep & abs_mask;
+
+                // Signaling NaN operands are invalid
+                if (a_abs > inf_rep && a_abs & quiet_bit == zero) ||
+                    (b_abs > inf_rep && b_abs & quiet_bit == zero) {
+                    exception::raise(exception::INVALID);
+                }
 
-            if a_exponent - one >= max_exponent - one ||
-                b_exponent - one >= max_exponent - one {
-                
+                // NaN / anything = qNaN
                 if a_abs > inf_rep {
-                    return (<$ty as Float>::from_repr((a_abs | quiet_bit).0));
+                    return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
                 }
+                // anything / NaN = qNaN
                 if b_abs > inf_rep {
-                    return (<$ty as Float>::from_repr((b_abs | quiet_bit).0));
+                    return <$ty>::from_repr(nan::propagate(b_rep | quiet_bit, qnan_rep));
                 }
 
                 if a_abs == inf_rep {
-                    if b_abs != zero {
-                        return (<$ty as Float>::from_repr((a_abs | quotient_sign).0));
+                    if b_abs == inf_rep {
+                        // infinity / infinity = NaN
+                        exception::raise(exception::INVALID);
+                        return <$ty>::from_repr(qnan_rep);
+                    } else {
+                        // infinity / anything else = +/- infinity
+                        return <$ty>::from_repr(a_abs | quotient_sign);
                     }
-                    return (<$ty as Float>::from_repr(qnan_rep.0));
                 }
 
+                // anything else / infinity = +/- 0
                 if b_abs == inf_rep {
-                    if a_abs != zero {
-                        return (<$ty as Float>::from_repr((b_abs | quotient_sign).0));
-                    }
-                    return (<$ty as Float>::from_repr(qnan_rep.0));
+                    return <$ty>::from_repr(quotient_sign);
                 }
 
                 if a_abs == zero {
-                    return (<$ty as Float>::from_repr(quotient_sign.0));
+                    if b_abs == zero {
+                        // zero / zero = NaN
+                        exception::raise(exception::INVALID);
+                        return <$ty>::from_repr(qnan_rep);
+                    } else {
+                        // zero / anything else = +/- zero
+                        return <$ty>::from_repr(quotient_sign);
+                    }
                 }
 
+                // anything else / zero = +/- infinity
                 if b_abs == zero {
-                    return (<$ty as Float>::from_repr(quotient_sign.0));
+                    exception::raise(exception::DIVIDE_BY_ZERO);
+                    return <$ty>::from_repr(inf_rep | quotient_sign);
                 }
 
+                // one or both of a or b is denormal, the other (if applicable) is a
+                // normal number.  Renormalize one or both of a and b, and set scale to
+                // include the necessary exponent adjustment.
                 if a_abs < implicit_bit {
-                    let (sc, ns) = (<$ty as Float>::normalize(a_significand.0));
-
-                    scale += sc;
-                    a_significand.0 = ns;
+                    let (exponent, significand) = <$ty>::normalize(a_significand);
+                    scale += exponent;
+                    a_significand = significand;
                 }
 
                 if b_abs < implicit_bit {
-                    let (sc, ns) = (<$ty as Float>::normalize(b_significand.0));
-
-                    scale -= sc;
-                    b_significand.0 = ns;
+                    let (exponent, significand) = <$ty>::normalize(b_significand);
+                    scale -= exponent;
+                    b_significand = significand;
                 }
-            } 
+            }
 
+            // Or in the implicit significand bit.  (If we fell through from the
+            // denormal path it was already set by normalize( ), but setting it twice
+            // won't hurt anything.)
             a_significand |= implicit_bit;
             b_significand |= implicit_bit;
 
-            let mut quotient_exponent = a_exponent - b_exponent + Wrapping(scale as <$ty as Float>::Int);
-            let reciprocal = (<$ty as Float>::compute_reciprocal(b_significand.0)) - 2;
+            let mut quotient_exponent: i32 = a_exponent as i32 - b_exponent as i32 + scale;
 
-            a_significand <<= shift_div;
+            // Align the significand of b as a fixed point number in [1.0, 2.0) with all of its
+            // bits after the binary point and compute an estimate of its reciprocal.  Subtracting
+            // two makes sure the estimate is never too large, so the quotient computed from it
+            // below is never too large either.  If the estimate for a significand of exactly 1.0
+            // wraps around to a tiny number, the subtraction wraps it back.
+            let reciprocal = <$ty>::compute_reciprocal(b_significand).wrapping_sub(2);
 
-            let mut quotient = Wrapping(<$ty as Float>::wide_multiply(a_significand.0, reciprocal).0);
-            let residual = if quotient < (implicit_bit << 1) {
-                quotient_exponent -= one;
-                (a_significand << (significand_bits + one).0 as usize) - quotient * b_significand
+            // The quotient is in [0.5, 2.0) with `significand_bits + 1` or `significand_bits + 2`
+            // significant bits.  Compute the residual `a - q*b` at the same time, which tells us
+            // how to round the quotient.
+            let mut quotient =
+                <$ty>::wide_multiply(a_significand << shift_div, reciprocal).0;
+            let mut residual = if quotient < (implicit_bit << 1) {
+                quotient_exponent -= 1;
+                (a_significand << (significand_bits + 1))
+                    .wrapping_sub(quotient.wrapping_mul(b_significand))
             } else {
                 quotient >>= 1;
-                (a_significand << significand_bits.0 as usize) - quotient * b_significand
+                (a_significand << significand_bits)
+                    .wrapping_sub(quotient.wrapping_mul(b_significand))
             };
 
-            let written_exponent = quotient_exponent + exponent_bias;
+            let written_exponent = quotient_exponent + exponent_bias as i32;
 
-            let final_rep = if written_exponent >= max_exponent {
-                inf_rep | quotient_sign
-            } else if written_exponent < one {
-                quotient_sign
+            let mode = round::mode();
+            let negative = quotient_sign != zero;
+
+            // The result of overflow: infinity, or the largest finite number if the rounding
+            // direction is toward zero for this sign
+            let overflow = if mode.overflows_to_infinity(negative) {
+                inf_rep
             } else {
-                let round = if (residual << 1) > b_significand { 1 } else { 0 };
+                inf_rep - one
+            };
+
+            if written_exponent >= max_exponent as i32 {
+                // If we have overflowed the exponent, return infinity.
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+                return <$ty>::from_repr(overflow | quotient_sign);
+            }
+
+            // The quotient may still be one too small; the residual needs to be below `b` to
+            // tell how the exact quotient compares to the halfway point.
+            let carry = residual >= b_significand;
+            if carry {
+                residual -= b_significand;
+            }
+            let half = (residual << 1) >= b_significand;
+            let sticky = if half {
+                (residual << 1) != b_significand
+            } else {
+                residual != zero
+            };
+
+            // The result is tiny unless correcting the quotient carries it into the smallest normal
+            // number
+            let corrected = quotient + carry as <$ty as Float>::Int;
+            if ftz && written_exponent < 1 &&
+                !(written_exponent == 0 && corrected == implicit_bit << 1) {
+                exception::raise(exception::UNDERFLOW);
+                return <$ty>::from_repr(quotient_sign);
+            }
+
+            let (abs_result, flags) = if written_exponent < 1 {
+                // Result is denormal before rounding.  Shift the quotient into place and round
+                // using the bits shifted out, with the residual acting as the sticky bit.
+                let shift = (1 - written_exponent) as u32;
+                if shift > significand_bits + 1 {
+                    // Less than half of the smallest denormal: rounds to +/- zero, or to the
+                    // smallest denormal when rounding away from zero
+                    exception::raise(exception::UNDERFLOW | exception::INEXACT);
+                    let round = mode.increment(negative, false, false, true);
+                    return <$ty>::from_repr(round as <$ty as Float>::Int | quotient_sign);
+                }
+
+                // The bits shifted out need to be exact here since they decide the rounding.
+                if carry {
+                    quotient += one;
+                }
+
+                let kept = quotient >> shift;
+                let rest = quotient & ((one << shift) - one);
+                let half_bit = one << (shift - 1);
+                let round = mode.increment(negative,
+                                           kept & one != zero,
+                                           rest & half_bit != zero,
+                                           rest & (half_bit - one) != zero || half || sticky);
+
+                // Rounding may carry into the implicit bit, which correctly produces the
+                // smallest normal number.  That still underflows as tininess is detected before
+                // rounding.
+                let flags = if rest != zero || half || sticky {
+                    exception::UNDERFLOW | exception::INEXACT
+                } else {
+                    0
+                };
+                (kept + round as <$ty as Float>::Int, flags)
+            } else {
+                // Result is normal before rounding.  Correcting a quotient that is one too small
+                // may carry into the exponent, which is correct, and the exact halfway case
+                // cannot occur.
                 let mut abs_result = quotient & significand_mask;
-                abs_result |= written_exponent << significand_bits.0 as usize;
-                abs_result += Wrapping(round);
+                abs_result |= (written_exponent as <$ty as Float>::Int) << significand_bits;
+                abs_result += carry as <$ty as Float>::Int;
 
-                abs_result
+                let round = mode.increment(negative, abs_result & one != zero, half, sticky);
+                let flags = if half || sticky { exception::INEXACT } else { 0 };
+                (abs_result + round as <$ty as Float>::Int, flags)
             };
 
-            (<$ty as Float>::from_repr(final_rep.0))
+            // The correction or the rounding may overflow to infinity here
+            if abs_result >= inf_rep {
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+                return <$ty>::from_repr(overflow | quotient_sign);
+            }
+            exception::raise(flags);
+
+            <$ty>::from_repr(abs_result | quotient_sign)
         }
     }
 }
 
+#[cfg(target_arch = "arm")]
+div!("aapcs", __divsf3: f32);
+
+#[cfg(not(target_arch = "arm"))]
 div!("C", __divsf3: f32);
+
+#[cfg(target_arch = "arm")]
+div!("aapcs", __divdf3: f64);
+
+#[cfg(not(target_arch = "arm"))]
 div!("C", __divdf3: f64);
 
+#[cfg(target_arch = "arm")]
+div!("aapcs", __divtf3: f128);
+
+#[cfg(not(target_arch = "arm"))]
+div!("C", __divtf3: f128);
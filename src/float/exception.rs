@@ -0,0 +1,35 @@
+use float::fenv::{__fe_clearexcept, __fe_raise, __fe_testexcept};
+
+// The IEEE 754 exceptions, as bits of the status word of `float::fenv`. The values are those of the cumulative
+// exception bits of ARM's FPSCR (and of `FE_*` in ARM's `fenv.h`)
+
+/// An operation had no meaningful result, e.g. `0 * inf` or an operand was a signaling NaN
+pub const INVALID: i32 = 0x1;
+/// A finite non-zero number was divided by zero
+pub const DIVIDE_BY_ZERO: i32 = 0x2;
+/// The rounded result was too large for the format
+pub const OVERFLOW: i32 = 0x4;
+/// The result was both tiny (below the smallest normal number before rounding) and inexact
+pub const UNDERFLOW: i32 = 0x8;
+/// The result had to be rounded
+pub const INEXACT: i32 = 0x10;
+
+/// All the exceptions
+pub const ALL: i32 = INVALID | DIVIDE_BY_ZERO | OVERFLOW | UNDERFLOW | INEXACT;
+
+/// Raises the exceptions `flags`, if any
+pub fn raise(flags: i32) {
+    if flags != 0 {
+        __fe_raise(flags)
+    }
+}
+
+/// Returns which of the exceptions in `mask` have been raised since they were last cleared
+pub fn test(mask: i32) -> i32 {
+    __fe_testexcept(mask)
+}
+
+/// Clears the exceptions in `mask`
+pub fn clear(mask: i32) {
+    __fe_clearexcept(mask)
+}
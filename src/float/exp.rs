@@ -0,0 +1,123 @@
+use float::Float;
+use float::nan;
+use float::scale::{scalbn, scalbnf};
+
+// NOTE(feature) Like `float::integral` these are C library functions that a hosted libm defines as
+// strong symbols, so they are only exported under their C names with the `math` feature
+
+// The algorithms are those of FreeBSD's msun (and musl), which derive from Sun's fdlibm: reduce
+// `x = k*ln2 + r` with `|r| <= 0.5*ln2`, approximate `exp(r)` with a rational function of `r` and
+// scale the result by `2^k`
+
+/// Returns `e^x`, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn expf(x: f32) -> f32 {
+    const LN2_HI: f32 = 6.9314575195e-01; // 0x3f317200
+    const LN2_LO: f32 = 1.4286067653e-06; // 0x35bfbe8e
+    const INV_LN2: f32 = 1.4426950216e+00; // 0x3fb8aa3b
+    // |x/(e^x - 1) - (1 - x/2 + x^2*P(x^2))| < 2^-27.74 on [-0.5*ln2, 0.5*ln2]
+    const P1: f32 = 1.6666625440e-01; // 0x3e2aaa8f
+    const P2: f32 = -2.7667332906e-03; // 0xbb355215
+
+    let rep = x.repr();
+    let negative = rep >> 31 != 0;
+    let abs = rep & 0x7fff_ffff;
+
+    // |x| >= 87.33655 or NaN
+    if abs >= 0x42ae_ac50 {
+        if abs > 0x7f80_0000 {
+            return nan::quietf(x);
+        }
+        if !negative && abs >= 0x42b1_7218 {
+            // x >= 88.72284: overflow, unless x is infinity
+            return scalbnf(x, 127);
+        }
+        if negative && abs >= 0x42cf_f1b5 {
+            // x <= -103.97208: underflow to zero
+            return if abs == 0x7f80_0000 { 0.0 } else { scalbnf(1.0, -150) };
+        }
+    }
+
+    let (hi, lo, k) = if abs > 0x3eb1_7218 {
+        // |x| > 0.5*ln2
+        let k = if abs > 0x3f85_1592 {
+            // |x| > 1.5*ln2
+            (INV_LN2 * x + if negative { -0.5 } else { 0.5 }) as i32
+        } else if negative {
+            -1
+        } else {
+            1
+        };
+        // `k*LN2_HI` is exact
+        (x - k as f32 * LN2_HI, k as f32 * LN2_LO, k)
+    } else if abs > 0x3900_0000 {
+        // |x| > 2^-13
+        (x, 0.0, 0)
+    } else {
+        return 1.0 + x;
+    };
+    let x = hi - lo;
+
+    let xx = x * x;
+    let c = x - xx * (P1 + xx * P2);
+    let y = 1.0 + (x * c / (2.0 - c) - lo + hi);
+    if k == 0 { y } else { scalbnf(y, k) }
+}
+
+/// Returns `e^x`, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn exp(x: f64) -> f64 {
+    const LN2_HI: f64 = 6.93147180369123816490e-01; // 0x3fe62e42_fee00000
+    const LN2_LO: f64 = 1.90821492927058770002e-10; // 0x3dea39ef_35793c76
+    const INV_LN2: f64 = 1.44269504088896338700e+00; // 0x3ff71547_652b82fe
+    // |x/(e^x - 1) - (1 - x/2 + x^2*P(x^2))| < 2^-59 on [-0.5*ln2, 0.5*ln2]
+    const P1: f64 = 1.66666666666666019037e-01; // 0x3fc55555_5555553e
+    const P2: f64 = -2.77777777770155933842e-03; // 0xbf66c16c_16bebd93
+    const P3: f64 = 6.61375632143793436117e-05; // 0x3f11566a_af25de2c
+    const P4: f64 = -1.65339022054652515390e-06; // 0xbebbbd41_c5d26bf1
+    const P5: f64 = 4.13813679705723846039e-08; // 0x3e663769_72bea4d0
+
+    let rep = x.repr();
+    let negative = rep >> 63 != 0;
+    let abs = (rep >> 32) as u32 & 0x7fff_ffff;
+
+    // |x| >= 708.39 or NaN
+    if abs >= 0x4086_232b {
+        if rep & 0x7fff_ffff_ffff_ffff > 0x7ff0_0000_0000_0000 {
+            return nan::quiet(x);
+        }
+        if x > 709.782712893383973096 {
+            // Overflow, unless x is infinity
+            return scalbn(x, 1023);
+        }
+        if x < -745.13321910194110842 {
+            // Underflow to zero
+            return if rep == 0xfff0_0000_0000_0000 { 0.0 } else { scalbn(1.0, -1075) };
+        }
+    }
+
+    let (hi, lo, k) = if abs > 0x3fd6_2e42 {
+        // |x| > 0.5*ln2
+        let k = if abs >= 0x3ff0_a2b2 {
+            // |x| >= 1.5*ln2
+            (INV_LN2 * x + if negative { -0.5 } else { 0.5 }) as i32
+        } else if negative {
+            -1
+        } else {
+            1
+        };
+        // `k*LN2_HI` is exact
+        (x - k as f64 * LN2_HI, k as f64 * LN2_LO, k)
+    } else if abs > 0x3e30_0000 {
+        // |x| > 2^-28
+        (x, 0.0, 0)
+    } else {
+        return 1.0 + x;
+    };
+    let x = hi - lo;
+
+    let xx = x * x;
+    let c = x - xx * (P1 + xx * (P2 + xx * (P3 + xx * (P4 + xx * P5))));
+    let y = 1.0 + (x * c / (2.0 - c) - lo + hi);
+    if k == 0 { y } else { scalbn(y, k) }
+}
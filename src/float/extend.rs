@@ -0,0 +1,108 @@
+use float::{f16, f128, Float};
+use float::exception;
+use float::flush;
+use float::nan::{self, Nan};
+
+macro_rules! extend {
+    ($extend:ident: $src:ty => $dst:ty) => {
+        fn $extend(a: $src) -> $dst {
+            let src_zero: <$src as Float>::Int = 0;
+            let src_one: <$src as Float>::Int = 1;
+
+            let src_bits =         <$src>::bits();
+            let src_sig_bits =     <$src>::significand_bits();
+            let src_exp_bias =     <$src>::exponent_bias();
+            let src_min_normal =   <$src>::implicit_bit();
+            let src_infinity =     <$src>::exponent_mask();
+            let src_sign_mask =    <$src>::sign_mask();
+            let src_abs_mask =     src_sign_mask - src_one;
+            let src_qnan =         src_min_normal >> 1;
+            let src_nan_code =     src_qnan - src_one;
+
+            let dst_bits =         <$dst>::bits();
+            let dst_sig_bits =     <$dst>::significand_bits();
+            let dst_inf_exp =      <$dst>::exponent_max();
+            let dst_exp_bias =     <$dst>::exponent_bias();
+            let dst_min_normal =   <$dst>::implicit_bit();
+            let dst_qnan =         dst_min_normal >> 1;
+
+            let sig_bits_delta = dst_sig_bits - src_sig_bits;
+
+            // Break a into a sign and representation of the absolute value
+            let a_rep = a.repr();
+            let a_abs = a_rep & src_abs_mask;
+            let sign = a_rep & src_sign_mask;
+
+            let abs_result = if a_abs.wrapping_sub(src_min_normal) < src_infinity - src_min_normal {
+                // a is a normal number.
+                // Extend to the destination type by shifting the significand and
+                // exponent into the proper position and rebiasing the exponent.
+                let abs_result = (a_abs as <$dst as Float>::Int) << sig_bits_delta;
+                abs_result + (((dst_exp_bias - src_exp_bias) as <$dst as Float>::Int) << dst_sig_bits)
+            } else if a_abs >= src_infinity {
+                // a is NaN or infinity.
+                // Conjure the result by beginning with infinity, then setting the qNaN
+                // bit (signaling NaNs are quieted) and left-aligning the rest of the
+                // trailing NaN payload field.
+                let mut abs_result = (dst_inf_exp as <$dst as Float>::Int) << dst_sig_bits;
+                if a_abs != src_infinity {
+                    if a_abs & src_qnan == src_zero {
+                        exception::raise(exception::INVALID);
+                    }
+                    abs_result |= dst_qnan;
+                    if nan::mode() == Nan::Default {
+                        return <$dst>::from_repr(abs_result);
+                    }
+                }
+                abs_result | ((a_abs & src_nan_code) as <$dst as Float>::Int) << sig_bits_delta
+            } else if a_abs != src_zero && !flush::enabled() {
+                // a is denormal.
+                // renormalize the significand and clear the leading bit, then insert
+                // the correct adjusted exponent in the destination type.
+                let scale = a_abs.leading_zeros() - src_min_normal.leading_zeros();
+                let mut abs_result = (a_abs as <$dst as Float>::Int) << (sig_bits_delta + scale);
+                abs_result ^= dst_min_normal;
+                let result_exponent = dst_exp_bias - src_exp_bias - scale + 1;
+                abs_result | (result_exponent as <$dst as Float>::Int) << dst_sig_bits
+            } else {
+                // a is zero, or denormal in the flush to zero mode.
+                0
+            };
+
+            // Apply the signbit to (dst_t)abs(a).
+            <$dst>::from_repr(abs_result | (sign as <$dst as Float>::Int) << (dst_bits - src_bits))
+        }
+    }
+}
+
+extend!(extendhfsf2: f16 => f32);
+extend!(extendsfdf2: f32 => f64);
+extend!(extendsftf2: f32 => f128);
+extend!(extenddftf2: f64 => f128);
+
+intrinsic!(/// Returns the half precision number with representation `a` converted to `f32`. This
+           /// conversion is exact
+           | __extendhfsf2(a: u16) -> f32 {
+               extendhfsf2(f16::from_repr(a))
+           });
+
+intrinsic!(/// Returns the half precision number with representation `a` converted to `f32`. Alias of
+           /// `__extendhfsf2` used by GCC
+           | __gnu_h2f_ieee(a: u16) -> f32 {
+               extendhfsf2(f16::from_repr(a))
+           });
+
+intrinsic!(/// Returns `a` converted to `f64`. This conversion is exact
+           | __extendsfdf2(a: f32) -> f64 {
+               extendsfdf2(a)
+           });
+
+intrinsic!(/// Returns `a` converted to `f128`. This conversion is exact
+           | __extendsftf2(a: f32) -> f128 {
+               extendsftf2(a)
+           });
+
+intrinsic!(/// Returns `a` converted to `f128`. This conversion is exact
+           | __extenddftf2(a: f64) -> f128 {
+               extenddftf2(a)
+           });
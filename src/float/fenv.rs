@@ -0,0 +1,213 @@
+#[cfg(not(thumbv6m))]
+use core::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+use float::exception;
+use float::nan::Nan;
+#[cfg(feature = "rounding-modes")]
+use float::round::Round;
+
+// The soft float environment is two words. The control word holds the modes: the rounding mode
+// (`float::round`) in bits 0 and 1, the NaN mode (`float::nan`) in bit 2 and the flush to zero
+// mode (`float::flush`) in bit 3. The status word holds the raised `float::exception` flags.
+//
+// The soft float routines only go through the `__fe_*` hooks below, never through the words
+// directly
+
+#[cfg(feature = "rounding-modes")]
+const ROUND: Field = Field { shift: 0, mask: 0b11 };
+const NAN: Field = Field { shift: 2, mask: 0b1 };
+#[cfg(feature = "flush-to-zero")]
+const FTZ: Field = Field { shift: 3, mask: 0b1 };
+
+/// A bit field of the control word
+struct Field {
+    shift: u32,
+    mask: usize,
+}
+
+impl Field {
+    fn get(&self) -> i32 {
+        ((load(Word::Control) >> self.shift) & self.mask) as i32
+    }
+
+    fn set(&self, value: i32) {
+        update(Word::Control, self.mask << self.shift, (value as usize) << self.shift)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Word {
+    Control,
+    Status,
+}
+
+// NOTE thumbv6m has no atomic instructions (`max-atomic-width` is 0) so `AtomicUsize` isn't
+// available there. That target is single core, so an update of a word can only be torn by an
+// interrupt handler that updates the same word
+#[cfg(not(thumbv6m))]
+static CONTROL: AtomicUsize = ATOMIC_USIZE_INIT;
+
+#[cfg(not(thumbv6m))]
+static STATUS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+#[cfg(thumbv6m)]
+static mut CONTROL: usize = 0;
+
+#[cfg(thumbv6m)]
+static mut STATUS: usize = 0;
+
+#[cfg(not(thumbv6m))]
+fn word(word: Word) -> &'static AtomicUsize {
+    match word {
+        Word::Control => &CONTROL,
+        Word::Status => &STATUS,
+    }
+}
+
+#[cfg(not(thumbv6m))]
+fn load(w: Word) -> usize {
+    word(w).load(Ordering::Relaxed)
+}
+
+/// Replaces the bits of the word `w` that are set in `mask` with those of `bits`
+#[cfg(not(thumbv6m))]
+fn update(w: Word, mask: usize, bits: usize) {
+    let word = word(w);
+    let mut old = word.load(Ordering::Relaxed);
+    loop {
+        let new = (old & !mask) | (bits & mask);
+        match word.compare_exchange_weak(old, new, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return,
+            Err(x) => old = x,
+        }
+    }
+}
+
+#[cfg(thumbv6m)]
+fn load(w: Word) -> usize {
+    unsafe {
+        match w {
+            Word::Control => ::core::ptr::read_volatile(&CONTROL),
+            Word::Status => ::core::ptr::read_volatile(&STATUS),
+        }
+    }
+}
+
+#[cfg(thumbv6m)]
+fn update(w: Word, mask: usize, bits: usize) {
+    let new = (load(w) & !mask) | (bits & mask);
+    unsafe {
+        match w {
+            Word::Control => ::core::ptr::write_volatile(&mut CONTROL, new),
+            Word::Status => ::core::ptr::write_volatile(&mut STATUS, new),
+        }
+    }
+}
+
+// NOTE(weak) All the hooks are weak so that a program can replace them, e.g. to keep the
+// environment in thread local storage or in a hardware register. The getters of the default
+// hooks read the environment that their setters change, for the whole program
+
+// NOTE(rounding-modes) Like compiler-rt without `CRT_HAS_FENV`, the soft float routines only
+// round to nearest unless the `rounding-modes` feature is enabled. Without it there are no rounding
+// mode hooks, and `float::round::mode` is a constant. Likewise the flush to zero hooks only exist
+// with the `flush-to-zero` feature
+
+/// Returns the rounding mode the soft float routines use, as a `Round` discriminant
+#[cfg(feature = "rounding-modes")]
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_getround() -> i32 {
+    ROUND.get()
+}
+
+/// Sets the rounding mode reported by the default `__fe_getround`. Returns zero on success and a
+/// non-zero value, leaving the mode unchanged, if `mode` isn't a `Round` discriminant
+#[cfg(feature = "rounding-modes")]
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_setround(mode: i32) -> i32 {
+    match Round::from_raw(mode) {
+        Some(_) => {
+            ROUND.set(mode);
+            0
+        }
+        None => 1,
+    }
+}
+
+/// Returns the NaN mode the soft float routines use, as a `Nan` discriminant
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_getnan() -> i32 {
+    NAN.get()
+}
+
+/// Sets the NaN mode reported by the default `__fe_getnan`. Returns zero on success and a non-zero
+/// value, leaving the mode unchanged, if `mode` isn't a `Nan` discriminant
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_setnan(mode: i32) -> i32 {
+    match Nan::from_raw(mode) {
+        Some(_) => {
+            NAN.set(mode);
+            0
+        }
+        None => 1,
+    }
+}
+
+/// Returns 1 if the soft float routines flush subnormals to zero and 0 if they don't
+#[cfg(feature = "flush-to-zero")]
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_getftz() -> i32 {
+    FTZ.get()
+}
+
+/// Turns the flush to zero mode reported by the default `__fe_getftz` on (1) or off (0). Returns
+/// zero on success and a non-zero value, leaving the mode unchanged, for other values
+#[cfg(feature = "flush-to-zero")]
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_setftz(mode: i32) -> i32 {
+    match mode {
+        0 | 1 => {
+            FTZ.set(mode);
+            0
+        }
+        _ => 1,
+    }
+}
+
+/// Sets the exceptions `flags` in the status word. They stay set until cleared
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_raise(flags: i32) {
+    let flags = (flags & exception::ALL) as usize;
+    // Skip the read-modify-write when nothing changes, which is the common case
+    if load(Word::Status) & flags != flags {
+        update(Word::Status, flags, flags);
+    }
+}
+
+/// Sets the inexact exception. This is the hook compiler-rt's soft float routines call
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_raise_inexact() {
+    __fe_raise(exception::INEXACT)
+}
+
+/// Returns which of the exceptions in `mask` are set in the status word
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_testexcept(mask: i32) -> i32 {
+    load(Word::Status) as i32 & mask
+}
+
+/// Clears the exceptions in `mask` from the status word
+#[cfg_attr(not(test), no_mangle)]
+#[cfg_attr(not(windows), linkage = "weak")]
+pub extern "C" fn __fe_clearexcept(mask: i32) {
+    update(Word::Status, mask as usize, 0)
+}
@@ -0,0 +1,62 @@
+use float::{f16, f128, Float};
+#[cfg(feature = "flush-to-zero")]
+use float::fenv::{__fe_getftz, __fe_setftz};
+
+// In the flush to zero mode, which mirrors the FZ bit of ARM's FPSCR, the soft float arithmetic
+// (`float::add`, `float::sub`, `float::mul` and `float::div`) and the conversions (`float::conv`,
+// `float::extend` and `float::trunc`) read subnormal operands as zeros of the same sign, and return
+// a zero of the same sign in place of a result that is tiny before rounding. Like ARM they raise
+// the underflow exception, but not the inexact one, when they flush a result. The libm functions
+// (`fma`, `sqrt`, `exp` and so on) always have gradual underflow.
+//
+// The mode is off by default, and only exists with the `flush-to-zero` feature
+
+/// Returns whether subnormals are flushed to zero. Any value but 1 reported by an overriding
+/// `__fe_getftz` means they aren't
+#[cfg(feature = "flush-to-zero")]
+pub fn enabled() -> bool {
+    __fe_getftz() == 1
+}
+
+/// Returns whether subnormals are flushed to zero, which they never are without the
+/// `flush-to-zero` feature
+#[cfg(not(feature = "flush-to-zero"))]
+#[inline(always)]
+pub fn enabled() -> bool {
+    false
+}
+
+/// Turns the flush to zero mode on or off, if `__fe_setftz` hasn't been overridden
+#[cfg(feature = "flush-to-zero")]
+pub fn set_enabled(enabled: bool) {
+    __fe_setftz(enabled as i32);
+}
+
+/// Does nothing: without the `flush-to-zero` feature subnormals are never flushed
+#[cfg(not(feature = "flush-to-zero"))]
+pub fn set_enabled(_enabled: bool) {}
+
+/// Flushing a single operand
+pub trait Flush: Float {
+    /// Returns zero with the sign of `self` if `self` is subnormal, and `self` otherwise
+    fn flush(self) -> Self;
+}
+
+macro_rules! flush {
+    ($($ty:ty),*) => {
+        $(
+            impl Flush for $ty {
+                fn flush(self) -> Self {
+                    let rep = self.repr();
+                    if rep & Self::exponent_mask() == 0 {
+                        Self::from_repr(rep & Self::sign_mask())
+                    } else {
+                        self
+                    }
+                }
+            }
+        )*
+    }
+}
+
+flush!(f16, f32, f64, f128);
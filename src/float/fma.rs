@@ -0,0 +1,227 @@
+use float::{f128, Float};
+use float::exception;
+use float::nan;
+use float::round::{self, Round};
+
+// NOTE(feature) These are the C library's `fma` functions rather than compiler-rt builtins. Ours
+// would take precedence over a libm that the program links, which may use a hardware FMA, even as
+// weak symbols (the linker doesn't look for a strong one in a shared library), and they would clash
+// with the strong symbols of a static libm like newlib or picolibc, so they are only exported under
+// their C names with the `math` feature
+macro_rules! fma {
+    ($intrinsic:ident: $ty:ty) => {
+        /// Returns `a * b + c` computed with a single rounding, in the current rounding mode
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $intrinsic(a: $ty, b: $ty, c: $ty) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let bits =             <$ty>::bits();
+            let significand_bits = <$ty>::significand_bits();
+            let max_exponent =     <$ty>::exponent_max();
+            let exponent_bias =    <$ty>::exponent_bias();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let c_rep = c.repr();
+            let a_abs = a_rep & abs_mask;
+            let b_abs = b_rep & abs_mask;
+            let c_abs = c_rep & abs_mask;
+            let product_sign = (a_rep ^ b_rep) & sign_bit;
+            let c_sign = c_rep & sign_bit;
+
+            let mode = round::mode();
+
+            // Signaling NaN operands are invalid
+            if (a_abs > inf_rep && a_abs & quiet_bit == zero) ||
+                (b_abs > inf_rep && b_abs & quiet_bit == zero) ||
+                (c_abs > inf_rep && c_abs & quiet_bit == zero) {
+                exception::raise(exception::INVALID);
+            }
+
+            // infinity * zero + anything = NaN, even if c is a quiet NaN (as on ARM)
+            if (a_abs == inf_rep && b_abs == zero) || (a_abs == zero && b_abs == inf_rep) {
+                exception::raise(exception::INVALID);
+                return <$ty>::from_repr(qnan_rep);
+            }
+
+            // NaN * anything + anything = qNaN, and so on
+            if a_abs > inf_rep {
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+            if b_abs > inf_rep {
+                return <$ty>::from_repr(nan::propagate(b_rep | quiet_bit, qnan_rep));
+            }
+            if c_abs > inf_rep {
+                return <$ty>::from_repr(nan::propagate(c_rep | quiet_bit, qnan_rep));
+            }
+
+            if a_abs == inf_rep || b_abs == inf_rep {
+                // infinity - infinity = NaN
+                if c_abs == inf_rep && c_sign != product_sign {
+                    exception::raise(exception::INVALID);
+                    return <$ty>::from_repr(qnan_rep);
+                }
+                // +/- infinity + anything else = +/- infinity
+                return <$ty>::from_repr(inf_rep | product_sign);
+            }
+
+            // finite + infinity = infinity
+            if c_abs == inf_rep {
+                return c;
+            }
+
+            if a_abs == zero || b_abs == zero {
+                // zero + non-zero = non-zero, exactly
+                if c_abs != zero {
+                    return c;
+                }
+                // The sum of zeros of opposite signs is +0, or -0 when rounding downward
+                return if product_sign == c_sign {
+                    c
+                } else if mode == Round::Downward {
+                    <$ty>::from_repr(sign_bit)
+                } else {
+                    <$ty>::from_repr(zero)
+                };
+            }
+
+            // Returns the exponent and the significand (with its implicit bit) of a finite non-zero
+            // representation, normalizing denormals
+            let unpack = |rep: <$ty as Float>::Int| {
+                let exponent = (rep >> significand_bits) as i32 & max_exponent as i32;
+                let significand = rep & significand_mask;
+                if exponent == 0 {
+                    <$ty>::normalize(significand)
+                } else {
+                    (exponent, significand | implicit_bit)
+                }
+            };
+
+            // The addends are kept as double width fixed point numbers `(hi, lo) * 2^exponent`,
+            // with their leading bit at bit `2 * bits - 2` so that the sum can't carry out.  The
+            // exact product has `2 * significand_bits + 2` bits at most, so it fits.
+            let (a_exponent, a_significand) = unpack(a_rep);
+            let (b_exponent, b_significand) = unpack(b_rep);
+            let (hi, lo) = <$ty>::wide_multiply(a_significand, b_significand);
+            let shift = hi.leading_zeros() - 1;
+            let (mut hi, mut lo) = <$ty>::wide_left_shift(hi, lo, shift as i32);
+            let mut exponent = a_exponent + b_exponent -
+                2 * (exponent_bias + significand_bits) as i32 - shift as i32;
+            let mut negative = product_sign != zero;
+
+            if c_abs != zero {
+                let (c_exponent, c_significand) = unpack(c_rep);
+                let mut c_hi = c_significand << (bits - 2 - significand_bits);
+                let mut c_lo = zero;
+                let mut c_exponent = c_exponent - (exponent_bias + 2 * bits) as i32 + 2;
+                let mut c_negative = c_sign != zero;
+
+                // Make the product the larger addend, in magnitude
+                if c_exponent > exponent || (c_exponent == exponent && (c_hi, c_lo) > (hi, lo)) {
+                    ::core::mem::swap(&mut hi, &mut c_hi);
+                    ::core::mem::swap(&mut lo, &mut c_lo);
+                    ::core::mem::swap(&mut exponent, &mut c_exponent);
+                    ::core::mem::swap(&mut negative, &mut c_negative);
+                }
+
+                // Align the smaller addend.  Whatever it loses sticks to its lowest bit, which is
+                // well below the rounding position unless the shift is too small to lose anything.
+                let align = (exponent - c_exponent) as u32;
+                let (c_hi, c_lo) = <$ty>::wide_right_shift_with_sticky(c_hi, c_lo, align);
+
+                if negative == c_negative {
+                    let (sum_lo, carry) = lo.overflowing_add(c_lo);
+                    hi = hi + c_hi + carry as <$ty as Float>::Int;
+                    lo = sum_lo;
+                } else {
+                    let (difference_lo, borrow) = lo.overflowing_sub(c_lo);
+                    hi = hi - c_hi - borrow as <$ty as Float>::Int;
+                    lo = difference_lo;
+                }
+
+                // Exact cancellation gives +0, or -0 when rounding downward
+                if hi == zero && lo == zero {
+                    return <$ty>::from_repr(if mode == Round::Downward { sign_bit } else { zero });
+                }
+
+                // Renormalize after cancellation
+                if hi == zero {
+                    hi = lo;
+                    lo = zero;
+                    exponent -= bits as i32;
+                }
+            }
+
+            let shift = hi.leading_zeros();
+            let (hi, lo) = <$ty>::wide_left_shift(hi, lo, shift as i32);
+            exponent -= shift as i32;
+
+            let sign = if negative { sign_bit } else { zero };
+
+            // The leading bit is now bit `2 * bits - 1`
+            let mut written_exponent = exponent + (2 * bits + exponent_bias) as i32 - 1;
+
+            // If we have overflowed the type, return +/- infinity, or the largest finite number
+            // if the rounding direction is toward zero for this sign.
+            if written_exponent >= max_exponent as i32 {
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+                if mode.overflows_to_infinity(negative) {
+                    return <$ty>::from_repr(inf_rep | sign);
+                }
+                return <$ty>::from_repr((inf_rep - one) | sign);
+            }
+
+            // A result that is denormal before rounding is shifted into place, which clears its
+            // implicit bit
+            let tiny = written_exponent < 1;
+            let (hi, lo) = if tiny {
+                let shift = (1 - written_exponent) as u32;
+                written_exponent = 1;
+                <$ty>::wide_right_shift_with_sticky(hi, lo, shift)
+            } else {
+                (hi, lo)
+            };
+
+            // The significand is the top of `hi`, the rest decides the rounding.  Adding the
+            // significand to the exponent lets the rounding carry into the exponent, which
+            // correctly produces the smallest normal number or infinity.
+            let round_bits = bits - significand_bits - 1;
+            let significand = hi >> round_bits;
+            let half = hi & (one << (round_bits - 1)) != zero;
+            let sticky = hi & ((one << (round_bits - 1)) - one) != zero || lo != zero;
+            let mut abs_result = ((written_exponent - 1) as <$ty as Float>::Int) << significand_bits;
+            abs_result += significand;
+            if mode.increment(negative, significand & one != zero, half, sticky) {
+                abs_result += one;
+            }
+
+            // Underflow is detected before rounding, as on ARM
+            if half || sticky {
+                let mut flags = exception::INEXACT;
+                if tiny {
+                    flags |= exception::UNDERFLOW;
+                }
+                if abs_result == inf_rep {
+                    flags |= exception::OVERFLOW;
+                }
+                exception::raise(flags);
+            }
+
+            <$ty>::from_repr(abs_result | sign)
+        }
+    }
+}
+
+fma!(fmaf: f32);
+fma!(fma: f64);
+fma!(fmaf128: f128);
@@ -0,0 +1,112 @@
+use float::{f128, Float};
+use float::exception;
+use float::nan;
+
+// NOTE(feature) Like `float::fma` these are C library functions, which LLVM calls for `frem` (the
+// `%` operator on floats), so they are only exported under their C names with the `math` feature
+macro_rules! fmod {
+    ($intrinsic:ident: $ty:ty) => {
+        /// Returns the remainder of `a / b` truncated toward zero, which has the sign of `a` and
+        /// is always exact
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $intrinsic(a: $ty, b: $ty) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let significand_bits = <$ty>::significand_bits();
+            let max_exponent =     <$ty>::exponent_max();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let a_abs = a_rep & abs_mask;
+            let b_abs = b_rep & abs_mask;
+            let sign = a_rep & sign_bit;
+
+            // Signaling NaN operands are invalid
+            if (a_abs > inf_rep && a_abs & quiet_bit == zero) ||
+                (b_abs > inf_rep && b_abs & quiet_bit == zero) {
+                exception::raise(exception::INVALID);
+            }
+
+            // NaN % anything = qNaN
+            if a_abs > inf_rep {
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+            // anything % NaN = qNaN
+            if b_abs > inf_rep {
+                return <$ty>::from_repr(nan::propagate(b_rep | quiet_bit, qnan_rep));
+            }
+
+            // infinity % anything = NaN and anything % zero = NaN
+            if a_abs == inf_rep || b_abs == zero {
+                exception::raise(exception::INVALID);
+                return <$ty>::from_repr(qnan_rep);
+            }
+
+            // x % y = x if |x| < |y|, which covers x % infinity and zero % y
+            if a_abs < b_abs {
+                return a;
+            }
+
+            // Returns the exponent and the significand (with its implicit bit) of a finite non-zero
+            // representation, normalizing denormals
+            let unpack = |rep: <$ty as Float>::Int| {
+                let exponent = (rep >> significand_bits) as i32 & max_exponent as i32;
+                let significand = rep & significand_mask;
+                if exponent == 0 {
+                    <$ty>::normalize(significand)
+                } else {
+                    (exponent, significand | implicit_bit)
+                }
+            };
+            let (mut exponent, mut significand) = unpack(a_abs);
+            let (b_exponent, b_significand) = unpack(b_abs);
+
+            // Long division, keeping only the remainder.  Both significands have their leading bit
+            // at the implicit bit, so each step subtracts `b` at most once and the remainder stays
+            // below `2 * b` after the shift.
+            loop {
+                if significand >= b_significand {
+                    significand -= b_significand;
+                    if significand == zero {
+                        return <$ty>::from_repr(sign);
+                    }
+                }
+                if exponent == b_exponent {
+                    break;
+                }
+                significand <<= 1;
+                exponent -= 1;
+            }
+
+            // Renormalize the remainder, which has the exponent of `b` or less
+            let shift = significand.leading_zeros() - implicit_bit.leading_zeros();
+            significand <<= shift;
+            exponent -= shift as i32;
+
+            let abs_result = if exponent > 0 {
+                ((exponent as <$ty as Float>::Int) << significand_bits) |
+                    (significand & significand_mask)
+            } else {
+                // Denormal.  The remainder is a multiple of the last place of `b`, so no bits are
+                // shifted out
+                significand >> (1 - exponent) as u32
+            };
+
+            <$ty>::from_repr(abs_result | sign)
+        }
+    }
+}
+
+fmod!(fmodf: f32);
+fmod!(fmod: f64);
+fmod!(fmodf128: f128);
@@ -0,0 +1,173 @@
+use float::Float;
+use float::exception;
+use float::nan;
+use float::round;
+
+// NOTE(feature) Like `float::fma` these are C library functions rather than compiler-rt builtins,
+// which a hosted libm defines as strong symbols that would clash with ours, so they are only
+// exported under their C names with the `rounding-functions` feature
+
+macro_rules! integral {
+    ($name:ident: $ty:ty) => {
+        /// Rounds `a` to an integral value, incrementing its magnitude when `increment`, given the
+        /// sign, the last integer bit, the first fraction bit and whether any other fraction bit
+        /// is set, says so. Raises the inexact exception for non-integral `a` if `inexact`
+        fn $name<F>(a: $ty, increment: F, inexact: bool) -> $ty
+        where
+            F: Fn(bool, bool, bool, bool) -> bool,
+        {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let significand_bits = <$ty>::significand_bits();
+            let exponent_bias =    <$ty>::exponent_bias();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let a_abs = a_rep & abs_mask;
+            let sign = a_rep & sign_bit;
+            let negative = sign != zero;
+            let exponent = (a_abs >> significand_bits) as u32;
+
+            if a_abs > inf_rep {
+                // Signaling NaN operands are invalid
+                if a_abs & quiet_bit == zero {
+                    exception::raise(exception::INVALID);
+                }
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+
+            // Infinities and numbers without fraction bits are already integral
+            if exponent >= exponent_bias + significand_bits {
+                return a;
+            }
+
+            let result = if exponent < exponent_bias {
+                // |a| < 1.0, so the integer part is zero and the first fraction bit is the one
+                // with weight 0.5
+                if a_abs == zero {
+                    return a;
+                }
+                let half = exponent == exponent_bias - 1;
+                let sticky = if half { a_abs & significand_mask != zero } else { true };
+                if increment(negative, false, half, sticky) {
+                    (exponent_bias as <$ty as Float>::Int) << significand_bits
+                } else {
+                    zero
+                }
+            } else {
+                let fraction_bits = exponent_bias + significand_bits - exponent;
+                let fraction_mask = (one << fraction_bits) - one;
+                let fraction = a_abs & fraction_mask;
+                if fraction == zero {
+                    return a;
+                }
+                let half_bit = one << (fraction_bits - 1);
+                let odd = ((a_abs & significand_mask) | implicit_bit) >> fraction_bits & one;
+                let round = increment(negative,
+                                      odd != zero,
+                                      fraction & half_bit != zero,
+                                      fraction & (half_bit - one) != zero);
+                // The increment may carry into the exponent, which is correct
+                (a_abs & !fraction_mask) + ((round as <$ty as Float>::Int) << fraction_bits)
+            };
+
+            if inexact {
+                exception::raise(exception::INEXACT);
+            }
+
+            <$ty>::from_repr(result | sign)
+        }
+    }
+}
+
+integral!(integralf: f32);
+integral!(integral: f64);
+
+/// Returns the largest integral value not greater than `a`
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn floorf(a: f32) -> f32 {
+    integralf(a, |sign, _, half, sticky| sign && (half || sticky), false)
+}
+
+/// Returns the largest integral value not greater than `a`
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn floor(a: f64) -> f64 {
+    integral(a, |sign, _, half, sticky| sign && (half || sticky), false)
+}
+
+/// Returns the smallest integral value not less than `a`
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn ceilf(a: f32) -> f32 {
+    integralf(a, |sign, _, half, sticky| !sign && (half || sticky), false)
+}
+
+/// Returns the smallest integral value not less than `a`
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn ceil(a: f64) -> f64 {
+    integral(a, |sign, _, half, sticky| !sign && (half || sticky), false)
+}
+
+/// Returns the integral value nearest to `a` that is not larger in magnitude
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn truncf(a: f32) -> f32 {
+    integralf(a, |_, _, _, _| false, false)
+}
+
+/// Returns the integral value nearest to `a` that is not larger in magnitude
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn trunc(a: f64) -> f64 {
+    integral(a, |_, _, _, _| false, false)
+}
+
+/// Returns the integral value nearest to `a`, with halfway cases rounded away from zero
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn roundf(a: f32) -> f32 {
+    integralf(a, |_, _, half, _| half, false)
+}
+
+/// Returns the integral value nearest to `a`, with halfway cases rounded away from zero
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn round(a: f64) -> f64 {
+    integral(a, |_, _, half, _| half, false)
+}
+
+/// Rounds `a` to an integral value in the current rounding mode, raising the inexact
+/// exception if that changes it
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn rintf(a: f32) -> f32 {
+    let mode = round::mode();
+    integralf(a, |sign, odd, half, sticky| mode.increment(sign, odd, half, sticky), true)
+}
+
+/// Rounds `a` to an integral value in the current rounding mode, raising the inexact
+/// exception if that changes it
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn rint(a: f64) -> f64 {
+    let mode = round::mode();
+    integral(a, |sign, odd, half, sticky| mode.increment(sign, odd, half, sticky), true)
+}
+
+/// Rounds `a` to an integral value in the current rounding mode, without raising the
+/// inexact exception
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn nearbyintf(a: f32) -> f32 {
+    let mode = round::mode();
+    integralf(a, |sign, odd, half, sticky| mode.increment(sign, odd, half, sticky), false)
+}
+
+/// Rounds `a` to an integral value in the current rounding mode, without raising the
+/// inexact exception
+#[cfg_attr(all(feature = "rounding-functions", not(test)), no_mangle)]
+pub extern "C" fn nearbyint(a: f64) -> f64 {
+    let mode = round::mode();
+    integral(a, |sign, odd, half, sticky| mode.increment(sign, odd, half, sticky), false)
+}
@@ -0,0 +1,127 @@
+use float::Float;
+use float::exception;
+use float::nan;
+
+// NOTE(feature) Like `float::integral` these are C library functions that a hosted libm defines as
+// strong symbols, so they are only exported under their C names with the `math` feature
+
+// The algorithms are those of FreeBSD's msun (and musl), which derive from Sun's fdlibm: reduce
+// `x = 2^k*(1 + f)` with `sqrt(2)/2 < 1 + f < sqrt(2)`, and approximate
+// `log(1 + f) = 2*atanh(s) = 2*s + s*R(s^2)`, where `s = f/(2 + f)`, with a polynomial `R`
+
+/// Returns the natural logarithm of `x`, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn logf(x: f32) -> f32 {
+    const LN2_HI: f32 = 6.9313812256e-01; // 0x3f317180
+    const LN2_LO: f32 = 9.0580006145e-06; // 0x3717f7d1
+    // |(log(1 + s) - log(1 - s))/s - Lg(s)| < 2^-34.24 on [-0.1716, 0.1716]
+    const LG1: f32 = 0.66666662693; // 0x3f2aaaaa
+    const LG2: f32 = 0.40000972152; // 0x3eccce13
+    const LG3: f32 = 0.28498786688; // 0x3e91e9ee
+    const LG4: f32 = 0.24279078841; // 0x3e789e26
+
+    let mut rep = x.repr();
+    let mut k = 0;
+    if rep & 0x7fff_ffff > 0x7f80_0000 {
+        return nan::quietf(x);
+    }
+    if rep < 0x0080_0000 || rep >> 31 != 0 {
+        // x < 2^-126, including negative numbers
+        if rep << 1 == 0 {
+            // log(+/-0) = -infinity
+            exception::raise(exception::DIVIDE_BY_ZERO);
+            return f32::from_repr(0xff80_0000);
+        }
+        if rep >> 31 != 0 {
+            // log(negative) = NaN
+            exception::raise(exception::INVALID);
+            return f32::from_repr(0x7fc0_0000);
+        }
+        // Denormal: scale x up
+        k -= 25;
+        rep = (x * 33554432.0).repr(); // 2^25
+    } else if rep == 0x7f80_0000 {
+        // log(infinity) = infinity
+        return x;
+    } else if rep == 0x3f80_0000 {
+        return 0.0;
+    }
+
+    // Reduce x into [sqrt(2)/2, sqrt(2)]
+    rep += 0x3f80_0000 - 0x3f35_04f3;
+    k += (rep >> 23) as i32 - 0x7f;
+    rep = (rep & 0x007f_ffff) + 0x3f35_04f3;
+    let x = f32::from_repr(rep);
+
+    let f = x - 1.0;
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let w = z * z;
+    let t1 = w * (LG2 + w * LG4);
+    let t2 = z * (LG1 + w * LG3);
+    let r = t2 + t1;
+    let hfsq = 0.5 * f * f;
+    let dk = k as f32;
+    s * (hfsq + r) + dk * LN2_LO - hfsq + f + dk * LN2_HI
+}
+
+/// Returns the natural logarithm of `x`, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn log(x: f64) -> f64 {
+    const LN2_HI: f64 = 6.93147180369123816490e-01; // 0x3fe62e42_fee00000
+    const LN2_LO: f64 = 1.90821492927058770002e-10; // 0x3dea39ef_35793c76
+    // |(log(1 + s) - log(1 - s))/s - Lg(s)| < 2^-58.45 on [-0.1716, 0.1716]
+    const LG1: f64 = 6.666666666666735130e-01; // 0x3fe55555_55555593
+    const LG2: f64 = 3.999999999940941908e-01; // 0x3fd99999_9997fa04
+    const LG3: f64 = 2.857142874366239149e-01; // 0x3fd24924_94229359
+    const LG4: f64 = 2.222219843214978396e-01; // 0x3fcc71c5_1d8e78af
+    const LG5: f64 = 1.818357216161805012e-01; // 0x3fc74664_96cb03de
+    const LG6: f64 = 1.531383769920937332e-01; // 0x3fc39a09_d078c69f
+    const LG7: f64 = 1.479819860511658591e-01; // 0x3fc2f112_df3e5244
+
+    let mut rep = x.repr();
+    let mut hi = (rep >> 32) as u32;
+    let mut k = 0;
+    if rep & 0x7fff_ffff_ffff_ffff > 0x7ff0_0000_0000_0000 {
+        return nan::quiet(x);
+    }
+    if hi < 0x0010_0000 || hi >> 31 != 0 {
+        // x < 2^-1022, including negative numbers
+        if rep << 1 == 0 {
+            // log(+/-0) = -infinity
+            exception::raise(exception::DIVIDE_BY_ZERO);
+            return f64::from_repr(0xfff0_0000_0000_0000);
+        }
+        if hi >> 31 != 0 {
+            // log(negative) = NaN
+            exception::raise(exception::INVALID);
+            return f64::from_repr(0x7ff8_0000_0000_0000);
+        }
+        // Denormal: scale x up
+        k -= 54;
+        rep = (x * 18014398509481984.0).repr(); // 2^54
+        hi = (rep >> 32) as u32;
+    } else if rep == 0x7ff0_0000_0000_0000 {
+        // log(infinity) = infinity
+        return x;
+    } else if rep == 0x3ff0_0000_0000_0000 {
+        return 0.0;
+    }
+
+    // Reduce x into [sqrt(2)/2, sqrt(2)]
+    hi += 0x3ff0_0000 - 0x3fe6_a09e;
+    k += (hi >> 20) as i32 - 0x3ff;
+    hi = (hi & 0x000f_ffff) + 0x3fe6_a09e;
+    let x = f64::from_repr((hi as u64) << 32 | (rep & 0xffff_ffff));
+
+    let f = x - 1.0;
+    let hfsq = 0.5 * f * f;
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let w = z * z;
+    let t1 = w * (LG2 + w * (LG4 + w * LG6));
+    let t2 = z * (LG1 + w * (LG3 + w * (LG5 + w * LG7)));
+    let r = t2 + t1;
+    let dk = k as f64;
+    s * (hfsq + r) + dk * LN2_LO - hfsq + f + dk * LN2_HI
+}
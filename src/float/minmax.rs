@@ -0,0 +1,82 @@
+use float::Float;
+use float::exception;
+use float::nan;
+
+// NOTE(feature) Like `float::fma` these are C library functions, which LLVM calls for
+// `llvm.minnum`, `llvm.maxnum`, `llvm.minimum` and `llvm.maximum` on targets without such
+// instructions, so they are only exported under their C names with the `math` feature
+macro_rules! minmax {
+    ($(#[$attr:meta])*
+     | $intrinsic:ident: $ty:ty, maximum: $maximum:expr, propagate_nan: $propagate_nan:expr) => {
+        $(#[$attr])*
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $intrinsic(a: $ty, b: $ty) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let a_abs = a_rep & abs_mask;
+            let b_abs = b_rep & abs_mask;
+
+            let a_nan = a_abs > inf_rep;
+            let b_nan = b_abs > inf_rep;
+            if a_nan || b_nan {
+                // Signaling NaN operands are invalid, and give a quiet NaN even if the other
+                // operand is a number
+                let signaling = (a_nan && a_abs & quiet_bit == zero) ||
+                    (b_nan && b_abs & quiet_bit == zero);
+                if signaling {
+                    exception::raise(exception::INVALID);
+                }
+                if !$propagate_nan && !signaling {
+                    // A quiet NaN is treated as missing data
+                    if !a_nan {
+                        return a;
+                    }
+                    if !b_nan {
+                        return b;
+                    }
+                }
+                let nan_rep = if a_nan { a_rep } else { b_rep };
+                return <$ty>::from_repr(nan::propagate(nan_rep | quiet_bit, qnan_rep));
+            }
+
+            // Order the numbers by their representations, with -0 below +0
+            let a_less = if (a_rep ^ b_rep) & sign_bit != zero {
+                a_rep & sign_bit != zero
+            } else if a_rep & sign_bit != zero {
+                a_abs > b_abs
+            } else {
+                a_abs < b_abs
+            };
+
+            if a_less != $maximum { a } else { b }
+        }
+    }
+}
+
+minmax!(/// Returns the smaller of `a` and `b`, or the number if the other one is a quiet NaN
+        | fminf: f32, maximum: false, propagate_nan: false);
+minmax!(/// Returns the smaller of `a` and `b`, or the number if the other one is a quiet NaN
+        | fmin: f64, maximum: false, propagate_nan: false);
+minmax!(/// Returns the larger of `a` and `b`, or the number if the other one is a quiet NaN
+        | fmaxf: f32, maximum: true, propagate_nan: false);
+minmax!(/// Returns the larger of `a` and `b`, or the number if the other one is a quiet NaN
+        | fmax: f64, maximum: true, propagate_nan: false);
+minmax!(/// Returns the smaller of `a` and `b`, or a NaN if either is one
+        | fminimumf: f32, maximum: false, propagate_nan: true);
+minmax!(/// Returns the smaller of `a` and `b`, or a NaN if either is one
+        | fminimum: f64, maximum: false, propagate_nan: true);
+minmax!(/// Returns the larger of `a` and `b`, or a NaN if either is one
+        | fmaximumf: f32, maximum: true, propagate_nan: true);
+minmax!(/// Returns the larger of `a` and `b`, or a NaN if either is one
+        | fmaximum: f64, maximum: true, propagate_nan: true);
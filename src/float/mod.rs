@@ -1,11 +1,103 @@
 use core::mem;
 
+use int::LargeInt;
+
+// NOTE(aapcs) compiler-rt gives every builtin the base AAPCS calling convention on ARM (see
+// `COMPILER_RT_ABI`), even on hard float targets
+macro_rules! intrinsic {
+    ($(#[$attr:meta])*
+     | $intrinsic:ident($($arg:ident: $aty:ty),*) -> $rty:ty $body:block) => {
+        $(#[$attr])*
+        #[cfg(target_arch = "arm")]
+        #[cfg_attr(not(test), no_mangle)]
+        pub extern "aapcs" fn $intrinsic($($arg: $aty),*) -> $rty $body
+
+        $(#[$attr])*
+        #[cfg(not(target_arch = "arm"))]
+        #[cfg_attr(not(test), no_mangle)]
+        pub extern "C" fn $intrinsic($($arg: $aty),*) -> $rty $body
+    }
+}
+
 pub mod conv;
+pub mod cmp;
+pub mod complex;
 pub mod add;
 pub mod pow;
 pub mod sub;
 pub mod mul;
+pub mod neg;
 pub mod div;
+pub mod extend;
+pub mod trunc;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_env = "msvc")))]
+pub mod xf;
+pub mod fenv;
+pub mod round;
+pub mod nan;
+pub mod flush;
+pub mod scale;
+pub mod exception;
+pub mod fma;
+pub mod sqrt;
+pub mod fmod;
+pub mod integral;
+pub mod minmax;
+pub mod exp;
+pub mod log;
+pub mod trig;
+
+/// A half precision (IEEE 754 binary16) floating point number
+///
+/// Rust has no primitive type for these so the intrinsics take and return the bit representation
+/// (`u16`); this type only exists to plug that representation into `Float`
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct f16(u16);
+
+/// A quadruple precision (IEEE 754 binary128) floating point number
+///
+/// Rust has no primitive type for these either. This is a 128-bit vector so that values are passed
+/// in the same registers C uses for them (`long double` on AArch64, `__float128` on x86_64)
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+#[repr(simd)]
+pub struct f128(u64, u64);
+
+/// An x87 extended precision floating point number
+///
+/// Unlike the IEEE formats the integer bit of the significand is stored explicitly, so this
+/// doesn't fit `Float`. The layout matches `long double` in memory on x86 targets
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct f80 {
+    significand: u64,
+    sign_exponent: u16,
+}
+
+impl f80 {
+    /// Returns the bit representation (in the low 80 bits)
+    pub fn repr(self) -> u128 {
+        (self.sign_exponent as u128) << 64 | self.significand as u128
+    }
+    /// Returns a value from its bit representation (the low 80 bits)
+    pub fn from_repr(a: u128) -> f80 {
+        f80 { significand: a as u64, sign_exponent: (a >> 64) as u16 }
+    }
+    fn from_parts(sign: bool, exponent: u16, significand: u64) -> f80 {
+        f80 { significand: significand, sign_exponent: (sign as u16) << 15 | exponent }
+    }
+    fn sign(self) -> bool {
+        self.sign_exponent >> 15 != 0
+    }
+    fn exponent(self) -> u16 {
+        self.sign_exponent & 0x7fff
+    }
+    fn significand(self) -> u64 {
+        self.significand
+    }
+}
 
 /// Trait for some basic operations on floats
 pub trait Float: Sized + Copy {
@@ -65,16 +157,55 @@ pub trait Float: Sized + Copy {
     /// Returns `a*b` with `(high_part, low_part)`
     fn wide_multiply(a: Self::Int, b: Self::Int) -> (Self::Int, Self::Int);
 
-    /// Returns `(hi,lo) << count`
+    /// Returns `(hi,lo) << count`, for any `count` below the width of `Self::Int` including 0
     fn wide_left_shift(a: Self::Int, b: Self::Int, count: i32) -> (Self::Int, Self::Int);
 
-    /// Returns `(hi,lo) >> count` (with sticky)
+    /// Returns `(hi,lo) >> count`, for any `count` including 0 and the width of `Self::Int`, with
+    /// any bits shifted out ORed into the lowest bit (sticky). The sticky bit is a single bit, like
+    /// compiler-rt's `bool`, so that it can't reach the guard and round bits above it
     fn wide_right_shift_with_sticky(a: Self::Int, b: Self::Int, count: u32) -> (Self::Int, Self::Int);
 
-    /// Compute reciprocal (not-corrected)
+    /// Returns an estimate of the reciprocal of the significand `b`, as a fixed point number with
+    /// all of its bits after the binary point. The estimate may be a few ulps too large
     fn compute_reciprocal(b: Self::Int) -> Self::Int;
 }
 
+/// The reciprocals of the midpoints of the intervals `[1 + i/128, 1 + (i + 1)/128)`, rounded to
+/// nine bits after the binary point, without the bit of `0.5` that all of them have
+static RECIPROCALS: [u8; 128] = [
+    0xfe, 0xfa, 0xf6, 0xf2, 0xef, 0xeb, 0xe7, 0xe4, 0xe0, 0xdd, 0xd9, 0xd6,
+    0xd2, 0xcf, 0xcc, 0xc9, 0xc6, 0xc2, 0xbf, 0xbc, 0xb9, 0xb6, 0xb3, 0xb1,
+    0xae, 0xab, 0xa8, 0xa5, 0xa3, 0xa0, 0x9d, 0x9b, 0x98, 0x96, 0x93, 0x91,
+    0x8e, 0x8c, 0x8a, 0x87, 0x85, 0x83, 0x80, 0x7e, 0x7c, 0x7a, 0x78, 0x75,
+    0x73, 0x71, 0x6f, 0x6d, 0x6b, 0x69, 0x67, 0x65, 0x63, 0x61, 0x5f, 0x5e,
+    0x5c, 0x5a, 0x58, 0x56, 0x54, 0x53, 0x51, 0x4f, 0x4e, 0x4c, 0x4a, 0x49,
+    0x47, 0x45, 0x44, 0x42, 0x40, 0x3f, 0x3d, 0x3c, 0x3a, 0x39, 0x37, 0x36,
+    0x34, 0x33, 0x32, 0x30, 0x2f, 0x2d, 0x2c, 0x2b, 0x29, 0x28, 0x27, 0x25,
+    0x24, 0x23, 0x21, 0x20, 0x1f, 0x1e, 0x1c, 0x1b, 0x1a, 0x19, 0x17, 0x16,
+    0x15, 0x14, 0x13, 0x12, 0x10, 0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09,
+    0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01,
+];
+
+/// Returns an estimate of the reciprocal of `q31b`, a number in [1.0, 2.0) with 31 bits after the
+/// binary point, with all of its bits after the binary point. The estimate is at most 5 ulps below
+/// and 2 ulps above the exact reciprocal, and doesn't wrap around for 1.0
+fn reciprocal32(q31b: u32) -> u32 {
+    // The table gives about 8 correct bits, and each Newton-Raphson iteration,
+    // `r = r * (2 - r * b)`, doubles them. The first iteration only needs the top 16 bits of b,
+    // which keeps its products within 32 bits (a `mul` takes 1 cycle on ARMv7-M, an `umull` up to
+    // 5): with 9 bits after the binary point in `r0` and 15 in `b`, `2 - r0 * b` has 24 (modulo
+    // 2^25), of which the top 17 are kept, and the new estimate 25
+    let r0 = RECIPROCALS[(q31b >> 24) as usize & 0x7f] as u32 | 0x100;
+    let correction = (r0 * (q31b >> 16)).wrapping_neg() & 0x01ff_ffff;
+    let reciprocal = (r0 * (correction >> 8)) << 7;
+    let correction = <f32 as Float>::wide_multiply(reciprocal, q31b).0.wrapping_neg();
+    let reciprocal = {
+        let (hi, lo) = <f32 as Float>::wide_multiply(reciprocal, correction);
+        (lo >> 31) | (hi << 1)
+    };
+    reciprocal
+}
+
 // FIXME: Some of this can be removed if RFC Issue #1424 is resolved
 //        https://github.com/rust-lang/rfcs/issues/1424
 impl Float for f32 {
@@ -131,6 +262,10 @@ impl Float for f32 {
     }
 
     fn wide_left_shift(a: Self::Int, b: Self::Int, count: i32) -> (Self::Int, Self::Int) {
+        if count == 0 {
+            return (a, b);
+        }
+
         let hi = a << count;
         let lohi = b >> (32 - count);
         let lolo = b << count;
@@ -139,44 +274,35 @@ impl Float for f32 {
     }
 
     fn wide_right_shift_with_sticky(a: Self::Int, b: Self::Int, count: u32) -> (Self::Int, Self::Int) {
-        if count < 32 {
-            let sticky = b << (32 - count);
+        if count == 0 {
+            (a, b)
+        }
+        else if count < 32 {
+            let sticky = (b << (32 - count) != 0) as Self::Int;
             let hi = a >> count;
             let lohi = a << (32 - count);
             let lolo = b >> count;
 
-            (hi, lohi|lolo|sticky)
+            (hi, lohi | lolo | sticky)
+        }
+        else if count == 32 {
+            let sticky = (b != 0) as Self::Int;
+
+            (0, a | sticky)
         }
         else if count < 64 {
-            let sticky = a << (64 - count) | b;
+            let sticky = ((a << (64 - count)) | b != 0) as Self::Int;
             let lohi = a >> (count - 32);
 
             (0, lohi | sticky)
         }
         else {
-            (0, a | b)
+            (0, ((a | b) != 0) as Self::Int)
         }
     }
 
     fn compute_reciprocal(b: Self::Int) -> Self::Int {
-        let q31b = b << 8;
-        let reciprocal = 0x7504F333u32 - q31b;
-        let correction = Self::wide_multiply(reciprocal, q31b).0.wrapping_neg();
-        let reciprocal = {
-            let (hi, lo) = Self::wide_multiply(reciprocal, correction);
-            (lo >> 31) | (hi << 1)
-        };
-        let correction = Self::wide_multiply(reciprocal, q31b).0.wrapping_neg();
-        let reciprocal = {
-            let (hi, lo) = Self::wide_multiply(reciprocal, correction);
-            (lo >> 31) | (hi << 1)
-        };
-        let correction = Self::wide_multiply(reciprocal, q31b).0.wrapping_neg();
-        let reciprocal = {
-            let (hi, lo) = Self::wide_multiply(reciprocal, correction);
-            (lo >> 31) | (hi << 1)
-        };
-        reciprocal
+        reciprocal32(b << 8)
     }
 }
 impl Float for f64 {
@@ -244,6 +370,10 @@ impl Float for f64 {
     }
 
     fn wide_left_shift(a: Self::Int, b: Self::Int, count: i32) -> (Self::Int, Self::Int) {
+        if count == 0 {
+            return (a, b);
+        }
+
         let hi = a << count;
         let lohi = b >> (64 - count);
         let lolo = b << count;
@@ -252,52 +382,45 @@ impl Float for f64 {
     }
 
     fn wide_right_shift_with_sticky(a: Self::Int, b: Self::Int, count: u32) -> (Self::Int, Self::Int) {
-        if count < 64 {
-            let sticky = b << (64 - count);
+        if count == 0 {
+            (a, b)
+        }
+        else if count < 64 {
+            let sticky = (b << (64 - count) != 0) as Self::Int;
             let hi = a >> count;
             let lohi = a << (64 - count);
             let lolo = b >> count;
 
-            (hi, lohi|lolo|sticky)
+            (hi, lohi | lolo | sticky)
+        }
+        else if count == 64 {
+            let sticky = (b != 0) as Self::Int;
+
+            (0, a | sticky)
         }
         else if count < 128 {
-            let sticky = a << (128 - count) | b;
+            let sticky = ((a << (128 - count)) | b != 0) as Self::Int;
             let lohi = a >> (count - 64);
 
             (0, lohi | sticky)
         }
         else {
-            (0, a | b)
+            (0, ((a | b) != 0) as Self::Int)
         }
     }
 
     fn compute_reciprocal(b: Self::Int) -> Self::Int {
         let q31b    : u32 = (b >> 21) as u32;
-        let recip32       = 0x7504F333u32 - q31b;
-        
-        let correction32 = <f32 as Float>::wide_multiply(recip32, q31b).0.wrapping_neg();
-        let recip32 = {
-            let (hi, lo) = <f32 as Float>::wide_multiply(recip32, correction32);
-            (lo >> 31) | (hi << 1)
-        };
-        let correction32 = <f32 as Float>::wide_multiply(recip32, q31b).0.wrapping_neg();
-        let recip32 = {
-            let (hi, lo) = <f32 as Float>::wide_multiply(recip32, correction32);
-            (lo >> 31) | (hi << 1)
-        };
-        let correction32 = <f32 as Float>::wide_multiply(recip32, q31b).0.wrapping_neg();
-        let recip32 = {
-            let (hi, lo) = <f32 as Float>::wide_multiply(recip32, correction32);
-            ((lo >> 31) | (hi << 1) - 1)
-        };
+        let recip32       = reciprocal32(q31b).wrapping_sub(1);
 
-        let q64blo = b << 11;
+        // Refine the 32-bit estimate using the low 32 bits of the Q63 significand
+        let q63blo = (b << 11) as u32;
         let correction = {
             let r32_64 = recip32 as u64;
             let r1 = r32_64 * q31b as u64;
-            let r2 = (r32_64 * q64blo) >> 32;
-            
-            (r1 + r2).wrapping_neg()
+            let r2 = (r32_64 * q63blo as u64) >> 32;
+
+            r1.wrapping_add(r2).wrapping_neg()
         };
         let (chi, clo) = ((correction >> 32) as u32, (correction & 0xFFFFFFFFu64) as u32);
 
@@ -308,6 +431,237 @@ impl Float for f64 {
         let r1lo_64 = r1lo as u64;
         let r2hi_64 = r2hi as u64;
 
-        ((r1hi_64 << 32 | r1lo_64) + r2hi_64)
+        (r1hi_64 << 32 | r1lo_64).wrapping_add(r2hi_64)
+    }
+}
+impl Float for f16 {
+    type Int = u16;
+    fn bits() -> u32 {
+        16
+    }
+    fn significand_bits() -> u32 {
+        10
+    }
+    fn implicit_bit() -> Self::Int {
+        1 << Self::significand_bits()
+    }
+    fn sign_mask() -> Self::Int {
+        1 << (Self::bits() - 1)
+    }
+    fn significand_mask() -> Self::Int {
+        (1 << Self::significand_bits()) - 1
+    }
+    fn exponent_mask() -> Self::Int {
+        !(Self::sign_mask() | Self::significand_mask())
+    }
+    fn repr(self) -> Self::Int {
+        self.0
+    }
+    #[cfg(test)]
+    fn eq_repr(self, rhs: Self) -> bool {
+        let is_nan = |x: Self| (x.repr() & !Self::sign_mask()) > Self::exponent_mask();
+        if is_nan(self) && is_nan(rhs) {
+            true
+        } else {
+            self.repr() == rhs.repr()
+        }
+    }
+    fn from_repr(a: Self::Int) -> Self {
+        f16(a)
+    }
+    fn from_parts(sign: bool, exponent: Self::Int, significand: Self::Int) -> Self {
+        Self::from_repr(((sign as Self::Int) << (Self::bits() - 1)) |
+            ((exponent << Self::significand_bits()) & Self::exponent_mask()) |
+            (significand & Self::significand_mask()))
+    }
+    fn normalize(significand: Self::Int) -> (i32, Self::Int) {
+        let shift = significand.leading_zeros()
+            .wrapping_sub((1u16 << Self::significand_bits()).leading_zeros());
+        (1i32.wrapping_sub(shift as i32), significand << shift as Self::Int)
+    }
+
+    fn wide_multiply(a: Self::Int, b: Self::Int) -> (Self::Int, Self::Int) {
+        let product = (a as u32) * (b as u32);
+        let hi : u16 = (product >> 16) as u16;
+        let lo : u16 = (product & 0xFFFF) as u16;
+
+        (hi, lo)
+    }
+
+    fn wide_left_shift(a: Self::Int, b: Self::Int, count: i32) -> (Self::Int, Self::Int) {
+        if count == 0 {
+            return (a, b);
+        }
+
+        let hi = a << count;
+        let lohi = b >> (16 - count);
+        let lolo = b << count;
+
+        (hi | lohi, lolo)
+    }
+
+    fn wide_right_shift_with_sticky(a: Self::Int, b: Self::Int, count: u32) -> (Self::Int, Self::Int) {
+        if count == 0 {
+            (a, b)
+        }
+        else if count < 16 {
+            let sticky = (b << (16 - count) != 0) as Self::Int;
+            let hi = a >> count;
+            let lohi = a << (16 - count);
+            let lolo = b >> count;
+
+            (hi, lohi | lolo | sticky)
+        }
+        else if count == 16 {
+            let sticky = (b != 0) as Self::Int;
+
+            (0, a | sticky)
+        }
+        else if count < 32 {
+            let sticky = ((a << (32 - count)) | b != 0) as Self::Int;
+            let lohi = a >> (count - 16);
+
+            (0, lohi | sticky)
+        }
+        else {
+            (0, ((a | b) != 0) as Self::Int)
+        }
+    }
+
+    fn compute_reciprocal(b: Self::Int) -> Self::Int {
+        // Truncating the single precision estimate keeps it from being too large
+        let b32 = (b as u32) << (<f32 as Float>::significand_bits() - Self::significand_bits());
+        (<f32 as Float>::compute_reciprocal(b32) >> 16) as u16
+    }
+}
+impl Float for f128 {
+    type Int = u128;
+    fn bits() -> u32 {
+        128
+    }
+    fn significand_bits() -> u32 {
+        112
+    }
+    fn implicit_bit() -> Self::Int {
+        1 << Self::significand_bits()
+    }
+    fn sign_mask() -> Self::Int {
+        1 << (Self::bits() - 1)
+    }
+    fn significand_mask() -> Self::Int {
+        (1 << Self::significand_bits()) - 1
+    }
+    fn exponent_mask() -> Self::Int {
+        !(Self::sign_mask() | Self::significand_mask())
+    }
+    fn repr(self) -> Self::Int {
+        unsafe { mem::transmute(self) }
+    }
+    #[cfg(test)]
+    fn eq_repr(self, rhs: Self) -> bool {
+        let is_nan = |x: Self| (x.repr() & !Self::sign_mask()) > Self::exponent_mask();
+        if is_nan(self) && is_nan(rhs) {
+            true
+        } else {
+            self.repr() == rhs.repr()
+        }
+    }
+    fn from_repr(a: Self::Int) -> Self {
+        unsafe { mem::transmute(a) }
+    }
+    fn from_parts(sign: bool, exponent: Self::Int, significand: Self::Int) -> Self {
+        Self::from_repr(((sign as Self::Int) << (Self::bits() - 1)) |
+            ((exponent << Self::significand_bits()) & Self::exponent_mask()) |
+            (significand & Self::significand_mask()))
+    }
+    fn normalize(significand: Self::Int) -> (i32, Self::Int) {
+        let shift = significand.leading_zeros()
+            .wrapping_sub((1u128 << Self::significand_bits()).leading_zeros());
+        (1i32.wrapping_sub(shift as i32), significand << shift as Self::Int)
+    }
+
+    fn wide_multiply(a: Self::Int, b: Self::Int) -> (Self::Int, Self::Int) {
+        let (ahi, alo) = (a.high() as u128, a.low() as u128);
+        let (bhi, blo) = (b.high() as u128, b.low() as u128);
+
+        let plolo = alo * blo;
+        let plohi = alo * bhi;
+        let philo = ahi * blo;
+        let phihi = ahi * bhi;
+
+        let r0 = plolo.low();
+        let r1 = plolo.high() as u128 + plohi.low() as u128 + philo.low() as u128;
+
+        let lo = <u128 as LargeInt>::from_parts(r0, r1.low());
+        let hi = phihi + plohi.high() as u128 + philo.high() as u128 + r1.high() as u128;
+
+        (hi, lo)
+    }
+
+    fn wide_left_shift(a: Self::Int, b: Self::Int, count: i32) -> (Self::Int, Self::Int) {
+        if count == 0 {
+            return (a, b);
+        }
+
+        let hi = a << count;
+        let lohi = b >> (128 - count);
+        let lolo = b << count;
+
+        (hi | lohi, lolo)
+    }
+
+    fn wide_right_shift_with_sticky(a: Self::Int, b: Self::Int, count: u32) -> (Self::Int, Self::Int) {
+        if count == 0 {
+            (a, b)
+        }
+        else if count < 128 {
+            let sticky = (b << (128 - count) != 0) as Self::Int;
+            let hi = a >> count;
+            let lohi = a << (128 - count);
+            let lolo = b >> count;
+
+            (hi, lohi | lolo | sticky)
+        }
+        else if count == 128 {
+            let sticky = (b != 0) as Self::Int;
+
+            (0, a | sticky)
+        }
+        else if count < 256 {
+            let sticky = ((a << (256 - count)) | b != 0) as Self::Int;
+            let lohi = a >> (count - 128);
+
+            (0, lohi | sticky)
+        }
+        else {
+            (0, ((a | b) != 0) as Self::Int)
+        }
+    }
+
+    fn compute_reciprocal(b: Self::Int) -> Self::Int {
+        // Start from the double precision estimate of the reciprocal of the truncated significand.
+        // Truncation limits it to about 52 correct bits, so two Newton-Raphson iterations,
+        // `r = r * (2 - r * b)`, are needed to get the full width
+        let b64 = (b >> (Self::significand_bits() - <f64 as Float>::significand_bits())) as u64;
+        let q127b = b << (Self::bits() - 1 - Self::significand_bits());
+
+        let reciprocal = (<f64 as Float>::compute_reciprocal(b64) as u128) << 64;
+        let correction = {
+            let (hi, lo) = Self::wide_multiply(reciprocal, q127b);
+            ((hi << 1) | (lo >> 127)).wrapping_neg()
+        };
+        let reciprocal = {
+            let (hi, lo) = Self::wide_multiply(reciprocal, correction);
+            (hi << 1) | (lo >> 127)
+        };
+        let correction = {
+            let (hi, lo) = Self::wide_multiply(reciprocal, q127b);
+            ((hi << 1) | (lo >> 127)).wrapping_neg()
+        };
+        let reciprocal = {
+            let (hi, lo) = Self::wide_multiply(reciprocal, correction);
+            (hi << 1) | (lo >> 127)
+        };
+        reciprocal
     }
 }
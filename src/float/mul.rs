@@ -1,145 +1,230 @@
-use core::num::Wrapping;
-
-use float::Float;
+use float::{f128, Float};
+use float::exception;
+use float::flush::{self, Flush};
+use float::nan;
+use float::round;
 
 macro_rules! mul {
     ($abi:tt, $intrinsic:ident: $ty:ty) => {
         /// Returns `a * b`
-        #[allow(unused_parens)]
         #[cfg_attr(not(test), no_mangle)]
         pub extern $abi fn $intrinsic(a: $ty, b: $ty) -> $ty {
-            let one = Wrapping(1 as <$ty as Float>::Int);
-            let zero = Wrapping(0 as <$ty as Float>::Int);
-
-            let bits =             Wrapping(<$ty>::bits() as <$ty as Float>::Int);
-            let significand_bits = Wrapping(<$ty>::significand_bits() as <$ty as Float>::Int);
-            let exponent_bits =    bits - significand_bits - one;
-            let max_exponent =     (one << exponent_bits.0 as usize) - one;
-
-            let implicit_bit =     one << significand_bits.0 as usize;
-            let significand_mask = implicit_bit - one;
-            let sign_bit =         one << (significand_bits + exponent_bits).0 as usize;
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let bits =             <$ty>::bits();
+            let significand_bits = <$ty>::significand_bits();
+            let exponent_bits =    <$ty>::exponent_bits();
+            let max_exponent =     <$ty>::exponent_max();
+            let exponent_bias =    <$ty>::exponent_bias();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
             let abs_mask =         sign_bit - one;
-            let exponent_mask =    abs_mask ^ significand_mask;
+            let exponent_mask =    <$ty>::exponent_mask();
             let inf_rep =          exponent_mask;
             let quiet_bit =        implicit_bit >> 1;
             let qnan_rep =         exponent_mask | quiet_bit;
-            let exponent_bias =    max_exponent >> 1;
 
-            let a_rep = Wrapping(a.repr());
-            let b_rep = Wrapping(b.repr());
-            let a_exponent = Wrapping((a_rep >> significand_bits.0 as usize & max_exponent).0 as <$ty as Float>::Int);
-            let b_exponent = Wrapping((b_rep >> significand_bits.0 as usize & max_exponent).0 as <$ty as Float>::Int);
+            // Subnormal operands are zeros in the flush to zero mode
+            let ftz = flush::enabled();
+            let (a, b) = if ftz { (a.flush(), b.flush()) } else { (a, b) };
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let a_exponent = (a_rep >> significand_bits) as u32 & max_exponent;
+            let b_exponent = (b_rep >> significand_bits) as u32 & max_exponent;
+            let product_sign = (a_rep ^ b_rep) & sign_bit;
+
             let mut a_significand = a_rep & significand_mask;
             let mut b_significand = b_rep & significand_mask;
-            let a_abs = a_rep & abs_mask;
-            let b_abs = b_rep & abs_mask;
-            let product_sign = (a_rep ^ b_rep) & sign_bit;
-            let mut scale : i32 = 0;
+            let mut scale = 0;
+
+            // Detect if a or b is zero, denormal, infinity, or NaN.
+            if a_exponent.wrapping_sub(1) >= max_exponent - 1 ||
+                b_exponent.wrapping_sub(1) >= max_exponent - 1 {
+                let a_abs = a_rep & abs_mask;
+                let b_abs = b_rep & abs_mask;
+
+                // Signaling NaN operands are invalid
+                if (a_abs > inf_rep && a_abs & quiet_bit == zero) ||
+                    (b_abs > inf_rep && b_abs & quiet_bit == zero) {
+                    exception::raise(exception::INVALID);
+                }
 
-            // Detect if a or b is zero, denormal, infinity or NaN
-            if a_exponent - one >= max_exponent - one ||
-                b_exponent - one >= max_exponent - one {
-                
                 // NaN * anything = qNaN
                 if a_abs > inf_rep {
-                    return (<$ty as Float>::from_repr((a_abs | quiet_bit).0));
+                    return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
                 }
                 // anything * NaN = qNaN
                 if b_abs > inf_rep {
-                    return (<$ty as Float>::from_repr((b_abs | quiet_bit).0));
+                    return <$ty>::from_repr(nan::propagate(b_rep | quiet_bit, qnan_rep));
                 }
 
                 if a_abs == inf_rep {
                     if b_abs != zero {
-                        return (<$ty as Float>::from_repr((a_abs | product_sign).0));
+                        // infinity * non-zero = +/- infinity
+                        return <$ty>::from_repr(a_abs | product_sign);
+                    } else {
+                        // infinity * zero = NaN
+                        exception::raise(exception::INVALID);
+                        return <$ty>::from_repr(qnan_rep);
                     }
-                    return (<$ty as Float>::from_repr(qnan_rep.0));
                 }
 
                 if b_abs == inf_rep {
                     if a_abs != zero {
-                        return (<$ty as Float>::from_repr((b_abs | product_sign).0));
+                        // non-zero * infinity = +/- infinity
+                        return <$ty>::from_repr(b_abs | product_sign);
+                    } else {
+                        // zero * infinity = NaN
+                        exception::raise(exception::INVALID);
+                        return <$ty>::from_repr(qnan_rep);
                     }
-                    return (<$ty as Float>::from_repr(qnan_rep.0));
                 }
 
+                // zero * anything = +/- zero
                 if a_abs == zero {
-                    return (<$ty as Float>::from_repr(product_sign.0));
+                    return <$ty>::from_repr(product_sign);
                 }
 
+                // anything * zero = +/- zero
                 if b_abs == zero {
-                    return (<$ty as Float>::from_repr(product_sign.0));
+                    return <$ty>::from_repr(product_sign);
                 }
 
+                // one or both of a or b is denormal, the other (if applicable) is a
+                // normal number.  Renormalize one or both of a and b, and set scale to
+                // include the necessary exponent adjustment.
                 if a_abs < implicit_bit {
-                    let (sc, ns) = (<$ty as Float>::normalize(a_significand.0));
-
-                    scale += sc;
-                    a_significand.0 = ns;
+                    let (exponent, significand) = <$ty>::normalize(a_significand);
+                    scale += exponent;
+                    a_significand = significand;
                 }
 
                 if b_abs < implicit_bit {
-                    let (sc, ns) = (<$ty as Float>::normalize(b_significand.0));
-
-                    scale += sc;
-                    b_significand.0 = ns;
+                    let (exponent, significand) = <$ty>::normalize(b_significand);
+                    scale += exponent;
+                    b_significand = significand;
                 }
-            } 
+            }
 
+            // Or in the implicit significand bit.  (If we fell through from the
+            // denormal path it was already set by normalize( ), but setting it twice
+            // won't hurt anything.)
             a_significand |= implicit_bit;
             b_significand |= implicit_bit;
 
-            let prod = <$ty as Float>::wide_multiply(a_significand.0,
-                (b_significand << exponent_bits.0 as usize).0);
-            let mut product_hi = Wrapping(prod.0);
-            let mut product_lo = Wrapping(prod.1);
+            // Get the significand of a*b.  Before multiplying the significands, shift
+            // one of them left to left-align it in the field.  Thus, the product will
+            // have (exponent_bits + 2) integral digits, all but two of which must be
+            // zero.  Normalizing this result is just a conditional left-shift by one
+            // and bumping the exponent accordingly.
+            let (mut product_hi, mut product_lo) =
+                <$ty>::wide_multiply(a_significand, b_significand << exponent_bits);
 
-            let product_exponent = Wrapping(a_exponent.0 + b_exponent.0 + (scale as <$ty as Float>::Int) - exponent_bias.0);
+            let mut product_exponent: i32 =
+                a_exponent as i32 + b_exponent as i32 - exponent_bias as i32 + scale;
 
-            if product_hi & implicit_bit != zero {
-                product_hi += one;
+            // Normalize the significand, adjust exponent if needed.
+            if (product_hi & implicit_bit) != zero {
+                product_exponent += 1;
             } else {
-                let prod = <$ty as Float>::wide_left_shift(product_hi.0, product_lo.0, 1);
-                product_hi.0 = prod.0;
-                product_lo.0 = prod.1;
+                let (hi, lo) = <$ty>::wide_left_shift(product_hi, product_lo, 1);
+                product_hi = hi;
+                product_lo = lo;
             }
 
-            if product_exponent >= max_exponent {
-                return (<$ty as Float>::from_repr((inf_rep | product_sign).0));
-            }
+            let mode = round::mode();
+            let negative = product_sign != zero;
 
-            if product_exponent <= zero {
-                let shift = one - product_exponent;
+            // If we have overflowed the type, return +/- infinity, or the largest finite number
+            // if the rounding direction is toward zero for this sign.
+            if product_exponent >= max_exponent as i32 {
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+                if mode.overflows_to_infinity(negative) {
+                    return <$ty>::from_repr(inf_rep | product_sign);
+                }
+                return <$ty>::from_repr((inf_rep - one) | product_sign);
+            }
 
-                if shift.0 as u32 >= <$ty as Float>::bits() {
-                    return (<$ty as Float>::from_repr(product_sign.0));
+            if product_exponent <= 0 {
+                if ftz {
+                    exception::raise(exception::UNDERFLOW);
+                    return <$ty>::from_repr(product_sign);
                 }
 
-                let prod = <$ty as Float>::wide_right_shift_with_sticky(product_hi.0, product_lo.0, shift.0 as u32);
+                // Result is denormal before rounding
+                //
+                // If the result is so small that it is less than half of the smallest
+                // denormal, return a zero of the appropriate sign (or the smallest denormal
+                // when rounding away from zero).  Mathematically there is no need to handle
+                // this case separately, but we make it a special case to simplify the shift
+                // logic.
+                let shift = (1 - product_exponent) as u32;
+                if shift >= bits {
+                    exception::raise(exception::UNDERFLOW | exception::INEXACT);
+                    let round = mode.increment(negative, false, false, true);
+                    return <$ty>::from_repr(round as <$ty as Float>::Int | product_sign);
+                }
 
-                product_hi.0 = prod.0;
-                product_lo.0 = prod.1;
+                // Otherwise, shift the significand of the result so that the round
+                // bit is the high bit of product_lo.
+                let (hi, lo) = <$ty>::wide_right_shift_with_sticky(product_hi, product_lo, shift);
+                product_hi = hi;
+                product_lo = lo;
             } else {
+                // Result is normal before rounding; insert the exponent.
                 product_hi &= significand_mask;
-                product_hi |= product_exponent << significand_bits.0 as usize;
+                product_hi |= (product_exponent as <$ty as Float>::Int) << significand_bits;
             }
 
+            // Insert the sign of the result:
             product_hi |= product_sign;
 
-            if product_lo > sign_bit {
+            // Final rounding.  The final result may overflow to infinity, or underflow
+            // to zero, but those are the correct results in those cases.  The high bit of
+            // product_lo is the round bit and the rest are sticky.
+            if mode.increment(negative,
+                              product_hi & one != zero,
+                              product_lo & sign_bit != zero,
+                              product_lo & !sign_bit != zero) {
                 product_hi += one;
             }
 
-            if product_lo == sign_bit {
-                product_hi += (product_hi & one);
+            // Underflow is detected before rounding, as on ARM: the result is tiny if it was
+            // denormal before rounding, even if rounding made it the smallest normal number
+            if product_lo != zero {
+                let mut flags = exception::INEXACT;
+                if product_exponent <= 0 {
+                    flags |= exception::UNDERFLOW;
+                }
+                if product_hi & abs_mask == inf_rep {
+                    flags |= exception::OVERFLOW;
+                }
+                exception::raise(flags);
             }
 
-            <$ty as Float>::from_repr(product_hi.0)
+            <$ty>::from_repr(product_hi)
         }
     }
 }
 
+#[cfg(target_arch = "arm")]
+mul!("aapcs", __mulsf3: f32);
+
+#[cfg(not(target_arch = "arm"))]
 mul!("C", __mulsf3: f32);
+
+#[cfg(target_arch = "arm")]
+mul!("aapcs", __muldf3: f64);
+
+#[cfg(not(target_arch = "arm"))]
 mul!("C", __muldf3: f64);
 
+#[cfg(target_arch = "arm")]
+mul!("aapcs", __multf3: f128);
+
+#[cfg(not(target_arch = "arm"))]
+mul!("C", __multf3: f128);
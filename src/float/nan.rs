@@ -0,0 +1,73 @@
+use float::Float;
+use float::exception;
+use float::fenv::{__fe_getnan, __fe_setnan};
+
+/// How the soft float routines choose the NaN they return for NaN operands
+///
+/// Either way an operation that makes a NaN out of numbers, like `0/0`, `inf - inf` or `sqrt(-1)`,
+/// returns the default NaN: the sign bit is clear and only the quiet bit of the significand is
+/// set (the x87 formats of `float::xf` use the x87's negative "real indefinite" instead). The sign
+/// bit operations (`float::neg`) copy NaNs as they are under both modes.
+///
+/// The discriminants are the values `__fe_getnan` returns
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub enum Nan {
+    /// Return the first NaN operand, quieted, keeping its sign and payload, like x86 (the
+    /// default)
+    Propagate = 0,
+    /// Always return the default NaN, like ARM's default NaN mode
+    Default = 1,
+}
+
+impl Nan {
+    /// Returns the mode with the discriminant `mode`, or `None` if there is no such mode
+    pub fn from_raw(mode: i32) -> Option<Nan> {
+        match mode {
+            0 => Some(Nan::Propagate),
+            1 => Some(Nan::Default),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the current NaN mode. An unknown mode reported by an overriding `__fe_getnan` is
+/// treated as `Nan::Propagate`
+pub fn mode() -> Nan {
+    Nan::from_raw(__fe_getnan()).unwrap_or(Nan::Propagate)
+}
+
+/// Sets the current NaN mode, if `__fe_setnan` hasn't been overridden
+pub fn set_mode(mode: Nan) {
+    __fe_setnan(mode as i32);
+}
+
+/// Returns the representation of the NaN an operation gives, where `quieted` is its first NaN
+/// operand with the quiet bit set and `default` the default NaN
+pub fn propagate<T>(quieted: T, default: T) -> T {
+    match mode() {
+        Nan::Propagate => quieted,
+        Nan::Default => default,
+    }
+}
+
+macro_rules! quiet {
+    ($(#[$attr:meta])* | $name:ident: $ty:ty) => {
+        $(#[$attr])*
+        pub fn $name(x: $ty) -> $ty {
+            let quiet_bit = <$ty>::implicit_bit() >> 1;
+            let rep = x.repr();
+            if rep & quiet_bit == 0 {
+                exception::raise(exception::INVALID);
+            }
+            <$ty>::from_repr(propagate(rep | quiet_bit, <$ty>::exponent_mask() | quiet_bit))
+        }
+    }
+}
+
+quiet!(/// Returns the result of a C library function whose first NaN argument is `x`, raising the
+       /// invalid exception if `x` is signaling
+       | quietf: f32);
+quiet!(/// Returns the result of a C library function whose first NaN argument is `x`, raising the
+       /// invalid exception if `x` is signaling
+       | quiet: f64);
@@ -0,0 +1,56 @@
+use float::Float;
+
+intrinsic!(/// Returns `-a`
+           | __negsf2(a: f32) -> f32 {
+               f32::from_repr(a.repr() ^ f32::sign_mask())
+           });
+
+intrinsic!(/// Returns `-a`
+           | __negdf2(a: f64) -> f64 {
+               f64::from_repr(a.repr() ^ f64::sign_mask())
+           });
+
+// NOTE(vfp) Unlike the rest, the `*vfp` builtins use the platform calling convention, which passes
+// floats in VFP registers. Like compiler-rt, which only builds them for `eabihf` targets, we only
+// provide them where there are VFP registers
+
+/// Returns `-a`
+#[cfg(all(target_arch = "arm", target_feature = "vfp2"))]
+#[cfg_attr(not(test), no_mangle)]
+pub extern "C" fn __negsf2vfp(a: f32) -> f32 {
+    f32::from_repr(a.repr() ^ f32::sign_mask())
+}
+
+/// Returns `-a`
+#[cfg(all(target_arch = "arm", target_feature = "vfp2"))]
+#[cfg_attr(not(test), no_mangle)]
+pub extern "C" fn __negdf2vfp(a: f64) -> f64 {
+    f64::from_repr(a.repr() ^ f64::sign_mask())
+}
+
+// NOTE(feature) Like `float::fma` these are C library functions, so they are only exported under
+// their C names with the `math` feature. They only touch the sign bit, even of NaNs.
+
+/// Returns `a` with the sign of `b`
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn copysignf(a: f32, b: f32) -> f32 {
+    f32::from_repr((a.repr() & !f32::sign_mask()) | (b.repr() & f32::sign_mask()))
+}
+
+/// Returns `a` with the sign of `b`
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn copysign(a: f64, b: f64) -> f64 {
+    f64::from_repr((a.repr() & !f64::sign_mask()) | (b.repr() & f64::sign_mask()))
+}
+
+/// Returns the absolute value of `a`
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn fabsf(a: f32) -> f32 {
+    f32::from_repr(a.repr() & !f32::sign_mask())
+}
+
+/// Returns the absolute value of `a`
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn fabs(a: f64) -> f64 {
+    f64::from_repr(a.repr() & !f64::sign_mask())
+}
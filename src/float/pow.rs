@@ -1,24 +1,89 @@
+use float::Float;
+use float::exception;
+use float::exp::exp;
+use float::log::log;
+use float::nan;
+use float::scale::{frexp, frexpf, scalbn, scalbnf};
+use float::sqrt::sqrt;
+
+// NOTE(fpu) The products and the reciprocal use `*` and `/`, which is the FPU where there is one.
+// Without an FPU, and with the `rounding-modes` feature, they call our intrinsics directly
+// instead, so that they see the rounding mode and raise exceptions
+macro_rules! op {
+    ($name:ident: $ty:ty, $op:tt, $intrinsic:path) => {
+        #[cfg(any(feature = "rounding-modes",
+                  all(target_arch = "arm", not(target_feature = "vfp2"))))]
+        fn $name(a: $ty, b: $ty) -> $ty {
+            $intrinsic(a, b)
+        }
+
+        #[cfg(not(any(feature = "rounding-modes",
+                      all(target_arch = "arm", not(target_feature = "vfp2")))))]
+        fn $name(a: $ty, b: $ty) -> $ty {
+            a $op b
+        }
+    }
+}
+
+op!(mulf: f32, *, ::float::mul::__mulsf3);
+op!(mul: f64, *, ::float::mul::__muldf3);
+op!(divf: f32, /, ::float::div::__divsf3);
+op!(div: f64, /, ::float::div::__divdf3);
+
 macro_rules! pow {
-    ($intrinsic:ident: $fty:ty, $ity:ident) => {
+    ($intrinsic:ident: $fty:ty, $ity:ident,
+     $mul:ident, $div:ident, $frexp:ident, $scalbn:ident) => {
         /// Returns `a` raised to the power `b`
         #[cfg_attr(not(test), no_mangle)]
         pub extern "C" fn $intrinsic(a: $fty, b: $ity) -> $fty {
             let (mut a, mut b) = (a, b);
             let recip = b < 0;
+
+            // For a negative power `r` would overflow or underflow before it is inverted, so `r`
+            // and `a` are kept as fractions and their exponents apart, in `r_exp` and `a_exp`.
+            // The fractions are in [0.5, 1), which keeps the products normal and the same as
+            // without the scaling.
+            let scale = |x: $fty, exp: &mut i32| if recip {
+                let mut e = 0;
+                let fraction = $frexp(x, &mut e);
+                *exp = exp.saturating_add(e);
+                fraction
+            } else {
+                x
+            };
             let mut r: $fty = 1.0;
+            let mut r_exp = 0i32;
+            let mut a_exp = 0i32;
+            a = scale(a, &mut a_exp);
             loop {
                 if (b & 1) != 0 {
-                    r *= a;
+                    r_exp = r_exp.saturating_add(a_exp);
+                    r = scale($mul(r, a), &mut r_exp);
                 }
                 b = sdiv!($ity, b, 2);
                 if b == 0 {
                     break;
                 }
-                a *= a;
+                a_exp = a_exp.saturating_mul(2);
+                a = scale($mul(a, a), &mut a_exp);
             }
 
             if recip {
-                1.0 / r
+                // `2^-r_exp / r`, with the power of two split between the operands so that both
+                // stay normal and the quotient is rounded once.  Past the exponent range the
+                // quotient is far out of range anyway.
+                let max = <$fty>::exponent_bias() as i32;
+                let clamp = |n: i32, min: i32| if n < min {
+                    min
+                } else if n > max {
+                    max
+                } else {
+                    n
+                };
+                let n = 0i32.saturating_sub(r_exp);
+                let n_a = clamp(n, 1 - max);
+                let n_b = clamp(n_a.saturating_sub(n), 2 - max);
+                $div($scalbn(1.0, n_a), $scalbn(r, n_b))
             } else {
                 r
             }
@@ -26,5 +91,369 @@ macro_rules! pow {
     }
 }
 
-pow!(__powisf2: f32, i32);
-pow!(__powidf2: f64, i32);
+pow!(__powisf2: f32, i32, mulf, divf, frexpf, scalbnf);
+pow!(__powidf2: f64, i32, mul, div, frexp, scalbn);
+
+// NOTE(feature) Like `float::exp` these are C library functions that a hosted libm defines as
+// strong symbols, so they are only exported under their C names with the `math` feature
+
+/// Returns `x` raised to the power `y`, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn powf(x: f32, y: f32) -> f32 {
+    let x_rep = x.repr();
+    let y_rep = y.repr();
+    let x_abs = x_rep & 0x7fff_ffff;
+    let y_abs = y_rep & 0x7fff_ffff;
+
+    // x^0 = 1 and 1^y = 1, even if the other operand is NaN
+    if y_abs == 0 || x_rep == 0x3f80_0000 {
+        return 1.0;
+    }
+    if x_abs > 0x7f80_0000 || y_abs > 0x7f80_0000 {
+        // The first NaN operand propagates, but a signaling second one is still invalid
+        if x_abs <= 0x7f80_0000 {
+            return nan::quietf(y);
+        }
+        if y_abs > 0x7f80_0000 {
+            nan::quietf(y);
+        }
+        return nan::quietf(x);
+    }
+
+    // 0 if `y` isn't an integer, 1 if it is an odd integer and 2 if it is an even integer
+    let y_int = if y_abs >= 0x4b80_0000 {
+        // |y| >= 2^24
+        2
+    } else if y_abs >= 0x3f80_0000 {
+        let shift = 23 - ((y_abs >> 23) as i32 - 0x7f);
+        let j = y_abs >> shift;
+        if j << shift == y_abs { 2 - (j & 1) } else { 0 }
+    } else {
+        0
+    };
+    let negative = x_rep >> 31 != 0 && y_int == 1;
+
+    if y_abs == 0x7f80_0000 {
+        return if x_abs == 0x3f80_0000 {
+            // (-1)^(+/-infinity) = 1
+            1.0
+        } else if (x_abs > 0x3f80_0000) == (y_rep >> 31 == 0) {
+            f32::from_repr(0x7f80_0000)
+        } else {
+            0.0
+        };
+    }
+    if x_abs == 0 || x_abs == 0x7f80_0000 {
+        // |x|^y, or 1/|x|^-y, is 0 or infinity
+        let z = if (x_abs == 0) == (y_rep >> 31 == 0) {
+            0.0
+        } else {
+            if x_abs == 0 {
+                exception::raise(exception::DIVIDE_BY_ZERO);
+            }
+            f32::from_repr(0x7f80_0000)
+        };
+        return if negative { -z } else { z };
+    }
+    if x_rep >> 31 != 0 && y_int == 0 {
+        // (negative)^(non-integer) = NaN
+        exception::raise(exception::INVALID);
+        return f32::from_repr(0x7fc0_0000);
+    }
+
+    // The double precision logarithm and exponential leave an error far below an ulp of the result,
+    // which is rounded once, by the conversion
+    let z = exp(y as f64 * log(f32::from_repr(x_abs) as f64)) as f32;
+    if negative { -z } else { z }
+}
+
+/// Returns `x` raised to the power `y`, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn pow(x: f64, y: f64) -> f64 {
+    // This is fdlibm's `__ieee754_pow`, by way of FreeBSD's msun and musl: `log2(|x|)` is computed
+    // in extra precision as `t1 + t2`, multiplied by `y` as `p_h + p_l`, and the result is
+    // `2^(p_h + p_l)`
+    const BP: [f64; 2] = [1.0, 1.5];
+    const DP_H: [f64; 2] = [0.0, 5.84962487220764160156e-01]; // 0x3fe2b803_40000000
+    const DP_L: [f64; 2] = [0.0, 1.35003920212974897128e-08]; // 0x3e4cfdeb_43cfd006
+    const TWO53: f64 = 9007199254740992.0; // 0x43400000_00000000
+    // Polynomial coefficients for (3/2)*(log(x) - 2s - 2/3*s^3)
+    const L1: f64 = 5.99999999999994648725e-01; // 0x3fe33333_33333303
+    const L2: f64 = 4.28571428578550184252e-01; // 0x3fdb6db6_db6fabff
+    const L3: f64 = 3.33333329818377432918e-01; // 0x3fd55555_518f264d
+    const L4: f64 = 2.72728123808534006489e-01; // 0x3fd17460_a91d4101
+    const L5: f64 = 2.30660745775561754067e-01; // 0x3fcd864a_93c9db65
+    const L6: f64 = 2.06975017800338417784e-01; // 0x3fca7e28_4a454eef
+    const P1: f64 = 1.66666666666666019037e-01; // 0x3fc55555_5555553e
+    const P2: f64 = -2.77777777770155933842e-03; // 0xbf66c16c_16bebd93
+    const P3: f64 = 6.61375632143793436117e-05; // 0x3f11566a_af25de2c
+    const P4: f64 = -1.65339022054652515390e-06; // 0xbebbbd41_c5d26bf1
+    const P5: f64 = 4.13813679705723846039e-08; // 0x3e663769_72bea4d0
+    const LG2: f64 = 6.93147180559945286227e-01; // 0x3fe62e42_fefa39ef
+    const LG2_H: f64 = 6.93147182464599609375e-01; // 0x3fe62e43_00000000
+    const LG2_L: f64 = -1.90465429995776804525e-09; // 0xbe205c61_0ca86c39
+    // -(1024 - log2(overflow threshold + 0.5 ulp))
+    const OVT: f64 = 8.0085662595372944372e-17;
+    // 2/(3*ln2), and its split into 24 bits and the rest
+    const CP: f64 = 9.61796693925975554329e-01; // 0x3feec709_dc3a03fd
+    const CP_H: f64 = 9.61796700954437255859e-01; // 0x3feec709_e0000000
+    const CP_L: f64 = -7.02846165095275826516e-09; // 0xbe3e2fe0_145b01f5
+    // 1/ln2, and its split into 24 bits and the rest
+    const IVLN2: f64 = 1.44269504088896338700e+00; // 0x3ff71547_652b82fe
+    const IVLN2_H: f64 = 1.44269502162933349609e+00; // 0x3ff71547_60000000
+    const IVLN2_L: f64 = 1.92596299112661746887e-08; // 0x3e54ae0b_f85ddf44
+
+    let high = |x: f64| (x.repr() >> 32) as i32;
+    let low = |x: f64| x.repr() as u32;
+    let with_high = |x: f64, hi: i32| {
+        f64::from_repr((hi as u32 as u64) << 32 | (x.repr() & 0xffff_ffff))
+    };
+    let clear_low = |x: f64| f64::from_repr(x.repr() & 0xffff_ffff_0000_0000);
+    // +/-infinity or +/-0 (or the nearest finite number in the rounding direction), raising the
+    // overflow or underflow exception
+    let overflow = |s: f64| scalbn(s, 2000);
+    let underflow = |s: f64| scalbn(s, -2000);
+
+    let (hx, lx) = (high(x), low(x));
+    let (hy, ly) = (high(y), low(y));
+    let mut ix = hx & 0x7fff_ffff;
+    let iy = hy & 0x7fff_ffff;
+
+    // x^0 = 1 and 1^y = 1, even if the other operand is NaN
+    if iy as u32 | ly == 0 || (hx == 0x3ff0_0000 && lx == 0) {
+        return 1.0;
+    }
+    let x_nan = ix > 0x7ff0_0000 || (ix == 0x7ff0_0000 && lx != 0);
+    let y_nan = iy > 0x7ff0_0000 || (iy == 0x7ff0_0000 && ly != 0);
+    if x_nan || y_nan {
+        // The first NaN operand propagates, but a signaling second one is still invalid
+        if !x_nan {
+            return nan::quiet(y);
+        }
+        if y_nan {
+            nan::quiet(y);
+        }
+        return nan::quiet(x);
+    }
+
+    // 0 if `y` isn't an integer, 1 if it is an odd integer and 2 if it is an even integer, when
+    // `x` is negative
+    let mut y_int = 0;
+    if hx < 0 {
+        if iy >= 0x4340_0000 {
+            // |y| >= 2^53
+            y_int = 2;
+        } else if iy >= 0x3ff0_0000 {
+            let k = (iy >> 20) - 0x3ff;
+            if k > 20 {
+                let j = ly >> (52 - k);
+                if j << (52 - k) == ly {
+                    y_int = 2 - (j & 1) as i32;
+                }
+            } else if ly == 0 {
+                let j = iy >> (20 - k);
+                if j << (20 - k) == iy {
+                    y_int = 2 - (j & 1);
+                }
+            }
+        }
+    }
+
+    if ly == 0 {
+        if iy == 0x7ff0_0000 {
+            // y = +/-infinity
+            return if (ix - 0x3ff0_0000) as u32 | lx == 0 {
+                // (-1)^(+/-infinity) = 1
+                1.0
+            } else if ix >= 0x3ff0_0000 {
+                // (|x| > 1)^(+/-infinity) = infinity or 0
+                if hy >= 0 { y } else { 0.0 }
+            } else {
+                // (|x| < 1)^(+/-infinity) = 0 or infinity
+                if hy >= 0 { 0.0 } else { -y }
+            };
+        }
+        if iy == 0x3ff0_0000 {
+            // y = +/-1
+            return if hy >= 0 { x } else { 1.0 / x };
+        }
+        if hy == 0x4000_0000 {
+            // y = 2
+            return x * x;
+        }
+        if hy == 0x3fe0_0000 && hx >= 0 {
+            // y = 0.5 and x >= +0
+            return sqrt(x);
+        }
+    }
+
+    let mut ax = f64::from_repr(x.repr() & 0x7fff_ffff_ffff_ffff);
+    if lx == 0 && (ix == 0x7ff0_0000 || ix == 0 || ix == 0x3ff0_0000) {
+        // x = +/-0, +/-infinity or +/-1
+        let mut z = ax;
+        if hy < 0 {
+            if ix == 0 {
+                exception::raise(exception::DIVIDE_BY_ZERO);
+                z = f64::from_repr(0x7ff0_0000_0000_0000);
+            } else {
+                z = 1.0 / z;
+            }
+        }
+        if hx < 0 {
+            if ix - 0x3ff0_0000 | y_int == 0 {
+                // (-1)^(non-integer) = NaN
+                exception::raise(exception::INVALID);
+                z = f64::from_repr(0x7ff8_0000_0000_0000);
+            } else if y_int == 1 {
+                // (negative)^(odd integer) = -(|x|^y)
+                z = -z;
+            }
+        }
+        return z;
+    }
+
+    let mut s = 1.0;
+    if hx < 0 {
+        if y_int == 0 {
+            // (negative)^(non-integer) = NaN
+            exception::raise(exception::INVALID);
+            return f64::from_repr(0x7ff8_0000_0000_0000);
+        }
+        if y_int == 1 {
+            s = -1.0;
+        }
+    }
+
+    let (t1, t2);
+    if iy > 0x41e0_0000 {
+        // |y| > 2^31
+        if iy > 0x43f0_0000 {
+            // |y| > 2^64 overflows or underflows unless x = 1
+            if ix <= 0x3fef_ffff {
+                return if hy < 0 { overflow(1.0) } else { underflow(1.0) };
+            }
+            if ix >= 0x3ff0_0000 {
+                return if hy > 0 { overflow(1.0) } else { underflow(1.0) };
+            }
+        }
+        // So does |y| > 2^31 unless x is close to 1
+        if ix < 0x3fef_ffff {
+            return if hy < 0 { overflow(s) } else { underflow(s) };
+        }
+        if ix > 0x3ff0_0000 {
+            return if hy > 0 { overflow(s) } else { underflow(s) };
+        }
+        // Now |1 - x| <= 2^-20, and log(x) = t - t^2/2 + t^3/3 - t^4/4 suffices
+        let t = ax - 1.0;
+        let w = (t * t) * (0.5 - t * (0.3333333333333333333333 - t * 0.25));
+        let u = IVLN2_H * t;
+        let v = t * IVLN2_L - w * IVLN2;
+        t1 = clear_low(u + v);
+        t2 = v - (t1 - u);
+    } else {
+        let mut n = 0;
+        if ix < 0x0010_0000 {
+            // Denormal: scale x up
+            ax *= TWO53;
+            n -= 53;
+            ix = high(ax);
+        }
+        n += (ix >> 20) - 0x3ff;
+        let j = ix & 0x000f_ffff;
+        // Pick the interval
+        ix = j | 0x3ff0_0000;
+        let k = if j <= 0x3988e {
+            // |x| < sqrt(3/2)
+            0
+        } else if j < 0xbb67a {
+            // |x| < sqrt(3)
+            1
+        } else {
+            n += 1;
+            ix -= 0x0010_0000;
+            0
+        };
+        ax = with_high(ax, ix);
+
+        // ss = s_h + s_l = (x - 1)/(x + 1) or (x - 1.5)/(x + 1.5)
+        let u = ax - BP[k];
+        let v = 1.0 / (ax + BP[k]);
+        let ss = u * v;
+        let s_h = clear_low(ss);
+        // t_h = ax + BP[k], high part
+        let t_h = with_high(0.0, ((ix >> 1) | 0x2000_0000) + 0x0008_0000 + ((k as i32) << 18));
+        let t_l = ax - (t_h - BP[k]);
+        let s_l = v * ((u - s_h * t_h) - s_h * t_l);
+
+        // log(ax)
+        let s2 = ss * ss;
+        let mut r = s2 * s2 * (L1 + s2 * (L2 + s2 * (L3 + s2 * (L4 + s2 * (L5 + s2 * L6)))));
+        r += s_l * (s_h + ss);
+        let s2 = s_h * s_h;
+        let t_h = clear_low(3.0 + s2 + r);
+        let t_l = r - ((t_h - 3.0) - s2);
+        // u + v = ss*(1 + ...)
+        let u = s_h * t_h;
+        let v = s_l * t_h + t_l * ss;
+        // 2/(3*ln2)*(ss + ...)
+        let p_h = clear_low(u + v);
+        let p_l = v - (p_h - u);
+        let z_h = CP_H * p_h;
+        let z_l = CP_L * p_h + p_l * CP + DP_L[k];
+        // log2(ax) = (ss + ...)*2/(3*ln2) = n + DP_H + z_h + z_l
+        let t = n as f64;
+        t1 = clear_low(((z_h + z_l) + DP_H[k]) + t);
+        t2 = z_l - (((t1 - t) - DP_H[k]) - z_h);
+    }
+
+    // Split y into y1 + y2 and compute (y1 + y2)*(t1 + t2)
+    let y1 = clear_low(y);
+    let p_l = (y - y1) * t1 + y * t2;
+    let mut p_h = y1 * t1;
+    let z = p_l + p_h;
+    let (j, i) = (high(z), low(z));
+    if j >= 0x4090_0000 {
+        // z >= 1024
+        if (j - 0x4090_0000) as u32 | i != 0 || p_l + OVT > z - p_h {
+            return overflow(s);
+        }
+    } else if j & 0x7fff_ffff >= 0x4090_cc00 {
+        // z <= -1075
+        if (j as u32).wrapping_sub(0xc090_cc00) | i != 0 || p_l <= z - p_h {
+            return underflow(s);
+        }
+    }
+
+    // 2^(p_h + p_l)
+    let i = j & 0x7fff_ffff;
+    let mut k = (i >> 20) - 0x3ff;
+    let mut n = 0;
+    if i > 0x3fe0_0000 {
+        // |z| > 0.5: n = [z + 0.5]
+        n = j + (0x0010_0000 >> (k + 1));
+        k = ((n & 0x7fff_ffff) >> 20) - 0x3ff;
+        let t = with_high(0.0, n & !(0x000f_ffff >> k));
+        n = ((n & 0x000f_ffff) | 0x0010_0000) >> (20 - k);
+        if j < 0 {
+            n = -n;
+        }
+        p_h -= t;
+    }
+    let t = clear_low(p_l + p_h);
+    let u = t * LG2_H;
+    let v = (p_l - (t - p_h)) * LG2 + t * LG2_L;
+    let z = u + v;
+    let w = v - (z - u);
+    let t = z * z;
+    let t1 = z - t * (P1 + t * (P2 + t * (P3 + t * (P4 + t * P5))));
+    let r = (z * t1) / (t1 - 2.0) - (w + z * w);
+    let z = 1.0 - (r - z);
+    let j = high(z) + (n << 20);
+    let z = if j >> 20 <= 0 {
+        // Denormal result
+        scalbn(z, n)
+    } else {
+        with_high(z, j)
+    };
+    s * z
+}
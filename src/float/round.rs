@@ -0,0 +1,83 @@
+#[cfg(feature = "rounding-modes")]
+use float::fenv::{__fe_getround, __fe_setround};
+
+/// An IEEE 754 rounding direction
+///
+/// The discriminants are the values `__fe_getround` returns, which match compiler-rt's
+/// `CRT_FE_*` constants
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub enum Round {
+    /// Round to nearest, ties to even (the default)
+    ToNearest = 0,
+    /// Round toward negative infinity
+    Downward = 1,
+    /// Round toward positive infinity
+    Upward = 2,
+    /// Round toward zero
+    TowardZero = 3,
+}
+
+impl Round {
+    /// Returns the mode with the discriminant `mode`, or `None` if there is no such mode
+    pub fn from_raw(mode: i32) -> Option<Round> {
+        match mode {
+            0 => Some(Round::ToNearest),
+            1 => Some(Round::Downward),
+            2 => Some(Round::Upward),
+            3 => Some(Round::TowardZero),
+            _ => None,
+        }
+    }
+
+    /// Decides whether a magnitude that has been cut to its last kept bit must be incremented by
+    /// one unit in that place
+    ///
+    /// `odd` is the last kept bit, `half` the first bit that was shifted out and `sticky` whether
+    /// any of the bits below `half` were set, which is what `Float::wide_right_shift_with_sticky`
+    /// leaves in the low word. `sign` is the sign of the result.
+    pub fn increment(self, sign: bool, odd: bool, half: bool, sticky: bool) -> bool {
+        match self {
+            Round::ToNearest => half && (sticky || odd),
+            Round::Downward => sign && (half || sticky),
+            Round::Upward => !sign && (half || sticky),
+            Round::TowardZero => false,
+        }
+    }
+
+    /// Returns whether a result that is too large for the format becomes infinity, rather than
+    /// the largest finite number, when rounded in this direction
+    pub fn overflows_to_infinity(self, sign: bool) -> bool {
+        match self {
+            Round::ToNearest => true,
+            Round::Downward => sign,
+            Round::Upward => !sign,
+            Round::TowardZero => false,
+        }
+    }
+}
+
+/// Returns the current rounding mode. An unknown mode reported by an overriding `__fe_getround`
+/// is treated as round to nearest
+#[cfg(feature = "rounding-modes")]
+pub fn mode() -> Round {
+    Round::from_raw(__fe_getround()).unwrap_or(Round::ToNearest)
+}
+
+/// Returns the current rounding mode, which is always round to nearest without the
+/// `rounding-modes` feature
+#[cfg(not(feature = "rounding-modes"))]
+#[inline(always)]
+pub fn mode() -> Round {
+    Round::ToNearest
+}
+
+/// Sets the current rounding mode, if `__fe_setround` hasn't been overridden
+#[cfg(feature = "rounding-modes")]
+pub fn set_mode(mode: Round) {
+    __fe_setround(mode as i32);
+}
+
+/// Does nothing: without the `rounding-modes` feature the only mode is round to nearest
+#[cfg(not(feature = "rounding-modes"))]
+pub fn set_mode(_mode: Round) {}
@@ -0,0 +1,282 @@
+use float::Float;
+use float::conv::{__floatsidf, __floatsisf};
+use float::exception;
+use float::nan;
+use float::round;
+
+// NOTE(feature) Like `float::fma` these are C library functions, so they are only exported under
+// their C names with the `math` feature. The crate calls them directly.
+macro_rules! scale {
+    ($ty:ty, $int_to_float:ident:
+     $scalbn:ident, $ldexp:ident, $frexp:ident, $ilogb:ident, $logb:ident, $nextafter:ident) => {
+        /// Returns `a * 2^n`, rounded in the current rounding mode
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $scalbn(a: $ty, n: i32) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let significand_bits = <$ty>::significand_bits();
+            let max_exponent =     <$ty>::exponent_max();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let a_abs = a_rep & abs_mask;
+            let sign = a_rep & sign_bit;
+
+            if a_abs > inf_rep {
+                // Signaling NaN operands are invalid
+                if a_abs & quiet_bit == zero {
+                    exception::raise(exception::INVALID);
+                }
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+
+            // Infinities and zeros don't change
+            if a_abs == inf_rep || a_abs == zero {
+                return a;
+            }
+
+            let exponent = (a_abs >> significand_bits) as i32;
+            let (exponent, significand) = if exponent == 0 {
+                <$ty>::normalize(a_abs)
+            } else {
+                (exponent, (a_abs & significand_mask) | implicit_bit)
+            };
+
+            // Scaling by more than the width of the exponent range overflows or underflows anyway,
+            // so the limit keeps the sum from overflowing
+            let limit = (max_exponent + significand_bits) as i32 + 2;
+            let n = if n > limit { limit } else if n < -limit { -limit } else { n };
+            let exponent = exponent + n;
+
+            // If we have overflowed the type, return +/- infinity, or the largest finite number
+            // if the rounding direction is toward zero for this sign.
+            if exponent >= max_exponent as i32 {
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+                if round::mode().overflows_to_infinity(sign != zero) {
+                    return <$ty>::from_repr(inf_rep | sign);
+                }
+                return <$ty>::from_repr((inf_rep - one) | sign);
+            }
+
+            if exponent > 0 {
+                let exponent = exponent as <$ty as Float>::Int;
+                return <$ty>::from_repr(sign | (exponent << significand_bits) |
+                                        (significand & significand_mask));
+            }
+
+            // Denormal.  Shifting by more than the significand leaves only sticky bits.
+            let shift = 1 - exponent;
+            let shift = if shift > significand_bits as i32 + 2 {
+                significand_bits + 2
+            } else {
+                shift as u32
+            };
+            let half = significand >> (shift - 1) & one != zero;
+            let sticky = significand & ((one << (shift - 1)) - one) != zero;
+            let mut abs_result = significand >> shift;
+            if round::mode().increment(sign != zero, abs_result & one != zero, half, sticky) {
+                abs_result += one;
+            }
+
+            if half || sticky {
+                exception::raise(exception::UNDERFLOW | exception::INEXACT);
+            }
+
+            <$ty>::from_repr(abs_result | sign)
+        }
+
+        /// Returns `a * 2^n`, rounded in the current rounding mode, like `scalbn`
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $ldexp(a: $ty, n: i32) -> $ty {
+            $scalbn(a, n)
+        }
+
+        /// Splits `a` into a fraction, which is returned, and a power of two, which is stored in
+        /// `exp`. The magnitude of the fraction is in `[0.5, 1)`, unless `a` is a zero, infinity or
+        /// NaN, which is returned with an `exp` of zero.
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $frexp(a: $ty, exp: &mut i32) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let significand_bits = <$ty>::significand_bits();
+            let exponent_bias =    <$ty>::exponent_bias();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let a_abs = a_rep & abs_mask;
+
+            *exp = 0;
+            if a_abs > inf_rep {
+                // Signaling NaN operands are invalid
+                if a_abs & quiet_bit == zero {
+                    exception::raise(exception::INVALID);
+                }
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+            if a_abs == inf_rep || a_abs == zero {
+                return a;
+            }
+
+            let exponent = (a_abs >> significand_bits) as i32;
+            let (exponent, significand) = if exponent == 0 {
+                <$ty>::normalize(a_abs)
+            } else {
+                (exponent, a_abs & significand_mask)
+            };
+
+            *exp = exponent - exponent_bias as i32 + 1;
+            <$ty>::from_parts(a_rep & sign_bit != zero,
+                              (exponent_bias - 1) as <$ty as Float>::Int,
+                              significand)
+        }
+
+        /// Returns the exponent of `a` as an integer, raising the invalid exception for zeros
+        /// (`i32::MIN`), infinities (`i32::MAX`) and NaNs (`i32::MIN`)
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $ilogb(a: $ty) -> i32 {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let significand_bits = <$ty>::significand_bits();
+            let exponent_bias =    <$ty>::exponent_bias();
+
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+
+            let a_abs = a.repr() & abs_mask;
+
+            if a_abs == zero || a_abs > inf_rep {
+                exception::raise(exception::INVALID);
+                return ::core::i32::MIN;
+            }
+            if a_abs == inf_rep {
+                exception::raise(exception::INVALID);
+                return ::core::i32::MAX;
+            }
+
+            let exponent = (a_abs >> significand_bits) as i32;
+            let exponent = if exponent == 0 {
+                <$ty>::normalize(a_abs).0
+            } else {
+                exponent
+            };
+            exponent - exponent_bias as i32
+        }
+
+        /// Returns the exponent of `a` as a float: `-infinity` for zeros, raising the divide by
+        /// zero exception, and `+infinity` for infinities
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $logb(a: $ty) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let a_abs = a_rep & abs_mask;
+
+            if a_abs > inf_rep {
+                // Signaling NaN operands are invalid
+                if a_abs & quiet_bit == zero {
+                    exception::raise(exception::INVALID);
+                }
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+            if a_abs == zero {
+                exception::raise(exception::DIVIDE_BY_ZERO);
+                return <$ty>::from_repr(inf_rep | sign_bit);
+            }
+            if a_abs == inf_rep {
+                return <$ty>::from_repr(inf_rep);
+            }
+
+            // The exponent is small enough to convert exactly
+            $int_to_float($ilogb(a))
+        }
+
+        /// Returns the number next to `a` in the direction of `b`, or `b` if they're equal
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $nextafter(a: $ty, b: $ty) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let b_rep = b.repr();
+            let a_abs = a_rep & abs_mask;
+            let b_abs = b_rep & abs_mask;
+
+            let a_nan = a_abs > inf_rep;
+            let b_nan = b_abs > inf_rep;
+            if a_nan || b_nan {
+                // Signaling NaN operands are invalid
+                if (a_nan && a_abs & quiet_bit == zero) || (b_nan && b_abs & quiet_bit == zero) {
+                    exception::raise(exception::INVALID);
+                }
+                let nan_rep = if a_nan { a_rep } else { b_rep };
+                return <$ty>::from_repr(nan::propagate(nan_rep | quiet_bit, qnan_rep));
+            }
+
+            // Equal numbers, including zeros of opposite signs
+            if a_rep == b_rep || (a_abs == zero && b_abs == zero) {
+                return b;
+            }
+
+            let result = if a_abs == zero {
+                // The smallest denormal, with the sign of `b`
+                (b_rep & sign_bit) | one
+            } else {
+                // Whether `b` is further from zero than `a`, on the same side
+                let away = (a_rep ^ b_rep) & sign_bit == zero && b_abs > a_abs;
+                if away { a_rep + one } else { a_rep - one }
+            };
+
+            // Stepping from the largest finite number to infinity overflows, and stepping to a
+            // denormal or zero underflows
+            let result_abs = result & abs_mask;
+            if result_abs == inf_rep {
+                exception::raise(exception::OVERFLOW | exception::INEXACT);
+            } else if result_abs < implicit_bit {
+                exception::raise(exception::UNDERFLOW | exception::INEXACT);
+            }
+
+            <$ty>::from_repr(result)
+        }
+    }
+}
+
+scale!(f32, __floatsisf: scalbnf, ldexpf, frexpf, ilogbf, logbf, nextafterf);
+scale!(f64, __floatsidf: scalbn, ldexp, frexp, ilogb, logb, nextafter);
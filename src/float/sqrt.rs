@@ -0,0 +1,115 @@
+use float::{f128, Float};
+use float::exception;
+use float::nan;
+use float::round;
+
+// NOTE(feature) Like `float::fma` these are C library functions, which LLVM calls for
+// `llvm.sqrt.*` on targets without a square root instruction, so they are only exported under their
+// C names with the `math` feature
+macro_rules! sqrt {
+    ($intrinsic:ident: $ty:ty) => {
+        /// Returns the square root of `a`, rounded in the current rounding mode
+        #[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+        pub extern "C" fn $intrinsic(a: $ty) -> $ty {
+            let one: <$ty as Float>::Int = 1;
+            let zero: <$ty as Float>::Int = 0;
+
+            let significand_bits = <$ty>::significand_bits();
+            let max_exponent =     <$ty>::exponent_max();
+            let exponent_bias =    <$ty>::exponent_bias();
+
+            let implicit_bit =     <$ty>::implicit_bit();
+            let significand_mask = <$ty>::significand_mask();
+            let sign_bit =         <$ty>::sign_mask();
+            let abs_mask =         sign_bit - one;
+            let exponent_mask =    <$ty>::exponent_mask();
+            let inf_rep =          exponent_mask;
+            let quiet_bit =        implicit_bit >> 1;
+            let qnan_rep =         exponent_mask | quiet_bit;
+
+            let a_rep = a.repr();
+            let a_abs = a_rep & abs_mask;
+
+            if a_abs > inf_rep {
+                // Signaling NaN operands are invalid
+                if a_abs & quiet_bit == zero {
+                    exception::raise(exception::INVALID);
+                }
+                // sqrt(NaN) = qNaN
+                return <$ty>::from_repr(nan::propagate(a_rep | quiet_bit, qnan_rep));
+            }
+
+            // sqrt(+/- 0) = +/- 0
+            if a_abs == zero {
+                return a;
+            }
+
+            // sqrt(negative) = NaN
+            if a_rep & sign_bit != zero {
+                exception::raise(exception::INVALID);
+                return <$ty>::from_repr(qnan_rep);
+            }
+
+            // sqrt(infinity) = infinity
+            if a_abs == inf_rep {
+                return a;
+            }
+
+            let a_exponent = (a_rep >> significand_bits) as i32 & max_exponent as i32;
+            let (a_exponent, mut significand) = if a_exponent == 0 {
+                <$ty>::normalize(a_rep & significand_mask)
+            } else {
+                (a_exponent, (a_rep & significand_mask) | implicit_bit)
+            };
+
+            // Make the exponent even so that halving it is exact; the significand is then in
+            // [1.0, 4.0) and its square root in [1.0, 2.0)
+            let exponent = a_exponent - exponent_bias as i32;
+            if exponent & 1 != 0 {
+                significand <<= 1;
+            }
+            let exponent = exponent >> 1;
+
+            // Compute the square root one bit at a time, from the implicit bit down to one bit
+            // past the last bit of the significand.  `significand` holds the (doubled) remainder
+            // and `s` twice the root found so far.
+            significand <<= 1;
+            let mut root = zero;
+            let mut s = zero;
+            let mut bit = implicit_bit << 1;
+            while bit != zero {
+                let t = s + bit;
+                if t <= significand {
+                    s = t + bit;
+                    significand -= t;
+                    root += bit;
+                }
+                significand <<= 1;
+                bit >>= 1;
+            }
+
+            // The extra bit of the root is the round bit and the remainder is the sticky bit.
+            // The result is always normal, and adding the root (with its implicit bit) to the
+            // exponent lets the rounding carry into it.
+            let half = root & one != zero;
+            let sticky = significand != zero;
+            let root = root >> 1;
+            let mut abs_result =
+                ((exponent + exponent_bias as i32 - 1) as <$ty as Float>::Int) << significand_bits;
+            abs_result += root;
+            if round::mode().increment(false, root & one != zero, half, sticky) {
+                abs_result += one;
+            }
+
+            if half || sticky {
+                exception::raise(exception::INEXACT);
+            }
+
+            <$ty>::from_repr(abs_result)
+        }
+    }
+}
+
+sqrt!(sqrtf: f32);
+sqrt!(sqrt: f64);
+sqrt!(sqrtf128: f128);
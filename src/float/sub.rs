@@ -1,20 +1,37 @@
-use float::Float;
+use float::{f128, Float};
+use float::add::{__adddf3, __addsf3, __addtf3};
 
 macro_rules! sub {
     ($(#[$attr:meta])*
-     | $intrinsic:ident: $ty:ty) => {
+     | $abi:tt, $intrinsic:ident: $ty:ty, $add:ident) => {
         /// Returns `a - b`
         $(#[$attr])*
-        pub extern "C" fn $intrinsic(a: $ty, b: $ty) -> $ty {
-            a + <$ty>::from_repr(b.repr() ^ <$ty>::sign_mask())
+        pub extern $abi fn $intrinsic(a: $ty, b: $ty) -> $ty {
+            // Call the addition intrinsic rather than using `+`, which the target may implement
+            // in hardware without honouring `float::round` or raising `float::exception`s. A NaN
+            // `b` keeps its sign, as it would propagate from a subtraction
+            let b_abs = b.repr() & !<$ty>::sign_mask();
+            if b_abs > <$ty>::exponent_mask() {
+                $add(a, b)
+            } else {
+                $add(a, <$ty>::from_repr(b.repr() ^ <$ty>::sign_mask()))
+            }
         }
     }
 }
 
 sub!(#[cfg_attr(all(not(test), not(target_arch = "arm")), no_mangle)]
      #[cfg_attr(all(not(test), target_arch = "arm"), inline(always))]
-     | __subsf3: f32);
+     | "C", __subsf3: f32, __addsf3);
 
 sub!(#[cfg_attr(all(not(test), not(target_arch = "arm")), no_mangle)]
      #[cfg_attr(all(not(test), target_arch = "arm"), inline(always))]
-     | __subdf3: f64);
+     | "C", __subdf3: f64, __adddf3);
+
+#[cfg(target_arch = "arm")]
+sub!(#[cfg_attr(not(test), no_mangle)]
+     | "aapcs", __subtf3: f128, __addtf3);
+
+#[cfg(not(target_arch = "arm"))]
+sub!(#[cfg_attr(not(test), no_mangle)]
+     | "C", __subtf3: f128, __addtf3);
@@ -0,0 +1,269 @@
+use float::Float;
+use float::exception;
+use float::fma::fma;
+use float::nan;
+
+// NOTE(feature) Like `float::exp` these are C library functions that a hosted libm defines as
+// strong symbols, so they are only exported under their C names with the `math` feature
+
+// The algorithms are those of FreeBSD's msun (and musl), which derive from Sun's fdlibm: reduce
+// `x = n*pi/2 + y` with `|y| <= pi/4`, where `y` is kept as the sum of two doubles, and evaluate
+// the sine or cosine of `y`, depending on `n`, with a polynomial
+
+/// Returns the sine of `x`, in radians, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn sin(x: f64) -> f64 {
+    let ix = (x.repr() >> 32) as u32 & 0x7fff_ffff;
+
+    if ix <= 0x3fe9_21fb {
+        // |x| ~<= pi/4
+        if ix < 0x3e50_0000 {
+            // |x| < 2^-26: sin(x) rounds to x
+            return x;
+        }
+        return sin_kernel(x, 0.0, false);
+    }
+
+    if ix >= 0x7ff0_0000 {
+        // sin(NaN) is NaN and sin(infinity) is an invalid NaN
+        if x.repr() << 1 != 0xffe0_0000_0000_0000 {
+            return nan::quiet(x);
+        }
+        exception::raise(exception::INVALID);
+        return f64::from_repr(0x7ff8_0000_0000_0000);
+    }
+
+    let (n, y0, y1) = rem_pio2(x);
+    match n & 3 {
+        0 => sin_kernel(y0, y1, true),
+        1 => cos_kernel(y0, y1),
+        2 => -sin_kernel(y0, y1, true),
+        _ => -cos_kernel(y0, y1),
+    }
+}
+
+/// Returns the cosine of `x`, in radians, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn cos(x: f64) -> f64 {
+    let ix = (x.repr() >> 32) as u32 & 0x7fff_ffff;
+
+    if ix <= 0x3fe9_21fb {
+        // |x| ~<= pi/4
+        if ix < 0x3e46_a09e {
+            // |x| < 2^-27*sqrt(2): cos(x) rounds to 1
+            return 1.0;
+        }
+        return cos_kernel(x, 0.0);
+    }
+
+    if ix >= 0x7ff0_0000 {
+        // cos(NaN) is NaN and cos(infinity) is an invalid NaN
+        if x.repr() << 1 != 0xffe0_0000_0000_0000 {
+            return nan::quiet(x);
+        }
+        exception::raise(exception::INVALID);
+        return f64::from_repr(0x7ff8_0000_0000_0000);
+    }
+
+    let (n, y0, y1) = rem_pio2(x);
+    match n & 3 {
+        0 => cos_kernel(y0, y1),
+        1 => -sin_kernel(y0, y1, true),
+        2 => -cos_kernel(y0, y1),
+        _ => sin_kernel(y0, y1, true),
+    }
+}
+
+// The single precision functions use the double precision ones, whose error is so small that the
+// result, rounded once by the conversion, is within 0.5 ulp plus a tiny fraction
+
+/// Returns the sine of `x`, in radians, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn sinf(x: f32) -> f32 {
+    // A signaling NaN must not be quieted by the conversion before `sin` sees it
+    if x.repr() & 0x7fff_ffff > 0x7f80_0000 {
+        return nan::quietf(x);
+    }
+    sin(x as f64) as f32
+}
+
+/// Returns the cosine of `x`, in radians, with an error below 1 ulp
+#[cfg_attr(all(feature = "math", not(test)), no_mangle)]
+pub extern "C" fn cosf(x: f32) -> f32 {
+    // A signaling NaN must not be quieted by the conversion before `cos` sees it
+    if x.repr() & 0x7fff_ffff > 0x7f80_0000 {
+        return nan::quietf(x);
+    }
+    cos(x as f64) as f32
+}
+
+/// Returns `sin(x + y)` for `|x| ~<= pi/4` and `|y|` below half an ulp of `x`, where `y` is
+/// assumed to be zero unless `tail` is set
+fn sin_kernel(x: f64, y: f64, tail: bool) -> f64 {
+    // |sin(x)/x - (1 + x^2*S(x^2))| < 2^-58 on [-pi/4, pi/4]
+    const S1: f64 = -1.66666666666666324348e-01; // 0xbfc55555_55555549
+    const S2: f64 = 8.33333333332248946124e-03; // 0x3f811111_1110f8a6
+    const S3: f64 = -1.98412698298579493134e-04; // 0xbf2a01a0_19c161d5
+    const S4: f64 = 2.75573137070700676789e-06; // 0x3ec71de3_57b1fe7d
+    const S5: f64 = -2.50507602534068634195e-08; // 0xbe5ae5e6_8a2b9ceb
+    const S6: f64 = 1.58969099521155010221e-10; // 0x3de5d93a_5acfd57c
+
+    let z = x * x;
+    let w = z * z;
+    let r = S2 + z * (S3 + z * S4) + z * w * (S5 + z * S6);
+    let v = z * x;
+    if tail {
+        x - ((z * (0.5 * y - v * r) - y) - v * S1)
+    } else {
+        x + v * (S1 + z * r)
+    }
+}
+
+/// Returns `cos(x + y)` for `|x| ~<= pi/4` and `|y|` below half an ulp of `x`
+fn cos_kernel(x: f64, y: f64) -> f64 {
+    // |cos(x) - (1 - x^2/2 + x^4*C(x^2))| < 2^-58 on [-pi/4, pi/4]
+    const C1: f64 = 4.16666666666666019037e-02; // 0x3fa55555_5555554c
+    const C2: f64 = -1.38888888888741095749e-03; // 0xbf56c16c_16c15177
+    const C3: f64 = 2.48015872894767294178e-05; // 0x3efa01a0_19cb1590
+    const C4: f64 = -2.75573143513906633035e-07; // 0xbe927e4f_809c52ad
+    const C5: f64 = 2.08757232129817482790e-09; // 0x3e21ee9e_bdb4b1c4
+    const C6: f64 = -1.13596475577881948265e-11; // 0xbda8fae9_be8838d4
+
+    let z = x * x;
+    let w = z * z;
+    let r = z * (C1 + z * (C2 + z * C3)) + w * w * (C4 + z * (C5 + z * C6));
+    let hz = 0.5 * z;
+    let w = 1.0 - hz;
+    w + (((1.0 - w) - hz) + (z * r - x * y))
+}
+
+/// Returns `n` and `y0 + y1` such that `x = n*pi/2 + y0 + y1`, with `|y0 + y1| <= pi/4` in
+/// about 100 bits of precision, for finite `x`
+fn rem_pio2(x: f64) -> (i32, f64, f64) {
+    // 1.5*2^52, which rounds a number of magnitude below 2^51 to an integer when added to it
+    const TO_INT: f64 = 6755399441055744.0;
+    const INV_PIO2: f64 = 6.36619772367581382433e-01; // 0x3fe45f30_6dc9c883
+    // pi/2 in three pieces, each of the first 33 bits of what's left and its remainder
+    const PIO2_1: f64 = 1.57079632673412561417e+00; // 0x3ff921fb_54400000
+    const PIO2_1T: f64 = 6.07710050650619224932e-11; // 0x3dd0b461_1a626331
+    const PIO2_2: f64 = 6.07710050630396597660e-11; // 0x3dd0b461_1a600000
+    const PIO2_2T: f64 = 2.02226624879595063154e-21; // 0x3ba3198a_2e037073
+    const PIO2_3: f64 = 2.02226624871116645580e-21; // 0x3ba3198a_2e000000
+    const PIO2_3T: f64 = 8.47842766036889956997e-32; // 0x397b839a_252049c1
+
+    let ix = (x.repr() >> 32) as u32 & 0x7fff_ffff;
+    if ix >= 0x4139_21fb {
+        // |x| ~>= 2^20*pi/2
+        return rem_pio2_large(x);
+    }
+
+    // `n*PIO2_1` is exact for `|n| < 2^20`. Each further piece is only needed when the
+    // subtraction cancels enough bits of the previous remainder.
+    let f = x * INV_PIO2 + TO_INT - TO_INT;
+    let n = f as i32;
+    let mut r = x - f * PIO2_1;
+    let mut w = f * PIO2_1T;
+    let mut y0 = r - w;
+    let ex = (ix >> 20) as i32;
+    let ey = (y0.repr() >> 52) as i32 & 0x7ff;
+    if ex - ey > 16 {
+        let t = r;
+        w = f * PIO2_2;
+        r = t - w;
+        w = f * PIO2_2T - ((t - r) - w);
+        y0 = r - w;
+        let ey = (y0.repr() >> 52) as i32 & 0x7ff;
+        if ex - ey > 49 {
+            let t = r;
+            w = f * PIO2_3;
+            r = t - w;
+            w = f * PIO2_3T - ((t - r) - w);
+            y0 = r - w;
+        }
+    }
+    let y1 = (r - y0) - w;
+    (n, y0, y1)
+}
+
+/// `rem_pio2` for `|x| >= 2^20*pi/2`, where the remainder is computed from `x*2/pi` in fixed
+/// point, with the bits of `2/pi` that `x` moves around the binary point
+fn rem_pio2_large(x: f64) -> (i32, f64, f64) {
+    // pi/2 as the sum of two doubles
+    const PIO2_HI: f64 = 1.57079632679489655800e+00; // 0x3ff921fb_54442d18
+    const PIO2_LO: f64 = 6.12323399573676603587e-17; // 0x3c91a626_33145c07
+    const TWO_M128: f64 = 2.93873587705571876992e-39; // 0x37f00000_00000000
+
+    let rep = x.repr();
+    let m = (rep & 0x000f_ffff_ffff_ffff) | 0x0010_0000_0000_0000;
+    // |x| = m*2^e
+    let e = (rep >> 52) as i32 & 0x7ff;
+    let e = e - 1075;
+
+    // The bits of 2/pi worth more than 2^(2 - e) only add multiples of 4 to `m*2^e*2/pi`, so the
+    // window of 192 bits starts right after them
+    let start = if e > 2 { (e - 2) as usize } else { 0 };
+    let (word, bit) = (start / 64, start % 64);
+    let mut window = [0u64; 3];
+    for (i, w) in window.iter_mut().rev().enumerate() {
+        *w = TWO_OVER_PI[word + i] << bit;
+        if bit != 0 {
+            *w |= TWO_OVER_PI[word + i + 1] >> (64 - bit);
+        }
+    }
+
+    // `product*2^-point` is `|x|*2/pi` modulo 4, with the bits of 2/pi below the window missing
+    let mut product = [0u64; 4];
+    let mut carry = 0u128;
+    for (p, &w) in product.iter_mut().zip(window.iter()) {
+        let t = m as u128 * w as u128 + carry;
+        *p = t as u64;
+        carry = t >> 64;
+    }
+    product[3] = carry as u64;
+    let point = (start as i32 + 192 - e) as usize;
+    let bits = |lo: usize| -> u128 {
+        let limb = |i: usize| product.get(i).map_or(0, |&p| p as u128);
+        let (i, shift) = (lo / 64, lo % 64);
+        let r = (limb(i + 1) << 64 | limb(i)) >> shift;
+        if shift == 0 { r } else { r | limb(i + 2) << (128 - shift) }
+    };
+
+    // The fraction, rounded to the nearest integer `n` so that it is in [-0.5, 0.5)
+    let mut n = bits(point) as i32 & 3;
+    let fraction = bits(point - 128) as i128;
+    if fraction < 0 {
+        n += 1;
+    }
+    let magnitude = if fraction < 0 {
+        (!fraction) as u128 + 1
+    } else {
+        fraction as u128
+    };
+    let hi = magnitude as f64;
+    let lo = magnitude.wrapping_sub(hi as u128) as i128 as f64;
+    let (hi, lo) = if fraction < 0 { (-hi, -lo) } else { (hi, lo) };
+
+    // (hi + lo)*2^-128*pi/2, in double-double arithmetic
+    let r_hi = hi * PIO2_HI;
+    let r_lo = fma(hi, PIO2_HI, -r_hi) + (hi * PIO2_LO + lo * PIO2_HI);
+    let (r_hi, r_lo) = (r_hi * TWO_M128, r_lo * TWO_M128);
+    let y0 = r_hi + r_lo;
+    let y1 = r_lo - (y0 - r_hi);
+
+    if rep >> 63 != 0 {
+        (-n, -y0, -y1)
+    } else {
+        (n, y0, y1)
+    }
+}
+
+/// The first 1280 bits of the fraction of 2/pi, which cover the largest double
+static TWO_OVER_PI: [u64; 20] = [
+    0xa2f9_836e_4e44_1529, 0xfc27_57d1_f534_ddc0, 0xdb62_9599_3c43_9041,
+    0xfe51_63ab_debb_c561, 0xb724_6e3a_424d_d2e0, 0x0649_2eea_09d1_921c,
+    0xfe1d_eb1c_b129_a73e, 0xe882_35f5_2ebb_4484, 0xe99c_7026_b45f_7e41,
+    0x3991_d639_8353_39f4, 0x9c84_5f8b_bdf9_283b, 0x1ff8_97ff_de05_980f,
+    0xef2f_118b_5a0a_6d1f, 0x6d36_7ecf_27cb_09b7, 0x4f46_3f66_9e5f_ea2d,
+    0x7527_bac7_ebe5_f17b, 0x3d07_39f7_8a52_92ea, 0x6bfb_5fb1_1f8d_5d08,
+    0x5603_3046_fc7b_6bab, 0xf0cf_bc20_9af4_361d,
+];
@@ -0,0 +1,184 @@
+use float::{f16, f128, Float};
+use float::exception;
+use float::flush;
+use float::nan::{self, Nan};
+use float::round;
+
+macro_rules! trunc {
+    ($trunc:ident: $src:ty => $dst:ty) => {
+        fn $trunc(a: $src) -> $dst {
+            let src_one: <$src as Float>::Int = 1;
+            let dst_one: <$dst as Float>::Int = 1;
+
+            let src_bits =         <$src>::bits();
+            let src_sig_bits =     <$src>::significand_bits();
+            let src_exp_bias =     <$src>::exponent_bias();
+            let src_min_normal =   <$src>::implicit_bit();
+            let src_sig_mask =     <$src>::significand_mask();
+            let src_infinity =     <$src>::exponent_mask();
+            let src_sign_mask =    <$src>::sign_mask();
+            let src_abs_mask =     src_sign_mask - src_one;
+            let src_qnan =         src_min_normal >> 1;
+            let src_nan_code =     src_qnan - src_one;
+
+            let dst_bits =         <$dst>::bits();
+            let dst_sig_bits =     <$dst>::significand_bits();
+            let dst_inf_exp =      <$dst>::exponent_max();
+            let dst_exp_bias =     <$dst>::exponent_bias();
+            let dst_qnan =         <$dst>::implicit_bit() >> 1;
+            let dst_nan_code =     dst_qnan - dst_one;
+
+            let sig_bits_delta = src_sig_bits - dst_sig_bits;
+            let round_mask = (src_one << sig_bits_delta) - src_one;
+            let halfway = src_one << (sig_bits_delta - 1);
+
+            // Biased source exponents of the smallest normal number and of the
+            // smallest number that overflows in the destination type
+            let underflow_exponent = src_exp_bias + 1 - dst_exp_bias;
+            let overflow_exponent = src_exp_bias + dst_inf_exp - dst_exp_bias;
+            let underflow = (underflow_exponent as <$src as Float>::Int) << src_sig_bits;
+            let overflow = (overflow_exponent as <$src as Float>::Int) << src_sig_bits;
+
+            // Break a into a sign and representation of the absolute value
+            let a_rep = a.repr();
+            let a_abs = a_rep & src_abs_mask;
+            let sign = a_rep & src_sign_mask;
+            let negative = sign == src_sign_mask;
+            let mode = round::mode();
+
+            let abs_result = if a_abs.wrapping_sub(underflow) < a_abs.wrapping_sub(overflow) {
+                // The exponent of a is within the range of normal numbers in the
+                // destination format.  We can convert by simply right-shifting with
+                // rounding and adjusting the exponent.
+                let mut abs_result = (a_abs >> sig_bits_delta) as <$dst as Float>::Int;
+                abs_result = abs_result.wrapping_sub(
+                    ((src_exp_bias - dst_exp_bias) as <$dst as Float>::Int) << dst_sig_bits);
+
+                let round_bits = a_abs & round_mask;
+                if mode.increment(negative,
+                                  abs_result & dst_one == dst_one,
+                                  round_bits & halfway == halfway,
+                                  round_bits & (halfway - src_one) != src_one - src_one) {
+                    abs_result += dst_one;
+                }
+                if round_bits != src_one - src_one {
+                    // Rounding may overflow to infinity
+                    if abs_result == (dst_inf_exp as <$dst as Float>::Int) << dst_sig_bits {
+                        exception::raise(exception::OVERFLOW | exception::INEXACT);
+                    } else {
+                        exception::raise(exception::INEXACT);
+                    }
+                }
+                abs_result
+            } else if a_abs > src_infinity {
+                // a is NaN.
+                // Conjure the result by beginning with infinity, setting the qNaN
+                // bit and inserting the (truncated) trailing NaN field.
+                if a_abs & src_qnan == src_one - src_one {
+                    exception::raise(exception::INVALID);
+                }
+                let mut abs_result = (dst_inf_exp as <$dst as Float>::Int) << dst_sig_bits;
+                abs_result |= dst_qnan;
+                if nan::mode() == Nan::Default {
+                    return <$dst>::from_repr(abs_result);
+                }
+                abs_result |
+                    ((a_abs & src_nan_code) >> sig_bits_delta) as <$dst as Float>::Int & dst_nan_code
+            } else if a_abs >= overflow {
+                // a overflows to infinity, or to the largest finite number if the rounding
+                // direction is toward zero for its sign.
+                let dst_infinity = (dst_inf_exp as <$dst as Float>::Int) << dst_sig_bits;
+                if a_abs != src_infinity {
+                    exception::raise(exception::OVERFLOW | exception::INEXACT);
+                }
+                if a_abs == src_infinity || mode.overflows_to_infinity(negative) {
+                    dst_infinity
+                } else {
+                    dst_infinity - dst_one
+                }
+            } else {
+                // a underflows on conversion to the destination type or is an exact
+                // zero.  The result may be a denormal or zero.  Extract the exponent
+                // to get the shift amount for the denormalization.
+                let a_exp = (a_abs >> src_sig_bits) as u32;
+                let shift = src_exp_bias - dst_exp_bias + 1 - a_exp;
+
+                let significand = (a_rep & src_sig_mask) | src_min_normal;
+
+                // Right shift by the denormalization amount with sticky.
+                if a_abs == src_one - src_one {
+                    0
+                } else if flush::enabled() {
+                    // The result is tiny, so it is flushed to zero, and so is a denormal a
+                    if a_exp != 0 {
+                        exception::raise(exception::UNDERFLOW);
+                    }
+                    0
+                } else if shift > src_sig_bits {
+                    // Less than half of the smallest denormal
+                    exception::raise(exception::UNDERFLOW | exception::INEXACT);
+                    mode.increment(negative, false, false, true) as <$dst as Float>::Int
+                } else {
+                    let sticky = (significand << (src_bits - shift) != 0) as <$src as Float>::Int;
+                    let denormalized_significand = significand >> shift | sticky;
+                    let mut abs_result =
+                        (denormalized_significand >> sig_bits_delta) as <$dst as Float>::Int;
+
+                    let round_bits = denormalized_significand & round_mask;
+                    if mode.increment(negative,
+                                      abs_result & dst_one == dst_one,
+                                      round_bits & halfway == halfway,
+                                      round_bits & (halfway - src_one) != src_one - src_one) {
+                        abs_result += dst_one;
+                    }
+                    if round_bits != src_one - src_one {
+                        exception::raise(exception::UNDERFLOW | exception::INEXACT);
+                    }
+                    abs_result
+                }
+            };
+
+            // Apply the signbit to (dst_t)abs(a).
+            <$dst>::from_repr(abs_result | (sign >> (src_bits - dst_bits)) as <$dst as Float>::Int)
+        }
+    }
+}
+
+trunc!(truncsfhf2: f32 => f16);
+trunc!(truncdfhf2: f64 => f16);
+trunc!(truncdfsf2: f64 => f32);
+trunc!(trunctfsf2: f128 => f32);
+trunc!(trunctfdf2: f128 => f64);
+
+intrinsic!(/// Returns the representation of `a` converted to half precision, rounded in the current
+           /// rounding mode
+           | __truncsfhf2(a: f32) -> u16 {
+               truncsfhf2(a).repr()
+           });
+
+intrinsic!(/// Returns the representation of `a` converted to half precision, rounded in the current
+           /// rounding mode. Alias of `__truncsfhf2` used by GCC
+           | __gnu_f2h_ieee(a: f32) -> u16 {
+               truncsfhf2(a).repr()
+           });
+
+intrinsic!(/// Returns the representation of `a` converted to half precision, rounded in the current
+           /// rounding mode
+           | __truncdfhf2(a: f64) -> u16 {
+               truncdfhf2(a).repr()
+           });
+
+intrinsic!(/// Returns `a` converted to `f32`, rounded in the current rounding mode
+           | __truncdfsf2(a: f64) -> f32 {
+               truncdfsf2(a)
+           });
+
+intrinsic!(/// Returns `a` converted to `f32`, rounded in the current rounding mode
+           | __trunctfsf2(a: f128) -> f32 {
+               trunctfsf2(a)
+           });
+
+intrinsic!(/// Returns `a` converted to `f64`, rounded in the current rounding mode
+           | __trunctfdf2(a: f128) -> f64 {
+               trunctfdf2(a)
+           });
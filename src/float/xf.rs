@@ -0,0 +1,272 @@
+use core::cmp;
+
+use float::f80;
+use float::nan;
+use float::round;
+use int::Int;
+
+// NOTE x87 `long double`s are passed on the stack and returned in `st(0)`, which can't be expressed
+// with a normal Rust function. The intrinsics themselves are assembly shims (see `x86.rs` and
+// `x86_64.rs`) around the functions below, which take and return `f80`s through pointers instead.
+// These are unmangled like the intrinsics because the shims call them by name
+
+const EXPONENT_BIAS: i32 = 16383;
+const EXPONENT_MAX: u16 = 0x7fff;
+const INTEGER_BIT: u64 = 1 << 63;
+const QUIET_BIT: u64 = 1 << 62;
+
+/// Weight of the last significand bit of denormals
+const EXPONENT_MIN: i32 = 1 - EXPONENT_BIAS - 63;
+
+fn is_nan(a: f80) -> bool {
+    a.exponent() == EXPONENT_MAX && a.significand() << 1 != 0
+}
+
+fn infinity(sign: bool) -> f80 {
+    f80::from_parts(sign, EXPONENT_MAX, INTEGER_BIT)
+}
+
+/// The "real indefinite" NaN the x87 returns for invalid operations
+fn indefinite() -> f80 {
+    f80::from_parts(true, EXPONENT_MAX, INTEGER_BIT | QUIET_BIT)
+}
+
+/// The NaN an operation returns for its first NaN operand `a`, see `float::nan`
+fn quiet(a: f80) -> f80 {
+    nan::propagate(f80::from_parts(a.sign(), a.exponent(), a.significand() | QUIET_BIT),
+                   indefinite())
+}
+
+/// Splits the finite `a` into a significand `m` and exponent `e` so that `|a| = m * 2^e`
+fn parts(a: f80) -> (u64, i32) {
+    (a.significand(), cmp::max(a.exponent() as i32, 1) + EXPONENT_MIN - 1)
+}
+
+/// Like `parts` but with the integer bit of the significand set
+fn normalized_parts(a: f80) -> (u64, i32) {
+    let (m, e) = parts(a);
+    let shift = m.leading_zeros();
+    (m << shift, e - shift as i32)
+}
+
+/// Returns `(-1)^sign * (m + t) * 2^e` rounded to an `f80` in the current rounding mode, where
+/// `t` is some number in `(0, 1)` if `sticky` is set and zero otherwise
+fn round(sign: bool, m: u128, sticky: bool, e: i32) -> f80 {
+    if m == 0 {
+        return f80::from_parts(sign, 0, 0);
+    }
+    let mode = round::mode();
+
+    let top = 127 - m.leading_zeros() as i32;
+    let mut ulp = cmp::max(top + e - 63, EXPONENT_MIN);
+    let shift = ulp - e;
+    let mut significand = if shift <= 0 {
+        m << -shift
+    } else if shift > top + 1 {
+        // Less than half of the smallest denormal
+        let round = mode.increment(sign, false, false, true);
+        return f80::from_parts(sign, 0, round as u64);
+    } else {
+        let half = m >> (shift - 1) & 1 != 0;
+        let rest = m & ((1 << (shift - 1)) - 1) != 0 || sticky;
+        let kept = if shift == 128 { 0 } else { m >> shift };
+        kept + mode.increment(sign, kept & 1 != 0, half, rest) as u128
+    };
+
+    if significand == 1 << 64 {
+        significand >>= 1;
+        ulp += 1;
+    }
+
+    let exponent = if significand & INTEGER_BIT as u128 == 0 {
+        0
+    } else {
+        ulp - EXPONENT_MIN + 1
+    };
+    if exponent >= EXPONENT_MAX as i32 {
+        return if mode.overflows_to_infinity(sign) {
+            infinity(sign)
+        } else {
+            f80::from_parts(sign, EXPONENT_MAX - 1, !0)
+        };
+    }
+    f80::from_parts(sign, exponent as u16, significand as u64)
+}
+
+fn mul(a: f80, b: f80) -> f80 {
+    let sign = a.sign() != b.sign();
+    if is_nan(a) {
+        return quiet(a);
+    }
+    if is_nan(b) {
+        return quiet(b);
+    }
+
+    let (a_significand, a_exponent) = parts(a);
+    let (b_significand, b_exponent) = parts(b);
+    let a_inf = a.exponent() == EXPONENT_MAX;
+    let b_inf = b.exponent() == EXPONENT_MAX;
+    if a_inf || b_inf {
+        // infinity * 0 = NaN
+        return if a_significand == 0 || b_significand == 0 {
+            indefinite()
+        } else {
+            infinity(sign)
+        };
+    }
+
+    round(sign,
+          a_significand as u128 * b_significand as u128,
+          false,
+          a_exponent + b_exponent)
+}
+
+/// Returns `a / b * 2^n`, rounded once
+fn div(a: f80, b: f80, n: i32) -> f80 {
+    let sign = a.sign() != b.sign();
+    if is_nan(a) {
+        return quiet(a);
+    }
+    if is_nan(b) {
+        return quiet(b);
+    }
+
+    let a_inf = a.exponent() == EXPONENT_MAX;
+    let b_inf = b.exponent() == EXPONENT_MAX;
+    let a_zero = a.significand() == 0;
+    let b_zero = b.significand() == 0;
+    if (a_inf && b_inf) || (a_zero && b_zero) {
+        return indefinite();
+    }
+    if a_inf || b_zero {
+        return infinity(sign);
+    }
+    if b_inf || a_zero {
+        return f80::from_parts(sign, 0, 0);
+    }
+
+    let (a_significand, a_exponent) = normalized_parts(a);
+    let (b_significand, b_exponent) = normalized_parts(b);
+
+    // Both significands are in [2^63, 2^64) so the quotient has 64 or 65 bits; two more bits
+    // leave room for the rounding bit
+    let b_significand = b_significand as u128;
+    let numerator = (a_significand as u128) << 64;
+    let mut quotient = numerator / b_significand;
+    let mut remainder = numerator % b_significand;
+    for _ in 0..2 {
+        quotient <<= 1;
+        remainder <<= 1;
+        if remainder >= b_significand {
+            remainder -= b_significand;
+            quotient |= 1;
+        }
+    }
+
+    round(sign, quotient, remainder != 0, a_exponent - b_exponent - 66 + n)
+}
+
+macro_rules! int_to_xf {
+    ($intrinsic:ident: $ity:ty) => {
+        #[cfg_attr(not(test), no_mangle)]
+        pub extern "C" fn $intrinsic(i: $ity, r: &mut f80) {
+            let (s, a) = i.extract_sign();
+            *r = round(s, a as u128, false, 0);
+        }
+    }
+}
+
+int_to_xf!(__rust_floatdixf: i64);
+int_to_xf!(__rust_floatundixf: u64);
+int_to_xf!(__rust_floattixf: i128);
+int_to_xf!(__rust_floatuntixf: u128);
+
+macro_rules! xf_to_int {
+    ($intrinsic:ident: $ity:ty) => {
+        #[cfg_attr(not(test), no_mangle)]
+        pub extern "C" fn $intrinsic(a: &f80) -> $ity {
+            let fixint_min = <$ity>::min_value();
+            let fixint_max = <$ity>::max_value();
+            let fixint_bits = <$ity>::bits() as i32;
+            let fixint_unsigned = fixint_min == 0;
+
+            let sign = a.sign();
+            let exponent = a.exponent() as i32 - EXPONENT_BIAS;
+            let significand = a.significand();
+
+            // if < 1 or unsigned & negative
+            if exponent < 0 || fixint_unsigned && sign {
+                return 0
+            }
+
+            // If the value is infinity or too large for the integer type, saturate
+            if exponent >= (if fixint_unsigned {fixint_bits} else {fixint_bits - 1}) {
+                return if sign {fixint_min} else {fixint_max}
+            }
+
+            // The integer bit is explicit, so the significand already is the integer part shifted
+            // left by `63 - exponent`
+            let r = if exponent < 63 {
+                (significand >> (63 - exponent)) as $ity
+            } else {
+                (significand as $ity) << (exponent - 63)
+            };
+
+            if sign {
+                (!r).wrapping_add(1)
+            } else {
+                r
+            }
+        }
+    }
+}
+
+xf_to_int!(__rust_fixxfdi: i64);
+xf_to_int!(__rust_fixxfti: i128);
+xf_to_int!(__rust_fixunsxfsi: u32);
+xf_to_int!(__rust_fixunsxfdi: u64);
+xf_to_int!(__rust_fixunsxfti: u128);
+
+/// Returns `a` raised to the power `b`, rounding after every multiplication like `__powixf2` does
+#[cfg_attr(not(test), no_mangle)]
+pub extern "C" fn __rust_powixf2(a: &f80, b: i32, r: &mut f80) {
+    let one = f80::from_parts(false, EXPONENT_BIAS as u16, INTEGER_BIT);
+    let (mut a, mut b) = (*a, b);
+    let recip = b < 0;
+
+    // Like in `__powisf2`, for a negative power `p` would overflow or underflow before it is
+    // inverted, so `p` and `a` are kept as fractions in [0.5, 1) and their exponents apart, in
+    // `p_exp` and `a_exp`. Zeros, infinities and NaNs are kept as they are.
+    let scale = |x: f80, exp: &mut i32| if recip && x.exponent() != EXPONENT_MAX &&
+                                           x.significand() != 0 {
+        let (m, e) = normalized_parts(x);
+        *exp = exp.saturating_add(e + 64);
+        f80::from_parts(x.sign(), EXPONENT_BIAS as u16 - 1, m)
+    } else {
+        x
+    };
+    let mut p = one;
+    let mut p_exp = 0i32;
+    let mut a_exp = 0i32;
+    a = scale(a, &mut a_exp);
+    loop {
+        if (b & 1) != 0 {
+            p_exp = p_exp.saturating_add(a_exp);
+            p = scale(mul(p, a), &mut p_exp);
+        }
+        b = sdiv!(i32, b, 2);
+        if b == 0 {
+            break;
+        }
+        a_exp = a_exp.saturating_mul(2);
+        a = scale(mul(a, a), &mut a_exp);
+    }
+
+    *r = if recip {
+        // `2^-p_exp / p`, rounded once. Past the clamp the quotient is far out of range anyway
+        let n = cmp::max(cmp::min(0i32.saturating_sub(p_exp), 1 << 20), -(1 << 20));
+        div(one, p, n)
+    } else {
+        p
+    };
+}
@@ -11,6 +11,7 @@
 #![feature(asm)]
 #![feature(compiler_builtins)]
 #![feature(core_intrinsics)]
+#![feature(linkage)]
 #![feature(naked_functions)]
 #![feature(staged_api)]
 #![feature(i128_type)]
@@ -124,5 +125,8 @@ pub mod mem;
 #[cfg(target_arch = "arm")]
 pub mod arm;
 
+#[cfg(all(target_arch = "x86", not(target_env = "msvc")))]
+pub mod x86;
+
 #[cfg(target_arch = "x86_64")]
 pub mod x86_64;
@@ -0,0 +1,92 @@
+use core::intrinsics;
+
+// NOTE These functions are implemented using assembly because the x87 extended precision
+// `long double`s they take are passed on the stack and returned in `st(0)`, which can't be
+// expressed with a normal Rust function. The actual work happens in `float::xf`
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __floatdixf() {
+    asm!("sub    $$44,%esp
+          mov    48(%esp),%eax
+          mov    %eax,(%esp)
+          mov    52(%esp),%eax
+          mov    %eax,4(%esp)
+          lea    16(%esp),%eax  // pointer to the result
+          mov    %eax,8(%esp)
+          call   __rust_floatdixf
+          fldt   16(%esp)
+          add    $$44,%esp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __floatundixf() {
+    asm!("sub    $$44,%esp
+          mov    48(%esp),%eax
+          mov    %eax,(%esp)
+          mov    52(%esp),%eax
+          mov    %eax,4(%esp)
+          lea    16(%esp),%eax
+          mov    %eax,8(%esp)
+          call   __rust_floatundixf
+          fldt   16(%esp)
+          add    $$44,%esp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixxfdi() {
+    asm!("sub    $$28,%esp
+          lea    32(%esp),%eax  // pointer to the argument
+          mov    %eax,(%esp)
+          call   __rust_fixxfdi
+          add    $$28,%esp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixunsxfsi() {
+    asm!("sub    $$28,%esp
+          lea    32(%esp),%eax
+          mov    %eax,(%esp)
+          call   __rust_fixunsxfsi
+          add    $$28,%esp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixunsxfdi() {
+    asm!("sub    $$28,%esp
+          lea    32(%esp),%eax
+          mov    %eax,(%esp)
+          call   __rust_fixunsxfdi
+          add    $$28,%esp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __powixf2() {
+    asm!("sub    $$44,%esp
+          lea    48(%esp),%eax  // pointer to the base
+          mov    %eax,(%esp)
+          mov    60(%esp),%eax  // the exponent
+          mov    %eax,4(%esp)
+          lea    16(%esp),%eax  // pointer to the result
+          mov    %eax,8(%esp)
+          call   __rust_powixf2
+          fldt   16(%esp)
+          add    $$44,%esp
+          ret");
+    intrinsics::unreachable();
+}
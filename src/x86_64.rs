@@ -67,3 +67,118 @@ pub unsafe fn ___chkstk() {
           ret");
     intrinsics::unreachable();
 }
+
+// NOTE The x87 extended precision intrinsics below take `long double`s on the stack and return them
+// in `st(0)`; the actual work happens in `float::xf`
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __floatdixf() {
+    asm!("sub    $$24,%rsp
+          mov    %rsp,%rsi
+          call   __rust_floatdixf
+          fldt   (%rsp)
+          add    $$24,%rsp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __floatundixf() {
+    asm!("sub    $$24,%rsp
+          mov    %rsp,%rsi
+          call   __rust_floatundixf
+          fldt   (%rsp)
+          add    $$24,%rsp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __floattixf() {
+    asm!("sub    $$24,%rsp
+          mov    %rsp,%rdx
+          call   __rust_floattixf
+          fldt   (%rsp)
+          add    $$24,%rsp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __floatuntixf() {
+    asm!("sub    $$24,%rsp
+          mov    %rsp,%rdx
+          call   __rust_floatuntixf
+          fldt   (%rsp)
+          add    $$24,%rsp
+          ret");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixxfdi() {
+    asm!("lea    8(%rsp),%rdi  // pointer to the argument
+          jmp    __rust_fixxfdi");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixxfti() {
+    asm!("lea    8(%rsp),%rdi
+          jmp    __rust_fixxfti");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixunsxfsi() {
+    asm!("lea    8(%rsp),%rdi
+          jmp    __rust_fixunsxfsi");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixunsxfdi() {
+    asm!("lea    8(%rsp),%rdi
+          jmp    __rust_fixunsxfdi");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __fixunsxfti() {
+    asm!("lea    8(%rsp),%rdi
+          jmp    __rust_fixunsxfti");
+    intrinsics::unreachable();
+}
+
+#[cfg(not(target_env = "msvc"))]
+#[naked]
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe fn __powixf2() {
+    asm!("sub    $$24,%rsp
+          mov    %edi,%esi      // the exponent
+          lea    32(%rsp),%rdi  // pointer to the base
+          mov    %rsp,%rdx
+          call   __rust_powixf2
+          fldt   (%rsp)
+          add    $$24,%rsp
+          ret");
+    intrinsics::unreachable();
+}
@@ -0,0 +1,14 @@
+// The Rust versions only exist on these targets, see `float::complex`
+#![cfg(any(target_arch = "aarch64",
+           target_arch = "arm",
+           target_arch = "mips64",
+           all(target_arch = "powerpc64", target_endian = "little"),
+           target_arch = "x86_64"))]
+#![feature(compiler_builtins_lib)]
+#![feature(i128_type)]
+#![cfg_attr(all(target_arch = "arm",
+                not(any(target_env = "gnu", target_env = "musl")),
+                target_os = "linux",
+                test), no_std)]
+
+include!(concat!(env!("OUT_DIR"), "/divsc3.rs"));